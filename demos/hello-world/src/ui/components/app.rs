@@ -14,7 +14,7 @@ pub fn app(context: WidgetContext) -> WidgetNode {
         })
     });
     let props = Props::new(VerticalBoxProps {
-        separation: 16.0,
+        separation: 16.0.into(),
         ..Default::default()
     })
     .with(NavJumpLooped);
@@ -49,7 +49,7 @@ pub fn popup(mut context: WidgetContext) -> WidgetNode {
             ..Default::default()
         }))
         .with(VerticalBoxProps {
-            separation: 10.0,
+            separation: 10.0.into(),
             ..Default::default()
         });
     let image_props = Props::new(ImageBoxProps {
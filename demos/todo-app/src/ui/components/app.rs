@@ -171,7 +171,7 @@ pub fn app(mut context: WidgetContext) -> WidgetNode {
     };
 
     let list_props = VerticalBoxProps {
-        separation: 10.0,
+        separation: 10.0.into(),
         ..Default::default()
     };
 
@@ -80,12 +80,12 @@ pub fn app_bar(mut context: WidgetContext) -> WidgetNode {
     let theme_mode = shared_props.read_cloned_or_default::<ThemeMode>();
 
     let props = props.clone().with(VerticalBoxProps {
-        separation: 10.0,
+        separation: 10.0.into(),
         ..Default::default()
     });
 
     let line_props = props.clone().with(HorizontalBoxProps {
-        separation: 10.0,
+        separation: 10.0.into(),
         ..Default::default()
     });
 
@@ -78,7 +78,7 @@ pub fn confirm_box(mut context: WidgetContext) -> WidgetNode {
         ..Default::default()
     })
     .with(VerticalBoxProps {
-        separation: 20.0,
+        separation: 20.0.into(),
         ..Default::default()
     });
 
@@ -143,7 +143,7 @@ pub fn task(mut context: WidgetContext) -> WidgetNode {
     });
 
     let list_props = Props::new(HorizontalBoxProps {
-        separation: 10.0,
+        separation: 10.0.into(),
         ..Default::default()
     })
     .with(ContentBoxItemLayout {
@@ -205,7 +205,7 @@ pub fn tasks_list(context: WidgetContext) -> WidgetNode {
         }));
 
     let list_props = VerticalBoxProps {
-        separation: 30.0,
+        separation: 30.0.into(),
         ..Default::default()
     };
 
@@ -17,7 +17,7 @@ fn app(ctx: WidgetContext) -> WidgetNode {
         .listed_slot(
             make_widget!(horizontal_box)
                 .with_props(HorizontalBoxProps {
-                    separation: 25.0,
+                    separation: 25.0.into(),
                     ..Default::default()
                 })
                 .listed_slot(
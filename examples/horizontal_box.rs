@@ -4,7 +4,7 @@ use raui_quick_start::RauiQuickStartBuilder;
 fn main() {
     let tree = make_widget!(horizontal_box)
         .with_props(HorizontalBoxProps {
-            separation: 50.0,
+            separation: 50.0.into(),
             ..Default::default()
         })
         .listed_slot(
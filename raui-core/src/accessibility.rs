@@ -0,0 +1,201 @@
+//! Accessibility data
+//!
+//! Components declare what they are to assistive technologies by putting [`A11yProps`] into their
+//! props. The [`Application`][crate::application::Application] then assembles them with the layout
+//! into a tree of [`A11yNode`]s with [`accessibility_tree`][accessibility_tree], which hosts can
+//! bridge to platform accessibility APIs (e.g. screen readers).
+//!
+//! Widgets without [`A11yProps`] are transparent: their accessible descendants become children of
+//! their nearest accessible ancestor.
+//!
+//! [accessibility_tree]: crate::application::Application::accessibility_tree
+
+use crate::{
+    widget::{utils::Rect, WidgetId},
+    PropsData,
+};
+use serde::{Deserialize, Serialize};
+
+fn is_empty(v: &str) -> bool {
+    v.is_empty()
+}
+
+/// What kind of element accessible widget is
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum A11yRole {
+    Group,
+    Window,
+    Dialog,
+    Button,
+    CheckBox,
+    Text,
+    TextInput,
+    Image,
+    Slider,
+    List,
+    ListItem,
+    TabList,
+    Tab,
+    ScrollView,
+    Custom(String),
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for A11yRole {
+    fn default() -> Self {
+        Self::Group
+    }
+}
+
+#[derive(PropsData, Debug, Default, Clone, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub struct A11yProps {
+    #[serde(default)]
+    pub role: A11yRole,
+    /// Name read to the user, e.g. button text.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_empty")]
+    pub label: String,
+    /// Current value, e.g. text input content or slider position.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_empty")]
+    pub value: String,
+}
+
+impl A11yProps {
+    pub fn new(role: A11yRole, label: impl ToString) -> Self {
+        Self {
+            role,
+            label: label.to_string(),
+            value: Default::default(),
+        }
+    }
+
+    pub fn value(mut self, value: impl ToString) -> Self {
+        self.value = value.to_string();
+        self
+    }
+}
+
+/// Node of accessibility tree
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct A11yNode {
+    /// Id of the rendered widget unit the node describes (default for the tree root).
+    #[serde(default)]
+    pub id: WidgetId,
+    #[serde(default)]
+    pub role: A11yRole,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_empty")]
+    pub label: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_empty")]
+    pub value: String,
+    /// Area in UI space (the same as layout `ui_space`).
+    #[serde(default)]
+    pub bounds: Rect,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<A11yNode>,
+}
+
+impl A11yNode {
+    /// Finds the first node (in pre-order) with given label.
+    pub fn find_by_label(&self, label: &str) -> Option<&A11yNode> {
+        if self.label == label {
+            return Some(self);
+        }
+        self.children
+            .iter()
+            .find_map(|child| child.find_by_label(label))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        layout::{default_layout_engine::DefaultLayoutEngine, CoordsMapping},
+        props::Props,
+        widget,
+        widget::{
+            component::{
+                containers::{
+                    content_box::content_box,
+                    size_box::{size_box, SizeBoxProps},
+                },
+                interactive::button::button,
+                text_box::{text_box, TextBoxProps},
+            },
+            unit::{content::ContentBoxItemLayout, size::SizeBoxSizeValue},
+        },
+    };
+
+    #[test]
+    fn test_accessibility_tree() {
+        let button_props =
+            Props::new(A11yProps::new(A11yRole::Button, "OK")).with(ContentBoxItemLayout {
+                anchors: Rect {
+                    left: 0.5,
+                    right: 1.0,
+                    top: 0.0,
+                    bottom: 0.5,
+                },
+                ..Default::default()
+            });
+        let size_props = SizeBoxProps {
+            width: SizeBoxSizeValue::Fill,
+            height: SizeBoxSizeValue::Fill,
+            ..Default::default()
+        };
+        let text_props =
+            Props::new(A11yProps::new(A11yRole::Text, "Save changes?")).with(TextBoxProps {
+                text: "Save changes?".to_owned(),
+                ..Default::default()
+            });
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"app"} content_box [
+                (#{"title"} text_box: {text_props})
+                (#{"ok"} button: {button_props} {
+                    content = (#{"size"} size_box: {size_props})
+                })
+            ])
+        });
+        application.process();
+        let mapping = CoordsMapping::new(Rect {
+            left: 0.0,
+            right: 200.0,
+            top: 0.0,
+            bottom: 100.0,
+        });
+        application
+            .layout(&mapping, &mut DefaultLayoutEngine)
+            .unwrap();
+
+        let tree = application.accessibility_tree();
+        assert_eq!(tree.role, A11yRole::Window);
+        assert_eq!(tree.bounds, Rect::from((200.0, 100.0)));
+        assert_eq!(
+            tree.children
+                .iter()
+                .map(|node| (node.role.to_owned(), node.label.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(A11yRole::Text, "Save changes?"), (A11yRole::Button, "OK")]
+        );
+        let node = tree.find_by_label("OK").unwrap();
+        assert_eq!(node.id.key(), "ok");
+        assert!(node.children.is_empty());
+        assert_eq!(
+            node.bounds,
+            Rect {
+                left: 100.0,
+                right: 200.0,
+                top: 0.0,
+                bottom: 50.0,
+            }
+        );
+    }
+}
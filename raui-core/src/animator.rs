@@ -7,7 +7,7 @@
 //! See [`Animator`] and [`AnimatorStates`] for code samples.
 //!
 //! [`WidgetContext`]: crate::widget::context::WidgetContext
-use crate::{messenger::MessageSender, widget::WidgetId, MessageData, Scalar};
+use crate::{messenger::MessageSender, widget::WidgetId, MessageData, PropsData, Scalar};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::mpsc::Sender};
 
@@ -22,15 +22,35 @@ pub enum AnimationError {
 /// Handle to an animation sending channel used internally to update widget animations values in
 /// lifecycle hooks
 #[derive(Clone)]
-pub(crate) struct AnimationUpdate(Sender<(String, Option<Animation>)>);
+pub(crate) struct AnimationUpdate(Sender<(String, AnimationUpdateAction)>);
+
+/// What gets done with widget animation when lifecycle hook updates it
+#[derive(Debug, Clone)]
+pub(crate) enum AnimationUpdateAction {
+    Change(Option<Animation>),
+    Pause(bool),
+    Seek(Scalar),
+}
 
 impl AnimationUpdate {
-    pub fn new(sender: Sender<(String, Option<Animation>)>) -> Self {
+    pub fn new(sender: Sender<(String, AnimationUpdateAction)>) -> Self {
         Self(sender)
     }
 
     pub fn change(&self, name: &str, data: Option<Animation>) -> Result<(), AnimationError> {
-        if self.0.send((name.to_owned(), data)).is_err() {
+        self.send(name, AnimationUpdateAction::Change(data))
+    }
+
+    pub fn pause(&self, name: &str, paused: bool) -> Result<(), AnimationError> {
+        self.send(name, AnimationUpdateAction::Pause(paused))
+    }
+
+    pub fn seek(&self, name: &str, time: Scalar) -> Result<(), AnimationError> {
+        self.send(name, AnimationUpdateAction::Seek(time))
+    }
+
+    fn send(&self, name: &str, action: AnimationUpdateAction) -> Result<(), AnimationError> {
+        if self.0.send((name.to_owned(), action)).is_err() {
             Err(AnimationError::CouldNotWriteData)
         } else {
             Ok(())
@@ -71,13 +91,33 @@ impl AnimationUpdate {
 pub struct Animator<'a> {
     states: &'a AnimatorStates,
     update: AnimationUpdate,
+    delta_time: Scalar,
 }
 
 impl<'a> Animator<'a> {
     /// Create a new [`Animator`]
     #[inline]
-    pub(crate) fn new(states: &'a AnimatorStates, update: AnimationUpdate) -> Self {
-        Self { states, update }
+    pub(crate) fn new(
+        states: &'a AnimatorStates,
+        update: AnimationUpdate,
+        delta_time: Scalar,
+    ) -> Self {
+        Self {
+            states,
+            update,
+            delta_time,
+        }
+    }
+
+    /// Get the time that has passed since the previous application process
+    ///
+    /// This is the accumulated [`animations_delta_time`] that animations got advanced by in the
+    /// current process, useful for hooks that simulate motion frame by frame.
+    ///
+    /// [`animations_delta_time`]: crate::application::Application::animations_delta_time
+    #[inline]
+    pub fn delta_time(&self) -> Scalar {
+        self.delta_time
     }
 
     /// Check whether or not the widget has an animation with the given `anim_id`
@@ -96,6 +136,30 @@ impl<'a> Animator<'a> {
         self.update.change(anim_id, animation)
     }
 
+    /// Pause or resume the animation associated to a given `anim_id`
+    ///
+    /// Paused animation keeps its progress without advancing until it gets resumed.
+    #[inline]
+    pub fn set_animation_paused(&self, anim_id: &str, paused: bool) -> Result<(), AnimationError> {
+        self.update.pause(anim_id, paused)
+    }
+
+    /// Pause or resume all animations of the widget
+    pub fn set_paused(&self, paused: bool) -> Result<(), AnimationError> {
+        for anim_id in self.states.0.keys() {
+            self.update.pause(anim_id, paused)?;
+        }
+        Ok(())
+    }
+
+    /// Move the animation associated to a given `anim_id` to given `time`
+    ///
+    /// Values progress is recomputed for that time, messages of skipped part are not sent.
+    #[inline]
+    pub fn seek(&self, anim_id: &str, time: Scalar) -> Result<(), AnimationError> {
+        self.update.seek(anim_id, time)
+    }
+
     /// Get the current progress of the animation of a given value
     ///
     /// This will return [`None`] if the value is not currently being animated.
@@ -207,10 +271,33 @@ impl AnimatorStates {
         self.0.values().any(|s| s.in_progress())
     }
 
-    /// Returns `true` if none of this component's animations are currently running
+    /// Returns `true` if all of this component's animations are finished
+    ///
+    /// Paused animations are neither in-progress nor done.
     #[inline]
     pub fn is_done(&self) -> bool {
-        !self.in_progress()
+        self.0.values().all(|s| s.is_done())
+    }
+
+    /// Pause or resume all animations of this component
+    pub fn set_paused(&mut self, paused: bool) {
+        for state in self.0.values_mut() {
+            state.set_paused(paused);
+        }
+    }
+
+    /// Pause or resume the animation with the given `anim_id`
+    pub fn set_animation_paused(&mut self, anim_id: &str, paused: bool) {
+        if let Some(state) = self.0.get_mut(anim_id) {
+            state.set_paused(paused);
+        }
+    }
+
+    /// Move the animation with the given `anim_id` to given `time`
+    pub fn seek(&mut self, anim_id: &str, time: Scalar) {
+        if let Some(state) = self.0.get_mut(anim_id) {
+            state.seek(time);
+        }
     }
 
     /// Returns true if the widget has an animation with the given `anim_id`
@@ -308,6 +395,8 @@ pub struct AnimatorState {
     duration: Scalar,
     #[serde(default)]
     looped: bool,
+    #[serde(default)]
+    paused: bool,
 }
 
 impl AnimatorState {
@@ -322,19 +411,37 @@ impl AnimatorState {
             time: 0.0,
             duration: time,
             looped,
+            paused: false,
         }
     }
 
-    /// Returns whether or not the animations is in-progress
+    /// Returns whether or not the animations is in-progress (not done and not paused)
     #[inline]
     pub fn in_progress(&self) -> bool {
-        self.looped || (self.time <= self.duration && !self.sheet.is_empty())
+        !self.paused && !self.is_done()
     }
 
-    /// Returns `true` if this animation is not in-progress
+    /// Returns `true` if this animation has finished
     #[inline]
     pub fn is_done(&self) -> bool {
-        !self.in_progress()
+        !self.looped && (self.time > self.duration || self.sheet.is_empty())
+    }
+
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Pause or resume this animation
+    #[inline]
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Move this animation to given `time` (clamped to its duration) and recompute values progress
+    pub fn seek(&mut self, time: Scalar) {
+        self.time = time.max(0.0).min(self.duration);
+        self.update_phases();
     }
 
     /// Get the current progress of the animation of a given value
@@ -379,20 +486,13 @@ impl AnimatorState {
         owner: &WidgetId,
         message_sender: &MessageSender,
     ) {
-        if delta_time > 0.0 {
+        if delta_time > 0.0 && !self.paused {
             if self.looped && self.time > self.duration {
                 self.time = 0.0;
             }
             let old_time = self.time;
             self.time += delta_time;
-            for phase in self.sheet.values_mut() {
-                phase.cached_time = (self.time - phase.start).min(phase.duration).max(0.0);
-                phase.cached_progress = if phase.duration > 0.0 {
-                    phase.cached_time / phase.duration
-                } else {
-                    0.0
-                };
-            }
+            self.update_phases();
             for (time, message) in &self.messages {
                 if *time >= old_time && *time < self.time {
                     message_sender.write(owner.to_owned(), AnimationMessage(message.to_owned()));
@@ -401,6 +501,17 @@ impl AnimatorState {
         }
     }
 
+    fn update_phases(&mut self) {
+        for phase in self.sheet.values_mut() {
+            phase.cached_time = (self.time - phase.start).min(phase.duration).max(0.0);
+            phase.cached_progress = if phase.duration > 0.0 {
+                phase.cached_time / phase.duration
+            } else {
+                0.0
+            };
+        }
+    }
+
     // Add an animation to this [`AnimatorState`] recursively
     fn include_animation(
         animation: Animation,
@@ -469,7 +580,7 @@ struct AnimationPhase {
 /// to track the _progress_ of an animated value using the
 /// [`value_progress`][AnimatorStates::value_progress] function. This allows you to use the progress
 /// to calculate how to interpolate the real values when you build your widget.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Animation {
     /// A single animated value with a name and a duration
     Value(AnimatedValue),
@@ -492,7 +603,7 @@ impl Default for Animation {
 }
 
 /// A single, animated value with a name and a duration
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AnimatedValue {
     /// The name of the animated value
     ///
@@ -505,6 +616,41 @@ pub struct AnimatedValue {
     pub duration: Scalar,
 }
 
+/// Animations defined in widget props, started by the application when widget gets mounted
+///
+/// Keys are `anim_id`s the animations are registered with, so they can be read (and changed) in
+/// widget hooks the same way as animations started with [`Animator::change`]. Hooks run after
+/// these got registered, so mount hooks can still replace them.
+///
+/// # Example
+///
+/// ```
+/// # use raui_core::prelude::*;
+/// let prefab = AnimationPrefab::default().with(
+///     "appear",
+///     Animation::Value(AnimatedValue {
+///         name: "alpha".to_owned(),
+///         duration: 0.5,
+///     }),
+/// );
+/// let tree = widget! { (image_box: {prefab}) };
+/// ```
+#[derive(PropsData, Debug, Default, Clone, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub struct AnimationPrefab {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub animations: HashMap<String, Animation>,
+}
+
+impl AnimationPrefab {
+    pub fn with(mut self, anim_id: impl ToString, animation: Animation) -> Self {
+        self.animations.insert(anim_id.to_string(), animation);
+        self
+    }
+}
+
 /// A [`MessageData`][crate::messenger::MessageData] implementation sent by running an
 /// [`Animation::Message`] animation
 #[derive(MessageData, Debug, Default, Clone)]
@@ -514,8 +660,90 @@ pub struct AnimationMessage(pub String);
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        application::Application,
+        pre_hooks,
+        props::Props,
+        widget,
+        widget::{context::WidgetContext, node::WidgetNode},
+    };
     use std::{str::FromStr, sync::mpsc::channel};
 
+    #[derive(MessageData, Debug, Clone)]
+    #[message_data(crate::messenger::MessageData)]
+    struct Progress(Scalar);
+
+    #[derive(MessageData, Debug, Clone)]
+    #[message_data(crate::messenger::MessageData)]
+    enum Control {
+        Pause(bool),
+        Seek(Scalar),
+    }
+
+    fn use_progress(context: &mut WidgetContext) {
+        context.life_cycle.change(|context| {
+            for msg in context.messenger.messages {
+                match msg.as_any().downcast_ref() {
+                    Some(Control::Pause(paused)) => {
+                        let _ = context.animator.set_paused(*paused);
+                    }
+                    Some(Control::Seek(time)) => {
+                        let _ = context.animator.seek("appear", *time);
+                    }
+                    None => {}
+                }
+            }
+            if let Some(factor) = context.animator.value_progress_factor("appear", "alpha") {
+                context.signals.write(Progress(factor));
+            }
+        });
+    }
+
+    #[pre_hooks(use_progress)]
+    fn animated(mut context: WidgetContext) -> WidgetNode {
+        widget! {()}
+    }
+
+    fn prefab() -> AnimationPrefab {
+        AnimationPrefab::default().with(
+            "appear",
+            Animation::Sequence(vec![
+                Animation::Value(AnimatedValue {
+                    name: "alpha".to_owned(),
+                    duration: 1.0,
+                }),
+                Animation::Message("appeared".to_owned()),
+            ]),
+        )
+    }
+
+    #[test]
+    fn test_animation_prefab_serialization() {
+        let mut application = Application::new();
+        application.setup(widget::setup);
+        let data = application.serialize_props(&Props::new(prefab())).unwrap();
+        let props = application.deserialize_props(data).unwrap();
+        assert_eq!(
+            props.read::<AnimationPrefab>().unwrap().animations,
+            prefab().animations
+        );
+    }
+
+    #[test]
+    fn test_animation_prefab_mount() {
+        let mut application = Application::new();
+        application.apply(widget! { (#{"animated"} animated: {prefab()}) });
+        application.process();
+        application.animations_delta_time = 0.5;
+        application.process();
+        let progress = application
+            .signals()
+            .iter()
+            .find_map(|(_, msg)| msg.as_any().downcast_ref::<Progress>())
+            .unwrap();
+        assert!((progress.0 - 0.5).abs() < 1.0e-6);
+    }
+
     #[test]
     fn test_animator() {
         let animation = Animation::Sequence(vec![
@@ -554,4 +782,64 @@ mod tests {
                 .unwrap()
         );
     }
+
+    fn progress(application: &Application) -> Scalar {
+        let id = WidgetId::new("animated", &["animated".to_owned()]);
+        application
+            .animator_states(&id)
+            .unwrap()
+            .value_progress_factor("appear", "alpha")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_animation_pause() {
+        let id = WidgetId::new("animated", &["animated".to_owned()]);
+        let mut application = Application::new();
+        application.apply(widget! { (#{"animated"} animated: {prefab()}) });
+        application.process();
+        application.animations_delta_time = 0.25;
+        application.process();
+        assert!((progress(&application) - 0.25).abs() < 1.0e-6);
+
+        application.send_message(&id, Control::Pause(true));
+        assert!(application.process());
+        assert!((progress(&application) - 0.5).abs() < 1.0e-6);
+        for _ in 0..3 {
+            assert!(!application.process());
+            application.mark_dirty();
+            assert!(application.process());
+        }
+        let states = application.animator_states(&id).unwrap();
+        assert!((progress(&application) - 0.5).abs() < 1.0e-6);
+        assert!(!states.in_progress());
+        assert!(!states.is_done());
+
+        application
+            .animator_states_mut(&id)
+            .unwrap()
+            .set_paused(false);
+        assert!(application.process());
+        assert!((progress(&application) - 0.75).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn test_animation_seek() {
+        let id = WidgetId::new("animated", &["animated".to_owned()]);
+        let mut application = Application::new();
+        application.apply(widget! { (#{"animated"} animated: {prefab()}) });
+        application.process();
+        application.send_message(&id, Control::Seek(0.4));
+        application.process();
+        assert!((progress(&application) - 0.4).abs() < 1.0e-6);
+
+        application
+            .animator_states_mut(&id)
+            .unwrap()
+            .seek("appear", 0.5);
+        assert!((progress(&application) - 0.5).abs() < 1.0e-6);
+        application.animations_delta_time = 0.25;
+        application.process();
+        assert!((progress(&application) - 0.75).abs() < 1.0e-6);
+    }
 }
@@ -93,16 +93,25 @@
 //! ```
 
 use crate::{
-    animator::{AnimationUpdate, Animator, AnimatorStates},
+    accessibility::{A11yNode, A11yProps, A11yRole},
+    animator::{
+        AnimatedValue, Animation, AnimationPrefab, AnimationUpdate, AnimationUpdateAction,
+        Animator, AnimatorStates,
+    },
     interactive::InteractionsEngine,
     layout::{CoordsMapping, Layout, LayoutEngine},
-    messenger::{Message, MessageData, MessageSender, Messages, Messenger},
-    props::{Props, PropsData, PropsRegistry},
-    renderer::Renderer,
-    signals::{Signal, SignalSender},
+    messenger::{DelayedMessage, Message, MessageData, MessageSender, Messages, Messenger},
+    props::{Props, PropsData, PropsGroupPrefab, PropsRegistration, PropsRegistry},
+    renderer::{Renderer, SnapshotRenderer},
+    signals::{
+        LayoutSignal, PendingLayoutSignal, Signal, SignalRequest, SignalResponse, SignalSender,
+    },
     state::{State, StateUpdate},
     widget::{
-        component::{WidgetComponent, WidgetComponentPrefab},
+        component::{
+            error_boundary::ErrorBoundaryMarker, fragment::expand_fragments, DeferredSlot,
+            IntervalMessage, WidgetComponent, WidgetComponentPrefab,
+        },
         context::{WidgetContext, WidgetMountOrChangeContext, WidgetUnmountContext},
         node::{WidgetNode, WidgetNodePrefab},
         unit::{
@@ -126,18 +135,21 @@ use crate::{
             text::{TextBoxNode, TextBoxNodePrefab},
             WidgetUnit, WidgetUnitNode, WidgetUnitNodePrefab,
         },
-        FnWidget, WidgetId, WidgetLifeCycle,
+        utils::{Rect, Transform, Vec2},
+        FnWidget, WidgetEffectCleanup, WidgetId, WidgetLifeCycle,
     },
-    Prefab, PrefabError, PrefabValue, Scalar,
+    LogKind, Logger, Prefab, PrefabError, PrefabValue, Scalar,
 };
+use serde::{Deserialize, Serialize};
 use std::{
     any::{Any, TypeId},
     collections::{HashMap, HashSet},
     convert::TryInto,
+    panic::{catch_unwind, AssertUnwindSafe},
     sync::{
         atomic::{AtomicBool, Ordering},
         mpsc::{channel, Sender},
-        Arc,
+        Arc, Weak,
     },
 };
 
@@ -163,6 +175,24 @@ impl ChangeNotifier {
     pub fn consume_change(&mut self) -> bool {
         self.0.swap(false, Ordering::Relaxed)
     }
+
+    /// Make [`WeakChangeNotifier`] that does not keep this notifier alive
+    pub fn downgrade(&self) -> WeakChangeNotifier {
+        WeakChangeNotifier(Arc::downgrade(&self.0))
+    }
+}
+
+/// Weak version of [`ChangeNotifier`], obtained with [`ChangeNotifier::downgrade`]
+///
+/// It can be upgraded back into [`ChangeNotifier`] as long as any strong notifier is still alive.
+#[derive(Debug, Default, Clone)]
+pub struct WeakChangeNotifier(Weak<AtomicBool>);
+
+impl WeakChangeNotifier {
+    /// Get [`ChangeNotifier`] back, or [`None`] if all strong notifiers were dropped
+    pub fn upgrade(&self) -> Option<ChangeNotifier> {
+        self.0.upgrade().map(ChangeNotifier)
+    }
 }
 
 /// Errors that can occur while interacting with an application
@@ -178,6 +208,41 @@ impl From<PrefabError> for ApplicationError {
     }
 }
 
+/// Node prefab made by [`Application::serialize_node_deduplicated`]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct DeduplicatedNodePrefab {
+    /// Props that repeat in the node, referenced by their index in place of props.
+    #[serde(default)]
+    props_table: Vec<PrefabValue>,
+    #[serde(default)]
+    node: PrefabValue,
+}
+
+impl Prefab for DeduplicatedNodePrefab {}
+
+/// Problem found by [`Application::validate_prefab`]
+///
+/// `path` points to the widget node in the prefab tree, e.g. `/named_slots/content/items/0/slot`.
+#[derive(Debug, Clone)]
+pub enum PrefabValidationError {
+    /// Part of prefab could not be read
+    Prefab { path: String, error: PrefabError },
+    /// Component type is not registered in application
+    ComponentMappingNotFound { path: String, type_name: String },
+    /// Props type is not registered in application
+    PropsNotRegistered { path: String, name: String },
+}
+
+impl PrefabValidationError {
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Prefab { path, .. }
+            | Self::ComponentMappingNotFound { path, .. }
+            | Self::PropsNotRegistered { path, .. } => path,
+        }
+    }
+}
+
 /// Indicates the reason that an [`Application`] state was invalidated and had to be re-rendered
 ///
 /// You can get the last invalidation cause of an application using [`last_invalidation_cause`]
@@ -205,6 +270,67 @@ impl Default for InvalidationCause {
     }
 }
 
+/// Tree and state changes staged by [`Application::apply_with`] to be committed at once
+#[derive(Default)]
+pub struct ApplicationTransaction {
+    tree: Option<WidgetNode>,
+    states: HashMap<WidgetId, Props>,
+}
+
+impl ApplicationTransaction {
+    /// Stage new application widget tree
+    pub fn tree(&mut self, tree: WidgetNode) -> &mut Self {
+        self.tree = Some(tree);
+        self
+    }
+
+    /// Stage state of given widget, also for widgets that will get mounted with staged tree
+    pub fn state<T>(&mut self, id: &WidgetId, data: T) -> &mut Self
+    where
+        T: Into<Props>,
+    {
+        self.states.insert(id.to_owned(), data.into());
+        self
+    }
+}
+
+/// Decides keys of unkeyed container items, which become the last part of their [`WidgetId`]s
+///
+/// Widgets keep their state as long as their ids do not change, so strategy that derives keys
+/// from item content lets widgets keep their state when they move within their container.
+/// Keys must be unique among items of the same container and must not contain `/`.
+pub trait ChildKeyStrategy {
+    /// Key of unkeyed `node` placed at `index` among its container items.
+    fn child_key(&self, node: &WidgetNode, index: usize) -> String;
+}
+
+impl<F> ChildKeyStrategy for F
+where
+    F: Fn(&WidgetNode, usize) -> String,
+{
+    fn child_key(&self, node: &WidgetNode, index: usize) -> String {
+        self(node, index)
+    }
+}
+
+/// Default [`ChildKeyStrategy`] that keys items with their index (`"<index>"`).
+#[derive(Debug, Default, Copy, Clone)]
+pub struct IndexChildKeyStrategy;
+
+impl ChildKeyStrategy for IndexChildKeyStrategy {
+    fn child_key(&self, _: &WidgetNode, index: usize) -> String {
+        format!("<{}>", index)
+    }
+}
+
+/// Unprocessed copy of keyed container item, kept to render widget while it exits.
+struct PresenceItem {
+    index: usize,
+    id: WidgetId,
+    possible_key: String,
+    item: Box<dyn Any + Send + Sync>,
+}
+
 /// Contains and orchestrates application layout, animations, interactions, etc.
 ///
 /// See the [`application`][self] module for more information and examples.
@@ -218,15 +344,42 @@ pub struct Application {
     state_changes: HashMap<WidgetId, Props>,
     animators: HashMap<WidgetId, AnimatorStates>,
     messages: HashMap<WidgetId, Messages>,
+    /// Messages of deferred widgets, kept until they get processed again.
+    deferred_messages: HashMap<WidgetId, Messages>,
+    /// (target, message, processes left before delivery), in the order they were sent.
+    delayed_messages: Vec<(WidgetId, Message, usize)>,
     signals: Vec<Signal>,
+    pending_signals: Vec<Signal>,
+    /// Payloads of signals waiting for layout to attach their sender rectangle.
+    layout_signals: Vec<Signal>,
     #[allow(clippy::type_complexity)]
     unmount_closures: HashMap<WidgetId, Vec<Box<dyn FnMut(WidgetUnmountContext) + Send + Sync>>>,
+    /// (dependencies hash of last run, its cleanup) of widget effects, in registration order.
+    effects: HashMap<WidgetId, Vec<(u64, Option<WidgetEffectCleanup>)>>,
+    exit_durations: HashMap<WidgetId, Scalar>,
+    exiting: HashMap<WidgetId, Scalar>,
+    /// (duration, elapsed time)
+    intervals: HashMap<WidgetId, (Scalar, Scalar)>,
+    accessibility: HashMap<WidgetId, A11yProps>,
+    presence: HashMap<WidgetId, Vec<PresenceItem>>,
+    last_presence: HashMap<WidgetId, Vec<PresenceItem>>,
+    preset_states: HashMap<WidgetId, Props>,
     dirty: bool,
     render_changed: bool,
+    #[allow(clippy::type_complexity)]
+    render_changed_callbacks: Vec<Box<dyn FnMut() + Send + Sync>>,
     last_invalidation_cause: InvalidationCause,
     change_notifier: ChangeNotifier,
     /// The amount of time between the last update, used when calculating animation progress
     pub animations_delta_time: Scalar,
+    min_process_interval: Scalar,
+    accumulated_delta_time: Scalar,
+    process_delta_time: Scalar,
+    time: f64,
+    time_scale: Scalar,
+    teleport_portals: bool,
+    logger: Box<dyn Logger + Send + Sync>,
+    child_key_strategy: Box<dyn ChildKeyStrategy + Send + Sync>,
 }
 
 impl Default for Application {
@@ -248,13 +401,34 @@ impl Application {
             state_changes: Default::default(),
             animators: Default::default(),
             messages: Default::default(),
+            deferred_messages: Default::default(),
+            delayed_messages: Default::default(),
             signals: Default::default(),
+            pending_signals: Default::default(),
+            layout_signals: Default::default(),
             unmount_closures: Default::default(),
+            effects: Default::default(),
+            exit_durations: Default::default(),
+            exiting: Default::default(),
+            intervals: Default::default(),
+            accessibility: Default::default(),
+            presence: Default::default(),
+            last_presence: Default::default(),
+            preset_states: Default::default(),
             dirty: true,
             render_changed: false,
+            render_changed_callbacks: Default::default(),
             last_invalidation_cause: Default::default(),
             change_notifier: ChangeNotifier::default(),
             animations_delta_time: 0.0,
+            min_process_interval: 0.0,
+            accumulated_delta_time: 0.0,
+            process_delta_time: 0.0,
+            time: 0.0,
+            time_scale: 1.0,
+            teleport_portals: true,
+            logger: Box::new(()),
+            child_key_strategy: Box::new(IndexChildKeyStrategy),
         }
     }
 
@@ -303,7 +477,8 @@ impl Application {
     /// Register's a component under a string name used when serializing the UI
     ///
     /// This function is often used in [`setup`][Self::setup] functions for registering batches of
-    /// components.
+    /// components. Widget nodes with matching type name run registered processor (see
+    /// [`hot_swap_component`][Self::hot_swap_component]).
     ///
     /// # Example
     ///
@@ -327,6 +502,33 @@ impl Application {
             .insert(type_name.to_owned(), processor);
     }
 
+    /// Registers a batch of components at once
+    ///
+    /// See [`register_component`][Self::register_component]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use raui_core::prelude::*;
+    /// fn my_widget(ctx: WidgetContext) -> WidgetNode {
+    ///     todo!("make awesome widget");
+    /// }
+    ///
+    /// let mut application = Application::new();
+    /// application.register_components([
+    ///     ("my_widget", my_widget as FnWidget),
+    ///     ("image_box", image_box),
+    /// ]);
+    /// ```
+    pub fn register_components<'a, I>(&mut self, components: I)
+    where
+        I: IntoIterator<Item = (&'a str, FnWidget)>,
+    {
+        for (type_name, processor) in components {
+            self.register_component(type_name, processor);
+        }
+    }
+
     /// Unregisters a component
     ///
     /// See [`register_component`][Self::register_component]
@@ -335,6 +537,43 @@ impl Application {
         self.component_mappings.remove(type_name);
     }
 
+    /// Replaces processor of component registered under `type_name` at runtime (e.g. with one
+    /// reloaded from dynamic library) and marks application dirty
+    ///
+    /// Processed widget nodes run processor registered under their type name rather than the one
+    /// they captured when they got created, no matter whether node comes from applied tree or
+    /// from other components, so [`register_component`][Self::register_component] overwrite
+    /// swaps processor too, just without marking application dirty. Node type name is matched
+    /// with registered name as is first, then by its last path segment, so node made by
+    /// [`widget`][crate::widget] macro as `my_mod::swappable` runs processor registered under
+    /// `swappable`.
+    pub fn hot_swap_component(&mut self, type_name: &str, processor: FnWidget) {
+        self.register_component(type_name, processor);
+        self.dirty = true;
+    }
+
+    fn registered_processor(&self, type_name: &str) -> Option<FnWidget> {
+        self.component_mappings
+            .get(type_name)
+            .or_else(|| {
+                let name = type_name.rsplit("::").next()?.trim();
+                self.component_mappings.get(name)
+            })
+            .copied()
+    }
+
+    /// Tells if component is registered under `type_name`
+    #[inline]
+    pub fn is_component_registered(&self, type_name: &str) -> bool {
+        self.component_mappings.contains_key(type_name)
+    }
+
+    /// Get the names of all registered components, in no particular order
+    #[inline]
+    pub fn registered_component_names(&self) -> impl Iterator<Item = &str> {
+        self.component_mappings.keys().map(|name| name.as_str())
+    }
+
     /// Register's a property type under a string name used when serializing the UI
     ///
     /// This function is often used in [`setup`][Self::setup] functions for registering batches of
@@ -366,6 +605,39 @@ impl Application {
         self.props_registry.register_factory::<T>(name);
     }
 
+    /// Same as [`register_props`][Self::register_props] but also makes
+    /// [`default_props_prefab`][Self::default_props_prefab] available for this type
+    #[inline]
+    pub fn register_props_with_default<T>(&mut self, name: &str)
+    where
+        T: 'static + Prefab + PropsData + Default,
+    {
+        self.props_registry.register_factory_with_default::<T>(name);
+    }
+
+    /// Registers a batch of property types at once
+    ///
+    /// See [`register_props`][Self::register_props]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use raui_core::prelude::*;
+    /// let mut application = Application::new();
+    /// application.register_props_batch([
+    ///     ("ImageBoxProps", PropsRegistry::registration::<ImageBoxProps>()),
+    ///     ("TextBoxProps", PropsRegistry::registration_with_default::<TextBoxProps>()),
+    /// ]);
+    /// ```
+    pub fn register_props_batch<'a, I>(&mut self, props: I)
+    where
+        I: IntoIterator<Item = (&'a str, PropsRegistration)>,
+    {
+        for (name, registration) in props {
+            registration(&mut self.props_registry, name);
+        }
+    }
+
     /// Unregisters a property type
     ///
     /// See [`register_props`][Self::register_props]
@@ -374,6 +646,35 @@ impl Application {
         self.props_registry.unregister_factory(name);
     }
 
+    /// Tells if property type is registered under `name`
+    #[inline]
+    pub fn is_props_registered(&self, name: &str) -> bool {
+        self.props_registry.is_registered(name)
+    }
+
+    /// Get the names of all registered property types, in no particular order
+    #[inline]
+    pub fn registered_props_names(&self) -> impl Iterator<Item = &str> {
+        self.props_registry.registered_names()
+    }
+
+    /// Get the registered names of all property types stored in `props`, in no particular order
+    ///
+    /// Property types that are not registered are skipped.
+    #[inline]
+    pub fn props_type_names<'a>(&'a self, props: &'a Props) -> impl Iterator<Item = &'a str> {
+        self.props_registry.props_type_names(props)
+    }
+
+    /// Get serialized default value of property type registered under `name`
+    ///
+    /// Returns [`None`] unless the type was registered with
+    /// [`register_props_with_default`][Self::register_props_with_default].
+    #[inline]
+    pub fn default_props_prefab(&self, name: &str) -> Option<Result<PrefabValue, PrefabError>> {
+        self.props_registry.default_prefab(name)
+    }
+
     /// Serialize the given [`Props`] to a [`PrefabValue`]
     #[inline]
     pub fn serialize_props(&self, props: &Props) -> Result<PrefabValue, PrefabError> {
@@ -387,15 +688,210 @@ impl Application {
     }
 
     /// Serialize a [`WidgetNode`] to a [`PrefabValue`]
+    ///
+    /// Component keys and slots order are kept (named slots are sorted by name), so the same node
+    /// always gives the same prefab. Component `idref`s are not serialized.
     #[inline]
     pub fn serialize_node(&self, data: &WidgetNode) -> Result<PrefabValue, ApplicationError> {
         Ok(self.node_to_prefab(data)?.to_prefab()?)
     }
 
+    /// Serialize a [`WidgetNode`] like [`serialize_node`][Self::serialize_node], but props that
+    /// repeat in the tree are stored once in `props_table` and referenced by their index in it
+    ///
+    /// This makes prefabs of big trees with many identical props much smaller.
+    /// [`deserialize_node`][Self::deserialize_node] reads both forms.
+    pub fn serialize_node_deduplicated(
+        &self,
+        data: &WidgetNode,
+    ) -> Result<PrefabValue, ApplicationError> {
+        let mut node = self.serialize_node(data)?;
+        let mut counts = HashMap::<PrefabValue, usize>::new();
+        Self::visit_props_prefabs(&mut node, &mut |props| {
+            if props.is_mapping() {
+                *counts.entry(props.to_owned()).or_default() += 1;
+            }
+            Ok(())
+        })?;
+        let mut props_table = vec![];
+        let mut indices = HashMap::<PrefabValue, usize>::new();
+        Self::visit_props_prefabs(&mut node, &mut |props| {
+            if counts.get(props).copied().unwrap_or_default() > 1 {
+                let index = match indices.get(props) {
+                    Some(index) => *index,
+                    None => {
+                        indices.insert(props.to_owned(), props_table.len());
+                        props_table.push(props.to_owned());
+                        props_table.len() - 1
+                    }
+                };
+                *props = PrefabValue::from(index);
+            }
+            Ok(())
+        })?;
+        Ok(DeduplicatedNodePrefab { props_table, node }.to_prefab()?)
+    }
+
     /// Deserialize a [`WidgetNode`] from a [`PrefabValue`]
     #[inline]
     pub fn deserialize_node(&self, data: PrefabValue) -> Result<WidgetNode, ApplicationError> {
-        self.node_from_prefab(WidgetNodePrefab::from_prefab(data)?)
+        self.node_from_prefab(WidgetNodePrefab::from_prefab(Self::resolve_props_table(
+            data,
+        )?)?)
+    }
+
+    /// Calls `f` with every component and unit props prefab of the node prefab, not going into
+    /// props themselves.
+    fn visit_props_prefabs<F>(data: &mut PrefabValue, f: &mut F) -> Result<(), PrefabError>
+    where
+        F: FnMut(&mut PrefabValue) -> Result<(), PrefabError>,
+    {
+        match data {
+            PrefabValue::Mapping(data) => {
+                for (key, value) in data.iter_mut() {
+                    match key.as_str() {
+                        Some("props") | Some("shared_props") => f(value)?,
+                        _ => Self::visit_props_prefabs(value, f)?,
+                    }
+                }
+            }
+            PrefabValue::Sequence(data) => {
+                for value in data {
+                    Self::visit_props_prefabs(value, f)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Replaces props references with props from table of deduplicated node prefab, other
+    /// prefabs are returned as they are.
+    fn resolve_props_table(data: PrefabValue) -> Result<PrefabValue, PrefabError> {
+        let deduplicated = match &data {
+            PrefabValue::Mapping(mapping) => {
+                mapping.contains_key(&PrefabValue::from("props_table"))
+            }
+            _ => false,
+        };
+        if !deduplicated {
+            return Ok(data);
+        }
+        let DeduplicatedNodePrefab {
+            props_table,
+            mut node,
+        } = DeduplicatedNodePrefab::from_prefab(data)?;
+        Self::visit_props_prefabs(&mut node, &mut |props| {
+            if let Some(index) = props.as_u64() {
+                *props = props_table.get(index as usize).cloned().ok_or_else(|| {
+                    PrefabError::CouldNotDeserialize(format!(
+                        "Props table has no item at index: {}",
+                        index
+                    ))
+                })?;
+            }
+            Ok(())
+        })?;
+        Ok(node)
+    }
+
+    /// Deep-merge `overrides` into `base` [`WidgetNode`] prefab, which allows to make variants of
+    /// prefab without duplicating it
+    ///
+    /// - Mappings are merged field by field, so override has to contain only changed fields (for
+    ///   example single field of single props type of component).
+    /// - Sequences (listed slots, tuples and unit items) are merged item by item: override item
+    ///   being component with `key` patches base component with the same key, any other override
+    ///   item patches base item at the same index. Override items with no match are appended.
+    /// - Any other override value replaces base value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use raui_core::{prelude::*, PrefabValue};
+    /// let base = serde_yaml::from_str::<PrefabValue>(r#"
+    /// Component:
+    ///   type_name: text_box
+    ///   key: title
+    ///   props:
+    ///     data:
+    ///       TextBoxProps:
+    ///         text: Hello
+    ///         width: Fill
+    /// "#).unwrap();
+    /// let overrides = serde_yaml::from_str::<PrefabValue>(r#"
+    /// Component:
+    ///   props:
+    ///     data:
+    ///       TextBoxProps:
+    ///         text: World
+    /// "#).unwrap();
+    /// let merged = Application::apply_overrides(base, overrides);
+    /// let props = &merged["Component"]["props"]["data"]["TextBoxProps"];
+    /// assert_eq!(props["text"].as_str(), Some("World"));
+    /// assert_eq!(props["width"].as_str(), Some("Fill"));
+    /// ```
+    pub fn apply_overrides(base: PrefabValue, overrides: PrefabValue) -> PrefabValue {
+        fn component_key(value: &PrefabValue) -> Option<String> {
+            value
+                .get("Component")?
+                .get("key")?
+                .as_str()
+                .map(|key| key.to_owned())
+        }
+
+        match (base, overrides) {
+            (PrefabValue::Mapping(mut base), PrefabValue::Mapping(overrides)) => {
+                for (key, value) in overrides {
+                    let value = match base.remove(&key) {
+                        Some(base) => Self::apply_overrides(base, value),
+                        None => value,
+                    };
+                    base.insert(key, value);
+                }
+                PrefabValue::Mapping(base)
+            }
+            (PrefabValue::Sequence(mut base), PrefabValue::Sequence(overrides)) => {
+                for (index, value) in overrides.into_iter().enumerate() {
+                    let found = match component_key(&value) {
+                        Some(key) => base
+                            .iter()
+                            .position(|item| component_key(item).as_ref() == Some(&key)),
+                        None => Some(index).filter(|index| *index < base.len()),
+                    };
+                    match found {
+                        Some(index) => {
+                            let item = std::mem::replace(&mut base[index], PrefabValue::Null);
+                            base[index] = Self::apply_overrides(item, value);
+                        }
+                        None => base.push(value),
+                    }
+                }
+                PrefabValue::Sequence(base)
+            }
+            (_, overrides) => overrides,
+        }
+    }
+
+    /// Check if [`WidgetNode`] prefab can be deserialized with currently registered components and
+    /// props types, reporting all problems found instead of just the first one
+    #[inline]
+    pub fn validate_prefab(&self, data: &PrefabValue) -> Result<(), Vec<PrefabValidationError>> {
+        let mut errors = vec![];
+        let data =
+            Self::resolve_props_table(data.to_owned()).and_then(WidgetNodePrefab::from_prefab);
+        match data {
+            Ok(data) => self.validate_node_prefab(&data, "", &mut errors),
+            Err(error) => errors.push(PrefabValidationError::Prefab {
+                path: "/".to_owned(),
+                error,
+            }),
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
     }
 
     /// Get the reason that the application state was last invalidated and caused to re-process
@@ -416,129 +912,521 @@ impl Application {
         self.dirty = true;
     }
 
+    /// Returns `true` if the last [`process`][Self::process] rendered new tree
     #[inline]
     pub fn does_render_changed(&self) -> bool {
         self.render_changed
     }
 
-    /// Get the [`WidgetNode`] for the application tree
-    #[inline]
-    pub fn tree(&self) -> &WidgetNode {
-        &self.tree
+    /// Register callback called at the end of every [`process`][Self::process] that rendered new
+    /// tree, so host can mark itself dirty instead of polling
+    /// [`does_render_changed`][Self::does_render_changed]
+    ///
+    /// Callbacks are called in the order they were registered.
+    pub fn on_render_changed<F>(&mut self, f: F)
+    where
+        F: 'static + FnMut() + Send + Sync,
+    {
+        self.render_changed_callbacks.push(Box::new(f));
     }
 
-    /// Get the application widget tree rendered to raw [`WidgetUnit`]'s
+    /// Get the minimal amount of time between application processing
     #[inline]
-    pub fn rendered_tree(&self) -> &WidgetUnit {
-        &self.rendered_tree
+    pub fn min_process_interval(&self) -> Scalar {
+        self.min_process_interval
     }
 
-    /// Get the application [`Layout`] data
+    /// Set the minimal amount of time between application processing
+    ///
+    /// When set, [`process()`][Self::process] skips processing until given amount of
+    /// [`animations_delta_time`][Self::animations_delta_time] accumulates, unless there is change
+    /// pending (application marked dirty, [`ChangeNotifier`] changed, state changed or message
+    /// sent). Delta time of skipped calls is not lost: it accumulates and gets applied to
+    /// animations at once when application finally processes, so animations keep their pace,
+    /// just with lower update rate. Zero (default) processes on every call.
     #[inline]
-    pub fn layout_data(&self) -> &Layout {
-        &self.layout
+    pub fn set_min_process_interval(&mut self, interval: Scalar) {
+        self.min_process_interval = interval.max(0.0);
     }
 
+    /// Get the multiplier applied to [`animations_delta_time`][Self::animations_delta_time]
     #[inline]
-    pub fn has_layout_widget(&self, id: &WidgetId) -> bool {
-        self.layout.items.keys().any(|k| k == id)
+    pub fn time_scale(&self) -> Scalar {
+        self.time_scale
     }
 
-    /// Update the application widget tree
+    /// Set the multiplier applied to [`animations_delta_time`][Self::animations_delta_time]
+    /// before animations advance (e.g. `0.5` for slow motion)
+    ///
+    /// Scale of `0` pauses all animations (including exit animations) without completing them.
+    /// Intervals are not scaled.
     #[inline]
-    pub fn apply(&mut self, tree: WidgetNode) {
-        self.tree = tree;
-        self.dirty = true;
+    pub fn set_time_scale(&mut self, scale: Scalar) {
+        self.time_scale = scale.max(0.0);
     }
 
-    /// Render the application
+    /// Get application time, that is scaled animations delta time accumulated by all processing
+    /// so far, as seen by widgets in [`WidgetContext::time`]
     #[inline]
-    pub fn render<R, T, E>(&self, mapping: &CoordsMapping, renderer: &mut R) -> Result<T, E>
-    where
-        R: Renderer<T, E>,
-    {
-        renderer.render(&self.rendered_tree, mapping, &self.layout)
+    pub fn time(&self) -> f64 {
+        self.time
     }
 
-    /// Render the application, but only if something effecting the rendering has changed and it
-    /// _needs_ to be re-rendered
+    /// Returns `true` if portals get teleported into their containers when rendering
     #[inline]
-    pub fn render_change<R, T, E>(
-        &mut self,
-        mapping: &CoordsMapping,
-        renderer: &mut R,
-    ) -> Result<Option<T>, E>
-    where
-        R: Renderer<T, E>,
-    {
-        if self.render_changed {
-            Ok(Some(self.render(mapping, renderer)?))
-        } else {
-            Ok(None)
-        }
+    pub fn does_teleport_portals(&self) -> bool {
+        self.teleport_portals
     }
 
-    /// Calculate application layout
+    /// Enable or disable teleporting portals into their containers when rendering (enabled by
+    /// default)
+    ///
+    /// When disabled, [`rendered_tree`][Self::rendered_tree] keeps portal boxes where they were
+    /// declared, which is useful for debugging misrouted portals. Takes effect on next render.
     #[inline]
-    pub fn layout<L, E>(&mut self, mapping: &CoordsMapping, layout_engine: &mut L) -> Result<(), E>
-    where
-        L: LayoutEngine<E>,
-    {
-        self.layout = layout_engine.layout(mapping, &self.rendered_tree)?;
-        Ok(())
+    pub fn set_teleport_portals(&mut self, value: bool) {
+        self.teleport_portals = value;
     }
 
-    /// Calculate application layout, but only if something effecting application layout has changed
-    /// and the layout _needs_ to be re-done
+    /// Set the [`Logger`] that application reports errors to (e.g. panics caught by
+    /// [`error_boundary`][crate::widget::component::error_boundary::error_boundary]).
+    ///
+    /// By default nothing gets logged.
     #[inline]
-    pub fn layout_change<L, E>(
-        &mut self,
-        mapping: &CoordsMapping,
-        layout_engine: &mut L,
-    ) -> Result<bool, E>
+    pub fn set_logger<L>(&mut self, logger: L)
     where
-        L: LayoutEngine<E>,
+        L: 'static + Logger + Send + Sync,
     {
-        if self.render_changed {
-            self.layout(mapping, layout_engine)?;
-            Ok(true)
-        } else {
-            Ok(false)
-        }
+        self.logger = Box::new(logger);
     }
 
-    /// Perform interactions on the application using the given interaction engine
+    /// Set the [`ChildKeyStrategy`] that decides keys of unkeyed container items.
+    ///
+    /// By default items are keyed with their index ([`IndexChildKeyStrategy`]).
     #[inline]
-    pub fn interact<I, R, E>(&mut self, interactions_engine: &mut I) -> Result<R, E>
+    pub fn set_child_key_strategy<S>(&mut self, strategy: S)
     where
-        I: InteractionsEngine<R, E>,
+        S: 'static + ChildKeyStrategy + Send + Sync,
     {
-        interactions_engine.perform_interactions(self)
+        self.child_key_strategy = Box::new(strategy);
     }
 
-    /// Send a message to the given widget
+    /// Get the [`WidgetNode`] for the application tree
     #[inline]
-    pub fn send_message<T>(&mut self, id: &WidgetId, data: T)
-    where
-        T: 'static + MessageData,
-    {
-        self.send_message_raw(id, Box::new(data));
+    pub fn tree(&self) -> &WidgetNode {
+        &self.tree
     }
 
-    /// Send raw message data to the given widget
+    /// Get the application widget tree rendered to raw [`WidgetUnit`]'s
     #[inline]
-    pub fn send_message_raw(&mut self, id: &WidgetId, data: Message) {
-        if let Some(list) = self.messages.get_mut(id) {
-            list.push(data);
-        } else {
-            self.messages.insert(id.to_owned(), vec![data]);
-        }
+    pub fn rendered_tree(&self) -> &WidgetUnit {
+        &self.rendered_tree
     }
 
-    /// Get the list of [signals][crate::signals] that have been sent by widgets
+    /// Get the application [`Layout`] data
     #[inline]
-    pub fn signals(&self) -> &[Signal] {
-        &self.signals
+    pub fn layout_data(&self) -> &Layout {
+        &self.layout
+    }
+
+    #[inline]
+    pub fn has_layout_widget(&self, id: &WidgetId) -> bool {
+        self.layout.items.keys().any(|k| k == id)
+    }
+
+    /// Transforms of `id` widget and all widgets it is nested in, paired with rectangles they get
+    /// applied around, from the root down to the widget itself. Identity transforms are skipped.
+    ///
+    /// Returns [`None`] if widget is not in the rendered tree.
+    fn widget_transforms(&self, id: &WidgetId) -> Option<Vec<(Transform, Rect)>> {
+        fn find(
+            layout: &Layout,
+            id: &WidgetId,
+            unit: &WidgetUnit,
+            result: &mut Vec<(Transform, Rect)>,
+        ) -> bool {
+            let data = match unit.as_data() {
+                Some(data) => data,
+                None => return false,
+            };
+            let count = result.len();
+            let rect = layout.items.get(data.id()).map(|item| item.ui_space);
+            if let (Some(transform), Some(rect)) = (unit.transform(), rect) {
+                if *transform != Transform::default() {
+                    result.push((*transform, rect));
+                }
+            }
+            if data.id() == id {
+                return true;
+            }
+            let found = if let WidgetUnit::ContentBox(unit) = unit {
+                unit.items.iter().any(|item| {
+                    let count = result.len();
+                    let rect = item
+                        .slot
+                        .as_data()
+                        .and_then(|data| layout.items.get(data.id()))
+                        .map(|item| item.ui_space);
+                    if let Some(rect) = rect {
+                        if item.layout.transform != Transform::default() {
+                            result.push((item.layout.transform, rect));
+                        }
+                    }
+                    if find(layout, id, &item.slot, result) {
+                        return true;
+                    }
+                    result.truncate(count);
+                    false
+                })
+            } else {
+                data.get_children()
+                    .into_iter()
+                    .any(|child| find(layout, id, child, result))
+            };
+            if !found {
+                result.truncate(count);
+            }
+            found
+        }
+
+        let mut result = vec![];
+        if find(&self.layout, id, &self.rendered_tree, &mut result) {
+            Some(result)
+        } else {
+            None
+        }
+    }
+
+    /// Maps `point` in layout (virtual) space into untransformed layout space of `id` widget,
+    /// undoing transforms of that widget and all widgets it is nested in.
+    pub(crate) fn untransform_widget_point(&self, id: &WidgetId, point: Vec2) -> Option<Vec2> {
+        self.widget_transforms(id)?
+            .into_iter()
+            .try_fold(point, |point, (transform, rect)| {
+                transform.inverse_transform_point(rect, point)
+            })
+    }
+
+    /// Converts screen (real) `point` into local space of `id` widget, that is layout units
+    /// relative to top-left corner of its layout rectangle, taking transforms of that widget and
+    /// all widgets it is nested in into account. Divide result by widget layout size to get
+    /// normalized (0 to 1) position.
+    ///
+    /// Returns [`None`] if widget has no layout, or its transforms cannot be inverted.
+    pub fn screen_to_local(
+        &self,
+        id: &WidgetId,
+        point: Vec2,
+        mapping: &CoordsMapping,
+    ) -> Option<Vec2> {
+        let rect = self.layout.items.get(id)?.ui_space;
+        let point = mapping.real_to_virtual_vec2(point, false);
+        let point = self.untransform_widget_point(id, point)?;
+        Some(Vec2 {
+            x: point.x - rect.left,
+            y: point.y - rect.top,
+        })
+    }
+
+    /// Inverse of [`screen_to_local`][Self::screen_to_local], converts `point` in local space of
+    /// `id` widget into screen (real) space.
+    ///
+    /// Returns [`None`] if widget has no layout.
+    pub fn local_to_screen(
+        &self,
+        id: &WidgetId,
+        point: Vec2,
+        mapping: &CoordsMapping,
+    ) -> Option<Vec2> {
+        let rect = self.layout.items.get(id)?.ui_space;
+        let point = Vec2 {
+            x: point.x + rect.left,
+            y: point.y + rect.top,
+        };
+        let point = self
+            .widget_transforms(id)?
+            .into_iter()
+            .rev()
+            .fold(point, |point, (transform, rect)| {
+                transform.transform_point(rect, point)
+            });
+        Some(mapping.virtual_to_real_vec2(point, false))
+    }
+
+    /// Assemble [accessibility][crate::accessibility] tree out of the rendered tree and its
+    /// [`Layout`], so call it after [`layout`][Self::layout]
+    ///
+    /// Components with [`A11yProps`] describe the outermost widget unit they render. Root node
+    /// is a [`A11yRole::Window`] covering whole UI space.
+    pub fn accessibility_tree(&self) -> A11yNode {
+        fn contains(path: &str, other: &str) -> bool {
+            other == path || (other.starts_with(path) && other[path.len()..].starts_with('/'))
+        }
+
+        fn nodes(
+            unit: &WidgetUnit,
+            layout: &Layout,
+            described: &HashMap<&WidgetId, &A11yProps>,
+            result: &mut Vec<A11yNode>,
+        ) {
+            if let Some(data) = unit.as_data() {
+                let mut children = vec![];
+                for child in data.get_children() {
+                    nodes(child, layout, described, &mut children);
+                }
+                match described.get(data.id()) {
+                    Some(props) => result.push(A11yNode {
+                        id: data.id().to_owned(),
+                        role: props.role.to_owned(),
+                        label: props.label.to_owned(),
+                        value: props.value.to_owned(),
+                        bounds: layout
+                            .items
+                            .get(data.id())
+                            .map(|item| item.ui_space)
+                            .unwrap_or_default(),
+                        children,
+                    }),
+                    None => result.extend(children),
+                }
+            }
+        }
+
+        // outer components go first, so they take precedence over components they render.
+        let mut declared = self.accessibility.iter().collect::<Vec<_>>();
+        declared.sort_by_key(|(id, _)| id.depth());
+        let mut described = HashMap::with_capacity(declared.len());
+        for (id, props) in declared {
+            if let Some(unit) = self
+                .layout
+                .ordered()
+                .iter()
+                .find(|unit| contains(id.path(), unit.path()))
+            {
+                described.entry(unit).or_insert(props);
+            }
+        }
+        let mut children = vec![];
+        nodes(&self.rendered_tree, &self.layout, &described, &mut children);
+        A11yNode {
+            role: A11yRole::Window,
+            bounds: self.layout.ui_space,
+            children,
+            ..Default::default()
+        }
+    }
+
+    /// Update the application widget tree
+    #[inline]
+    pub fn apply(&mut self, tree: WidgetNode) {
+        self.tree = tree;
+        self.dirty = true;
+    }
+
+    /// Update the application widget tree and widget states at once
+    ///
+    /// Staged changes are committed together so the next process already uses both new tree and
+    /// new states, also widgets mounted by new tree start with their staged state.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use raui_core::prelude::*;
+    /// # let mut application = Application::new();
+    /// let id = WidgetId::new("text_box", &["text".to_owned()]);
+    /// application.apply_with(|transaction| {
+    ///     transaction
+    ///         .tree(widget! { (#{"text"} text_box) })
+    ///         .state(&id, "hello".to_owned());
+    /// });
+    /// ```
+    pub fn apply_with<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut ApplicationTransaction),
+    {
+        let mut transaction = ApplicationTransaction::default();
+        f(&mut transaction);
+        let ApplicationTransaction { tree, states } = transaction;
+        if let Some(tree) = tree {
+            self.apply(tree);
+        }
+        for (id, data) in states {
+            if self.states.contains_key(&id) {
+                self.state_changes.insert(id, data);
+            } else {
+                self.preset_states.insert(id, data);
+            }
+        }
+    }
+
+    /// Render the application
+    #[inline]
+    pub fn render<R, T, E>(&self, mapping: &CoordsMapping, renderer: &mut R) -> Result<T, E>
+    where
+        R: Renderer<T, E>,
+    {
+        renderer.render(&self.rendered_tree, mapping, &self.layout)
+    }
+
+    /// Render the application with [`SnapshotRenderer`], into stable text that can be committed
+    /// and compared in snapshot tests.
+    #[inline]
+    pub fn render_snapshot(&self, mapping: &CoordsMapping) -> String {
+        self.render(mapping, &mut SnapshotRenderer)
+            .unwrap_or_default()
+    }
+
+    /// Render the application, but only if something effecting the rendering has changed and it
+    /// _needs_ to be re-rendered
+    #[inline]
+    pub fn render_change<R, T, E>(
+        &mut self,
+        mapping: &CoordsMapping,
+        renderer: &mut R,
+    ) -> Result<Option<T>, E>
+    where
+        R: Renderer<T, E>,
+    {
+        if self.render_changed {
+            Ok(Some(self.render(mapping, renderer)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Calculate application layout
+    ///
+    /// Signals sent with [`SignalSender::write_with_rect`] since previous layout get reported by
+    /// [`signals`][Self::signals] as [`LayoutSignal`]s carrying layout rectangles of their senders.
+    #[inline]
+    pub fn layout<L, E>(&mut self, mapping: &CoordsMapping, layout_engine: &mut L) -> Result<(), E>
+    where
+        L: LayoutEngine<E>,
+    {
+        self.layout = layout_engine.layout(mapping, &self.rendered_tree)?;
+        self.layout.ensure_ordered();
+        for (id, payload) in std::mem::take(&mut self.layout_signals) {
+            let rect = self.layout.items.get(&id).map(|item| item.ui_space);
+            self.signals
+                .push((id, Box::new(LayoutSignal { rect, payload })));
+        }
+        Ok(())
+    }
+
+    /// Calculate application layout, but only if something effecting application layout has changed
+    /// and the layout _needs_ to be re-done
+    #[inline]
+    pub fn layout_change<L, E>(
+        &mut self,
+        mapping: &CoordsMapping,
+        layout_engine: &mut L,
+    ) -> Result<bool, E>
+    where
+        L: LayoutEngine<E>,
+    {
+        if self.render_changed {
+            self.layout(mapping, layout_engine)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Perform interactions on the application using the given interaction engine
+    #[inline]
+    pub fn interact<I, R, E>(&mut self, interactions_engine: &mut I) -> Result<R, E>
+    where
+        I: InteractionsEngine<R, E>,
+    {
+        interactions_engine.perform_interactions(self)
+    }
+
+    /// Send a message to the given widget
+    ///
+    /// Messages sent to the same widget are delivered in the next process in the order they were
+    /// sent, and messages sent by widgets during process (in the order widgets send them) are
+    /// queued after messages sent before that process.
+    #[inline]
+    pub fn send_message<T>(&mut self, id: &WidgetId, data: T)
+    where
+        T: 'static + MessageData,
+    {
+        self.send_message_raw(id, Box::new(data));
+    }
+
+    /// Send a message to the given widget, delivered `frames` processes later than
+    /// [`send_message`][Self::send_message] would deliver it
+    ///
+    /// Every process counts, whether the application has changed or not. Message is dropped if
+    /// target widget gets unmounted before delivery.
+    #[inline]
+    pub fn send_message_delayed<T>(&mut self, id: &WidgetId, data: T, frames: usize)
+    where
+        T: 'static + MessageData,
+    {
+        self.send_message_delayed_raw(id, Box::new(data), frames);
+    }
+
+    /// Send raw message data to the given widget, delivered `frames` processes later than
+    /// [`send_message_raw`][Self::send_message_raw] would deliver it
+    pub fn send_message_delayed_raw(&mut self, id: &WidgetId, data: Message, frames: usize) {
+        if frames == 0 {
+            self.send_message_raw(id, data);
+        } else {
+            self.delayed_messages.push((id.to_owned(), data, frames));
+        }
+    }
+
+    /// Send raw message data to the given widget
+    #[inline]
+    pub fn send_message_raw(&mut self, id: &WidgetId, data: Message) {
+        if let Some(list) = self.messages.get_mut(id) {
+            list.push(data);
+        } else {
+            self.messages.insert(id.to_owned(), vec![data]);
+        }
+    }
+
+    /// Send a [signal][crate::signals] on behalf of the given widget, for cases where the host
+    /// should act on something no widget took care of (e.g. unhandled navigation back action)
+    ///
+    /// It is reported by [`signals`][Self::signals] after the next process, before signals sent
+    /// by widgets.
+    #[inline]
+    pub fn send_signal<T>(&mut self, id: &WidgetId, data: T)
+    where
+        T: 'static + MessageData,
+    {
+        self.pending_signals.push((id.to_owned(), Box::new(data)));
+    }
+
+    /// Respond to [`SignalRequest`] signal by sending [`SignalResponse`] message with the same
+    /// correlation id to the requesting widget
+    ///
+    /// Returns `false` if given signal is not a request.
+    pub fn respond_signal<T>(&mut self, signal: &Signal, payload: T) -> bool
+    where
+        T: 'static + MessageData,
+    {
+        let (id, message) = signal;
+        match message.as_any().downcast_ref::<SignalRequest>() {
+            Some(request) => {
+                let response = SignalResponse {
+                    id: request.id,
+                    payload: Box::new(payload),
+                };
+                self.send_message(id, response);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get the list of [signals][crate::signals] that have been sent by widgets, in the order they
+    /// were sent
+    #[inline]
+    pub fn signals(&self) -> &[Signal] {
+        &self.signals
     }
 
     /// Get the list of [signals][crate::signals] that have been sent by widgets, consuming the
@@ -554,6 +1442,46 @@ impl Application {
         self.states.get(id)
     }
 
+    /// Get the animations of a given widget, if it has any that are not finished
+    #[inline]
+    pub fn animator_states(&self, id: &WidgetId) -> Option<&AnimatorStates> {
+        self.animators.get(id)
+    }
+
+    /// Number of widgets that have any animation in progress (paused ones do not count)
+    ///
+    /// Host can use it to lower its frame rate while UI is not animating.
+    #[inline]
+    pub fn active_animation_count(&self) -> usize {
+        self.animators.values().filter(|a| a.in_progress()).count()
+    }
+
+    /// Returns `true` if any widget has animation in progress
+    #[inline]
+    pub fn has_active_animations(&self) -> bool {
+        self.animators.values().any(|a| a.in_progress())
+    }
+
+    /// Get mutable access to the animations of a given widget (e.g. to pause or seek them)
+    ///
+    /// Widgets see these changes in the next process, so call [`mark_dirty`] if animation got
+    /// paused or seeked and has to be shown without waiting for other changes.
+    ///
+    /// [`mark_dirty`]: Self::mark_dirty
+    #[inline]
+    pub fn animator_states_mut(&mut self, id: &WidgetId) -> Option<&mut AnimatorStates> {
+        self.animators.get_mut(id)
+    }
+
+    /// Return's `true` if widget was removed from the tree but is kept alive until its exit
+    /// animation finishes
+    ///
+    /// See [`WidgetLifeCycle::exit`]
+    #[inline]
+    pub fn is_exiting(&self, id: &WidgetId) -> bool {
+        self.exiting.contains_key(id)
+    }
+
     /// Set the props of a given widget
     #[inline]
     pub fn state_write(&mut self, id: &WidgetId, data: Props) {
@@ -585,6 +1513,28 @@ impl Application {
         }
     }
 
+    /// Get mutable access to the widget's [`Props`] in a closure, same as
+    /// [`state_mutate_cloned`][Self::state_mutate_cloned], except closure starts from change
+    /// pending for the next process if there is one, instead of the current state
+    ///
+    /// Successive updates of the same widget between two processes compose in the order they were
+    /// called, while [`state_write`][Self::state_write] and other mutating methods replace
+    /// pending change. Pending changes also include ones that widgets made during the last
+    /// process, so updates apply on top of these too.
+    pub fn state_update<F>(&mut self, id: &WidgetId, f: F)
+    where
+        F: FnOnce(&mut Props),
+    {
+        let state = match self.state_changes.get(id) {
+            Some(state) => Some(state.clone()),
+            None => self.states.get(id).cloned(),
+        };
+        if let Some(mut state) = state {
+            f(&mut state);
+            self.state_changes.insert(id.to_owned(), state);
+        }
+    }
+
     /// [`process()`][Self::process] application, even if no changes have been detected
     #[inline]
     pub fn forced_process(&mut self) -> bool {
@@ -601,8 +1551,92 @@ impl Application {
         self.process_with_context(process_context)
     }
 
+    /// [Process][Self::process] application repeatedly, until it settles (process finds nothing
+    /// to change) or `max_iterations` processes were made, so changes made by processing (like
+    /// mount closures writing state) get applied right away instead of a frame later.
+    ///
+    /// Returns number of processes that changed the application. When it equals `max_iterations`
+    /// the application might still not be settled, for example when it runs animations or some
+    /// widget keeps changing its state on every process.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use raui_core::prelude::*;
+    /// let mut application = Application::new();
+    /// application.apply(widget! { (image_box) });
+    /// assert_eq!(application.process_until_stable(10), 1);
+    /// assert_eq!(application.process_until_stable(10), 0);
+    /// ```
+    #[inline]
+    pub fn process_until_stable(&mut self, max_iterations: usize) -> usize {
+        self.process_until_stable_with_context(max_iterations, &mut Default::default())
+    }
+
+    /// [`process_until_stable()`][Self::process_until_stable] application with a custom
+    /// [`ProcessContext`]
+    pub fn process_until_stable_with_context<'b>(
+        &mut self,
+        max_iterations: usize,
+        process_context: &mut ProcessContext<'b>,
+    ) -> usize {
+        let mut iterations = 0;
+        while iterations < max_iterations && self.process_with_context(process_context) {
+            iterations += 1;
+        }
+        iterations
+    }
+
+    /// Tears down whole UI, while keeping registered components, props and application settings
+    ///
+    /// Applies empty tree and processes it, so all widgets get unmounted (skipping their exit
+    /// durations) and run their unmount closures and effect cleanups. Then clears all transient
+    /// data: states, animators, messages, rendered tree and layout. Signals sent by unmounting
+    /// widgets are kept available in [`signals`][Self::signals].
+    #[inline]
+    pub fn reset(&mut self) {
+        self.reset_with_context(&mut Default::default())
+    }
+
+    /// [`reset()`][Self::reset] application and provide a custom [`ProcessContext`] to unmount
+    /// closures
+    pub fn reset_with_context<'b>(&mut self, process_context: &mut ProcessContext<'b>) {
+        self.apply(WidgetNode::None);
+        self.forced_process_with_context(process_context);
+        self.rendered_tree = Default::default();
+        self.layout = Default::default();
+        self.states.clear();
+        self.state_changes.clear();
+        self.animators.clear();
+        self.messages.clear();
+        self.deferred_messages.clear();
+        self.delayed_messages.clear();
+        self.pending_signals.clear();
+        self.layout_signals.clear();
+        self.unmount_closures.clear();
+        self.effects.clear();
+        self.exit_durations.clear();
+        self.exiting.clear();
+        self.intervals.clear();
+        self.accessibility.clear();
+        self.presence.clear();
+        self.last_presence.clear();
+        self.preset_states.clear();
+        self.accumulated_delta_time = 0.0;
+        self.time = 0.0;
+        self.last_invalidation_cause = InvalidationCause::None;
+    }
+
     /// Process the application, updating animations, applying state changes, handling widget
     /// messages, etc.
+    ///
+    /// # Processing order
+    ///
+    /// Components are processed in tree order, which is deterministic: component (together with
+    /// its mount and change closures and effects) is processed before components it renders, and
+    /// those are processed depth first, in the order of their placement in the rendered tree
+    /// (items of containers in list order). Slots passed to a component get processed where that
+    /// component places them, so named slots follow placement order, not their names.
     #[inline]
     pub fn process(&mut self) -> bool {
         self.process_with_context(&mut Default::default())
@@ -690,14 +1724,57 @@ impl Application {
             self.dirty = true;
         }
         self.animations_delta_time = self.animations_delta_time.max(0.0);
+        self.accumulated_delta_time += self.animations_delta_time;
+        if self.accumulated_delta_time < self.min_process_interval
+            && !self.dirty
+            && self.state_changes.is_empty()
+            && self.messages.is_empty()
+            && self.delayed_messages.is_empty()
+            && self.pending_signals.is_empty()
+        {
+            return false;
+        }
+        let delta_time = std::mem::take(&mut self.accumulated_delta_time);
+        let animations_delta_time = delta_time * self.time_scale;
+        self.process_delta_time = animations_delta_time;
+        self.time += animations_delta_time as f64;
         self.last_invalidation_cause = InvalidationCause::None;
         self.render_changed = false;
         let changed_states = std::mem::take(&mut self.state_changes);
         let mut messages = std::mem::take(&mut self.messages);
-        let changed_animators = self.animators.values().any(|a| a.in_progress());
-        if !self.dirty && changed_states.is_empty() && messages.is_empty() && !changed_animators {
+        for (id, message, frames) in std::mem::take(&mut self.delayed_messages) {
+            if frames == 0 {
+                messages.entry(id).or_default().push(message);
+            } else {
+                self.delayed_messages.push((id, message, frames - 1));
+            }
+        }
+        for (id, (duration, elapsed)) in &mut self.intervals {
+            *elapsed += delta_time;
+            while *duration > 0.0 && *elapsed >= *duration {
+                *elapsed -= *duration;
+                messages
+                    .entry(id.to_owned())
+                    .or_default()
+                    .push(Box::new(IntervalMessage));
+            }
+        }
+        let changed_animators = self.time_scale > 0.0 && self.has_active_animations();
+        if !self.dirty
+            && changed_states.is_empty()
+            && messages.is_empty()
+            && !changed_animators
+            && self.exiting.is_empty()
+            && self.pending_signals.is_empty()
+        {
             return false;
         }
+        // messages kept for deferred widgets were sent before the others.
+        for (id, mut list) in std::mem::take(&mut self.deferred_messages) {
+            let entry = messages.entry(id).or_default();
+            list.append(entry);
+            *entry = list;
+        }
         if self.dirty {
             self.last_invalidation_cause = InvalidationCause::Forced;
         }
@@ -713,8 +1790,12 @@ impl Application {
         let (message_sender, message_receiver) = channel();
         let message_sender = MessageSender::new(message_sender);
         for (k, a) in &mut self.animators {
-            a.process(self.animations_delta_time, k, &message_sender);
+            a.process(animations_delta_time, k, &message_sender);
         }
+        for remaining in self.exiting.values_mut() {
+            *remaining -= animations_delta_time;
+        }
+        self.last_presence = std::mem::take(&mut self.presence);
         self.dirty = false;
         let old_states = std::mem::take(&mut self.states);
         let states = old_states
@@ -725,6 +1806,7 @@ impl Application {
         let tree = self.tree.clone();
         let mut used_ids = HashSet::new();
         let mut new_states = HashMap::new();
+        self.accessibility.clear();
         let rendered_tree = self.process_node(
             tree,
             &states,
@@ -738,49 +1820,98 @@ impl Application {
             &signal_sender,
             process_context,
         );
+        self.preset_states.clear();
+        let mut unmounted = vec![];
         self.states = states
             .into_iter()
             .chain(new_states.into_iter())
-            .filter(|(id, state)| {
-                if used_ids.contains(id) {
-                    true
+            .filter_map(|(id, state)| {
+                if used_ids.contains(&id) {
+                    Some((id, state))
                 } else {
-                    if let Some(closures) = self.unmount_closures.remove(id) {
-                        for mut closure in closures {
-                            let messenger = &message_sender;
-                            let signals = SignalSender::new(id.clone(), signal_sender.clone());
-                            let context = WidgetUnmountContext {
-                                id,
-                                state,
-                                messenger,
-                                signals,
-                                process_context,
-                            };
-                            (closure)(context);
-                        }
-                    }
-                    self.animators.remove(id);
-                    false
+                    unmounted.push((id, state));
+                    None
                 }
             })
             .collect();
+        // unmount in stable order (nested widgets before their parents, siblings by path), so
+        // teardown side effects do not depend on hash set iteration order.
+        unmounted.sort_by(|(a, _), (b, _)| {
+            b.depth()
+                .cmp(&a.depth())
+                .then_with(|| a.path().cmp(b.path()))
+        });
+        for (id, state) in &unmounted {
+            if let Some(closures) = self.unmount_closures.remove(id) {
+                for mut closure in closures {
+                    let messenger = &message_sender;
+                    let signals = SignalSender::new(id.clone(), signal_sender.clone());
+                    let context = WidgetUnmountContext {
+                        id,
+                        state,
+                        messenger,
+                        signals,
+                        process_context,
+                    };
+                    (closure)(context);
+                }
+            }
+            if let Some(effects) = self.effects.remove(id) {
+                for cleanup in effects.into_iter().filter_map(|(_, cleanup)| cleanup) {
+                    let messenger = &message_sender;
+                    let signals = SignalSender::new(id.clone(), signal_sender.clone());
+                    let context = WidgetUnmountContext {
+                        id,
+                        state,
+                        messenger,
+                        signals,
+                        process_context,
+                    };
+                    (cleanup)(context);
+                }
+            }
+            self.animators.remove(id);
+            self.exit_durations.remove(id);
+            self.exiting.remove(id);
+            self.intervals.remove(id);
+            self.deferred_messages.remove(id);
+            self.delayed_messages.retain(|(target, _, _)| target != id);
+        }
+        // channel keeps send order, so messages of each widget stay in the order they were sent.
         while let Ok((id, message)) = message_receiver.try_recv() {
+            if let Some(delayed) = message.as_any().downcast_ref::<DelayedMessage>() {
+                self.delayed_messages
+                    .push((id, delayed.message.clone(), delayed.frames));
+                continue;
+            }
             if let Some(list) = self.messages.get_mut(&id) {
                 list.push(message);
             } else {
                 self.messages.insert(id, vec![message]);
             }
         }
-        self.signals.clear();
-        while let Ok(data) = signal_receiver.try_recv() {
-            self.signals.push(data);
+        self.signals = std::mem::take(&mut self.pending_signals);
+        while let Ok((id, message)) = signal_receiver.try_recv() {
+            if let Some(pending) = message.as_any().downcast_ref::<PendingLayoutSignal>() {
+                self.layout_signals.push((id, pending.0.clone()));
+                continue;
+            }
+            self.signals.push((id, message));
         }
         self.animators = std::mem::take(&mut self.animators)
             .into_iter()
-            .filter_map(|(k, a)| if a.in_progress() { Some((k, a)) } else { None })
+            .filter_map(|(k, a)| if a.is_done() { None } else { Some((k, a)) })
             .collect::<HashMap<_, _>>();
         if let Ok(tree) = rendered_tree.try_into() {
-            self.rendered_tree = Self::teleport_portals(tree);
+            self.rendered_tree = if self.teleport_portals {
+                Self::teleport_portals(tree)
+            } else {
+                tree
+            };
+            self.render_changed = true;
+            for callback in &mut self.render_changed_callbacks {
+                callback();
+            }
             true
         } else {
             false
@@ -857,6 +1988,18 @@ impl Application {
             listed_slots,
             named_slots,
         } = component;
+        let processor = self.registered_processor(&type_name).unwrap_or(processor);
+        let listed_slots = expand_fragments(listed_slots);
+        let fallback = if props.has::<ErrorBoundaryMarker>() {
+            Some(
+                named_slots
+                    .get("fallback")
+                    .cloned()
+                    .unwrap_or(WidgetNode::None),
+            )
+        } else {
+            None
+        };
         let mut shared_props = match (master_shared_props, shared_props) {
             (Some(master_shared_props), Some(shared_props)) => {
                 master_shared_props.merge(shared_props)
@@ -875,6 +2018,26 @@ impl Application {
         if let Some(idref) = &mut idref {
             idref.write(id.to_owned());
         }
+        if props.has::<DeferredSlot>() {
+            for other in states.keys() {
+                if id.is_ancestor_of(other) {
+                    used_ids.insert(other.to_owned());
+                }
+            }
+            let deferred = messages
+                .keys()
+                .filter(|other| {
+                    (*other == &id || id.is_ancestor_of(other)) && states.contains_key(other)
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            for other in deferred {
+                if let Some(list) = messages.remove(&other) {
+                    self.deferred_messages.insert(other, list);
+                }
+            }
+            return WidgetNode::None;
+        }
         let (state_sender, state_receiver) = channel();
         let (animation_sender, animation_receiver) = channel();
         let messages_list = match messages.remove(&id) {
@@ -898,12 +2061,14 @@ impl Application {
                     life_cycle: &mut life_cycle,
                     named_slots,
                     listed_slots,
+                    time: self.time,
+                    delta_time: self.process_delta_time,
                     process_context,
                 };
                 ((processor)(context), false)
             }
             None => {
-                let state_data = Props::default();
+                let state_data = self.preset_states.remove(&id).unwrap_or_default();
                 let state = State::new(&state_data, StateUpdate::new(state_sender.clone()));
                 let animator = self.animators.get(&id).unwrap_or(&default_animator_state);
                 let context = WidgetContext {
@@ -917,6 +2082,8 @@ impl Application {
                     life_cycle: &mut life_cycle,
                     named_slots,
                     listed_slots,
+                    time: self.time,
+                    delta_time: self.process_delta_time,
                     process_context,
                 };
                 let node = (processor)(context);
@@ -924,8 +2091,28 @@ impl Application {
                 (node, true)
             }
         };
+        if let Ok(a11y) = props.read::<A11yProps>() {
+            self.accessibility.insert(id.to_owned(), a11y.to_owned());
+        }
+        if let Some(duration) = life_cycle.exit_duration() {
+            self.exit_durations.insert(id.to_owned(), duration);
+        } else if !self.exit_durations.is_empty() {
+            self.exit_durations.remove(&id);
+        }
+        if let Some(duration) = life_cycle.interval_duration() {
+            self.intervals.entry(id.to_owned()).or_default().0 = duration;
+        } else if !self.intervals.is_empty() {
+            self.intervals.remove(&id);
+        }
+        let effects = life_cycle.take_effects();
         let (mount, change, unmount) = life_cycle.unwrap();
         if mounted {
+            if let Ok(prefab) = props.read::<AnimationPrefab>() {
+                for (anim_id, animation) in &prefab.animations {
+                    let action = AnimationUpdateAction::Change(Some(animation.to_owned()));
+                    let _ = animation_sender.send((anim_id.to_owned(), action));
+                }
+            }
             if !mount.is_empty() {
                 if let Some(state) = new_states.get(&id) {
                     for mut closure in mount {
@@ -935,6 +2122,7 @@ impl Application {
                         let animator = Animator::new(
                             self.animators.get(&id).unwrap_or(&default_animator_state),
                             AnimationUpdate::new(animation_sender.clone()),
+                            self.process_delta_time,
                         );
                         let context = WidgetMountOrChangeContext {
                             id: &id,
@@ -959,6 +2147,7 @@ impl Application {
                     let animator = Animator::new(
                         self.animators.get(&id).unwrap_or(&default_animator_state),
                         AnimationUpdate::new(animation_sender.clone()),
+                        self.process_delta_time,
                     );
                     let context = WidgetMountOrChangeContext {
                         id: &id,
@@ -974,30 +2163,159 @@ impl Application {
                 }
             }
         }
+        if !effects.is_empty() || self.effects.contains_key(&id) {
+            let mut ran = self.effects.remove(&id).unwrap_or_default();
+            let state = if mounted {
+                new_states.get(&id)
+            } else {
+                states.get(&id)
+            };
+            if let Some(state) = state {
+                // effects no longer registered get cleaned up.
+                let removed = ran.split_off(effects.len().min(ran.len()));
+                let mut cleanups = removed
+                    .into_iter()
+                    .filter_map(|(_, cleanup)| cleanup)
+                    .collect::<Vec<_>>();
+                let mut pending = vec![];
+                for (index, (hash, effect)) in effects.into_iter().enumerate() {
+                    match ran.get_mut(index) {
+                        Some((last, _)) if *last == hash => {}
+                        Some((last, cleanup)) => {
+                            *last = hash;
+                            cleanups.extend(cleanup.take());
+                            pending.push((index, effect));
+                        }
+                        None => {
+                            ran.push((hash, None));
+                            pending.push((index, effect));
+                        }
+                    }
+                }
+                for cleanup in cleanups {
+                    let signals = SignalSender::new(id.clone(), signal_sender.clone());
+                    let context = WidgetUnmountContext {
+                        id: &id,
+                        state,
+                        messenger: message_sender,
+                        signals,
+                        process_context,
+                    };
+                    (cleanup)(context);
+                }
+                for (index, effect) in pending {
+                    let state = State::new(state, StateUpdate::new(state_sender.clone()));
+                    let messenger = Messenger::new(message_sender.clone(), &messages_list);
+                    let signals = SignalSender::new(id.clone(), signal_sender.clone());
+                    let animator = Animator::new(
+                        self.animators.get(&id).unwrap_or(&default_animator_state),
+                        AnimationUpdate::new(animation_sender.clone()),
+                        self.process_delta_time,
+                    );
+                    let context = WidgetMountOrChangeContext {
+                        id: &id,
+                        props: &props,
+                        shared_props: &shared_props,
+                        state,
+                        messenger,
+                        signals,
+                        animator,
+                        process_context,
+                    };
+                    ran[index].1 = (effect)(context);
+                }
+            }
+            if !ran.is_empty() {
+                self.effects.insert(id.clone(), ran);
+            }
+        }
         if !unmount.is_empty() {
             self.unmount_closures.insert(id.clone(), unmount);
         }
-        while let Ok((name, data)) = animation_receiver.try_recv() {
-            if let Some(states) = self.animators.get_mut(&id) {
-                states.change(name, data);
-            } else if let Some(data) = data {
-                self.animators
-                    .insert(id.to_owned(), AnimatorStates::new(name, data));
-            }
-        }
-        let new_node = self.process_node(
-            new_node,
-            states,
-            path,
-            messages,
-            new_states,
-            used_ids,
-            possible_key,
-            Some(shared_props),
-            message_sender,
-            signal_sender,
-            process_context,
-        );
+        while let Ok((name, action)) = animation_receiver.try_recv() {
+            match action {
+                AnimationUpdateAction::Change(data) => {
+                    if let Some(states) = self.animators.get_mut(&id) {
+                        states.change(name, data);
+                    } else if let Some(data) = data {
+                        self.animators
+                            .insert(id.to_owned(), AnimatorStates::new(name, data));
+                    }
+                }
+                AnimationUpdateAction::Pause(paused) => {
+                    if let Some(states) = self.animators.get_mut(&id) {
+                        states.set_animation_paused(&name, paused);
+                    }
+                }
+                AnimationUpdateAction::Seek(time) => {
+                    if let Some(states) = self.animators.get_mut(&id) {
+                        states.seek(&name, time);
+                    }
+                }
+            }
+        }
+        let new_node = if let Some(fallback) = fallback {
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                self.process_node(
+                    new_node,
+                    states,
+                    path.clone(),
+                    messages,
+                    new_states,
+                    used_ids,
+                    possible_key.clone(),
+                    Some(shared_props.clone()),
+                    message_sender,
+                    signal_sender,
+                    process_context,
+                )
+            }));
+            match result {
+                Ok(node) => node,
+                Err(error) => {
+                    let reason = if let Some(reason) = error.downcast_ref::<&str>() {
+                        reason.to_string()
+                    } else if let Some(reason) = error.downcast_ref::<String>() {
+                        reason.to_owned()
+                    } else {
+                        "Unknown reason".to_owned()
+                    };
+                    self.logger.log(
+                        LogKind::Error,
+                        &format!("Error boundary `{}` caught panic: {}", id.as_ref(), reason),
+                    );
+                    // widgets of failed content are not used anymore, so they get unmounted.
+                    used_ids.retain(|other| !id.is_ancestor_of(other));
+                    self.process_node(
+                        fallback,
+                        states,
+                        path,
+                        messages,
+                        new_states,
+                        used_ids,
+                        possible_key,
+                        Some(shared_props),
+                        message_sender,
+                        signal_sender,
+                        process_context,
+                    )
+                }
+            }
+        } else {
+            self.process_node(
+                new_node,
+                states,
+                path,
+                messages,
+                new_states,
+                used_ids,
+                possible_key,
+                Some(shared_props),
+                message_sender,
+                signal_sender,
+                process_context,
+            )
+        };
         while let Ok(data) = state_receiver.try_recv() {
             self.state_changes.insert(id.to_owned(), data);
         }
@@ -1104,75 +2422,54 @@ impl Application {
             },
             WidgetUnitNode::ContentBox(unit) => {
                 let items = std::mem::take(&mut unit.items);
-                unit.items = items
-                    .into_iter()
-                    .enumerate()
-                    .map(|(i, mut node)| {
-                        let slot = std::mem::take(&mut node.slot);
-                        node.slot = self.process_node(
-                            slot,
-                            states,
-                            path.clone(),
-                            messages,
-                            new_states,
-                            used_ids,
-                            format!("<{}>", i),
-                            master_shared_props.clone(),
-                            message_sender,
-                            signal_sender,
-                            process_context,
-                        );
-                        node
-                    })
-                    .collect::<Vec<_>>();
+                unit.items = self.process_node_items(
+                    &unit.id,
+                    items,
+                    |node| &mut node.slot,
+                    states,
+                    path,
+                    messages,
+                    new_states,
+                    used_ids,
+                    master_shared_props,
+                    message_sender,
+                    signal_sender,
+                    process_context,
+                );
             }
             WidgetUnitNode::FlexBox(unit) => {
                 let items = std::mem::take(&mut unit.items);
-                unit.items = items
-                    .into_iter()
-                    .enumerate()
-                    .map(|(i, mut node)| {
-                        let slot = std::mem::take(&mut node.slot);
-                        node.slot = self.process_node(
-                            slot,
-                            states,
-                            path.clone(),
-                            messages,
-                            new_states,
-                            used_ids,
-                            format!("<{}>", i),
-                            master_shared_props.clone(),
-                            message_sender,
-                            signal_sender,
-                            process_context,
-                        );
-                        node
-                    })
-                    .collect::<Vec<_>>();
+                unit.items = self.process_node_items(
+                    &unit.id,
+                    items,
+                    |node| &mut node.slot,
+                    states,
+                    path,
+                    messages,
+                    new_states,
+                    used_ids,
+                    master_shared_props,
+                    message_sender,
+                    signal_sender,
+                    process_context,
+                );
             }
             WidgetUnitNode::GridBox(unit) => {
                 let items = std::mem::take(&mut unit.items);
-                unit.items = items
-                    .into_iter()
-                    .enumerate()
-                    .map(|(i, mut node)| {
-                        let slot = std::mem::take(&mut node.slot);
-                        node.slot = self.process_node(
-                            slot,
-                            states,
-                            path.clone(),
-                            messages,
-                            new_states,
-                            used_ids,
-                            format!("<{}>", i),
-                            master_shared_props.clone(),
-                            message_sender,
-                            signal_sender,
-                            process_context,
-                        );
-                        node
-                    })
-                    .collect::<Vec<_>>();
+                unit.items = self.process_node_items(
+                    &unit.id,
+                    items,
+                    |node| &mut node.slot,
+                    states,
+                    path,
+                    messages,
+                    new_states,
+                    used_ids,
+                    master_shared_props,
+                    message_sender,
+                    signal_sender,
+                    process_context,
+                );
             }
             WidgetUnitNode::SizeBox(unit) => {
                 let slot = *std::mem::take(&mut unit.slot);
@@ -1194,6 +2491,151 @@ impl Application {
         unit.into()
     }
 
+    /// Processes container items, bringing back keyed items that were removed but still exit.
+    #[allow(clippy::too_many_arguments)]
+    fn process_node_items<'a, 'b, T, F>(
+        &mut self,
+        owner: &WidgetId,
+        items: Vec<T>,
+        slot: F,
+        states: &'a HashMap<WidgetId, Props>,
+        path: Vec<String>,
+        messages: &mut HashMap<WidgetId, Messages>,
+        new_states: &mut HashMap<WidgetId, Props>,
+        used_ids: &mut HashSet<WidgetId>,
+        master_shared_props: Option<Props>,
+        message_sender: &MessageSender,
+        signal_sender: &Sender<Signal>,
+        process_context: &mut ProcessContext<'b>,
+    ) -> Vec<T>
+    where
+        T: 'static + Clone + Send + Sync,
+        F: Fn(&mut T) -> &mut WidgetNode,
+    {
+        let mut present = HashSet::new();
+        let mut presence = vec![];
+        let mut result = Vec::with_capacity(items.len());
+        for (index, mut item) in items.into_iter().enumerate() {
+            let possible_key = self.child_key_strategy.child_key(slot(&mut item), index);
+            let id = Self::component_id(slot(&mut item), &path, &possible_key);
+            // newly mounted widgets are not known to declare exit yet.
+            let template = match &id {
+                Some(id) if self.exit_durations.contains_key(id) || !states.contains_key(id) => {
+                    Some(item.clone())
+                }
+                _ => None,
+            };
+            let node = std::mem::take(slot(&mut item));
+            *slot(&mut item) = self.process_node(
+                node,
+                states,
+                path.clone(),
+                messages,
+                new_states,
+                used_ids,
+                possible_key.to_owned(),
+                master_shared_props.clone(),
+                message_sender,
+                signal_sender,
+                process_context,
+            );
+            if let Some(id) = id {
+                if let Some(template) = template.filter(|_| self.exit_durations.contains_key(&id)) {
+                    if self.exiting.remove(&id).is_some() {
+                        if let Some(states) = self.animators.get_mut(&id) {
+                            states.change("exit".to_owned(), None);
+                        }
+                    }
+                    presence.push(PresenceItem {
+                        index,
+                        id: id.to_owned(),
+                        possible_key,
+                        item: Box::new(template),
+                    });
+                }
+                present.insert(id);
+            }
+            result.push(item);
+        }
+        for presence_item in self.last_presence.remove(owner).unwrap_or_default() {
+            if present.contains(&presence_item.id) {
+                continue;
+            }
+            let PresenceItem {
+                index,
+                id,
+                possible_key,
+                item,
+            } = presence_item;
+            let template = match item.downcast::<T>() {
+                Ok(template) => *template,
+                Err(_) => continue,
+            };
+            let remaining = match self.exiting.get(&id) {
+                Some(remaining) => *remaining,
+                None => {
+                    let duration = self.exit_durations.get(&id).copied().unwrap_or_default();
+                    let animation = Some(Animation::Value(AnimatedValue {
+                        name: "exit".to_owned(),
+                        duration,
+                    }));
+                    if let Some(states) = self.animators.get_mut(&id) {
+                        states.change("exit".to_owned(), animation);
+                    } else if let Some(animation) = animation {
+                        self.animators.insert(
+                            id.to_owned(),
+                            AnimatorStates::new("exit".to_owned(), animation),
+                        );
+                    }
+                    self.exiting.insert(id.to_owned(), duration);
+                    duration
+                }
+            };
+            if remaining <= 0.0 {
+                continue;
+            }
+            let mut item = template.clone();
+            let node = std::mem::take(slot(&mut item));
+            *slot(&mut item) = self.process_node(
+                node,
+                states,
+                path.clone(),
+                messages,
+                new_states,
+                used_ids,
+                possible_key.to_owned(),
+                master_shared_props.clone(),
+                message_sender,
+                signal_sender,
+                process_context,
+            );
+            result.insert(index.min(result.len()), item);
+            presence.push(PresenceItem {
+                index,
+                id,
+                possible_key,
+                item: Box::new(template),
+            });
+        }
+        if !presence.is_empty() {
+            self.presence.insert(owner.to_owned(), presence);
+        }
+        result
+    }
+
+    fn component_id(node: &WidgetNode, path: &[String], possible_key: &str) -> Option<WidgetId> {
+        if let WidgetNode::Component(component) = node {
+            let mut path = path.to_vec();
+            path.push(match &component.key {
+                Some(key) => key.to_owned(),
+                None => possible_key.to_owned(),
+            });
+            Some(WidgetId::new(&component.type_name, &path))
+        } else {
+            None
+        }
+    }
+
     fn teleport_portals(mut root: WidgetUnit) -> WidgetUnit {
         let count = Self::estimate_portals(&root);
         if count == 0 {
@@ -1201,6 +2643,12 @@ impl Application {
         }
         let mut portals = Vec::with_capacity(count);
         Self::consume_portals(&mut root, &mut portals);
+        // stable sort keeps discovery order of portals within the same layer.
+        portals.sort_by_key(|(_, layer, _)| *layer);
+        let mut portals = portals
+            .into_iter()
+            .map(|(owner, _, slot)| (owner, slot))
+            .collect();
         Self::inject_portals(&mut root, &mut portals);
         root
     }
@@ -1238,13 +2686,16 @@ impl Application {
         count
     }
 
-    fn consume_portals(unit: &mut WidgetUnit, bucket: &mut Vec<(WidgetId, PortalBoxSlot)>) {
+    fn consume_portals(unit: &mut WidgetUnit, bucket: &mut Vec<(WidgetId, i32, PortalBoxSlot)>) {
         match unit {
             WidgetUnit::None | WidgetUnit::ImageBox(_) | WidgetUnit::TextBox(_) => {}
             WidgetUnit::AreaBox(b) => Self::consume_portals(&mut b.slot, bucket),
             WidgetUnit::PortalBox(b) => {
                 let PortalBox {
-                    owner, mut slot, ..
+                    owner,
+                    mut slot,
+                    layer,
+                    ..
                 } = std::mem::take(b);
                 Self::consume_portals(
                     match &mut *slot {
@@ -1255,7 +2706,7 @@ impl Application {
                     },
                     bucket,
                 );
-                bucket.push((owner, *slot));
+                bucket.push((owner, layer, *slot));
             }
             WidgetUnit::ContentBox(b) => {
                 for item in &mut b.items {
@@ -1276,120 +2727,122 @@ impl Application {
         }
     }
 
-    fn inject_portals(unit: &mut WidgetUnit, portals: &mut Vec<(WidgetId, PortalBoxSlot)>) -> bool {
+    /// Injects portals into their owners, wherever in the tree they are. Portals injected into
+    /// an owner are searched for owners too, so portals nested in other portals get their owners
+    /// regardless of the order the portals were discovered in.
+    fn inject_portals(unit: &mut WidgetUnit, portals: &mut Vec<(WidgetId, PortalBoxSlot)>) {
         if portals.is_empty() {
-            return false;
+            return;
         }
-        while let Some(data) = unit.as_data() {
-            let found = portals.iter().position(|(id, _)| data.id() == id);
-            if let Some(index) = found {
-                let slot = portals.swap_remove(index).1;
-                match unit {
-                    WidgetUnit::None
-                    | WidgetUnit::PortalBox(_)
-                    | WidgetUnit::ImageBox(_)
-                    | WidgetUnit::TextBox(_) => {}
-                    WidgetUnit::AreaBox(b) => {
-                        match slot {
-                            PortalBoxSlot::Slot(slot) => b.slot = Box::new(slot),
-                            PortalBoxSlot::ContentItem(item) => b.slot = Box::new(item.slot),
-                            PortalBoxSlot::FlexItem(item) => b.slot = Box::new(item.slot),
-                            PortalBoxSlot::GridItem(item) => b.slot = Box::new(item.slot),
-                        }
-                        if !Self::inject_portals(&mut b.slot, portals) {
-                            return false;
-                        }
-                    }
-                    WidgetUnit::ContentBox(b) => {
-                        b.items.push(match slot {
-                            PortalBoxSlot::Slot(slot) => ContentBoxItem {
-                                slot,
-                                ..Default::default()
-                            },
-                            PortalBoxSlot::ContentItem(item) => item,
-                            PortalBoxSlot::FlexItem(item) => ContentBoxItem {
-                                slot: item.slot,
-                                ..Default::default()
-                            },
-                            PortalBoxSlot::GridItem(item) => ContentBoxItem {
-                                slot: item.slot,
-                                ..Default::default()
-                            },
-                        });
-                        for item in &mut b.items {
-                            if !Self::inject_portals(&mut item.slot, portals) {
-                                return false;
-                            }
-                        }
-                    }
-                    WidgetUnit::FlexBox(b) => {
-                        b.items.push(match slot {
-                            PortalBoxSlot::Slot(slot) => FlexBoxItem {
-                                slot,
-                                ..Default::default()
-                            },
-                            PortalBoxSlot::ContentItem(item) => FlexBoxItem {
-                                slot: item.slot,
-                                ..Default::default()
-                            },
-                            PortalBoxSlot::FlexItem(item) => item,
-                            PortalBoxSlot::GridItem(item) => FlexBoxItem {
-                                slot: item.slot,
-                                ..Default::default()
-                            },
-                        });
-                        for item in &mut b.items {
-                            if !Self::inject_portals(&mut item.slot, portals) {
-                                return false;
-                            }
-                        }
-                    }
-                    WidgetUnit::GridBox(b) => {
-                        b.items.push(match slot {
-                            PortalBoxSlot::Slot(slot) => GridBoxItem {
-                                slot,
-                                ..Default::default()
-                            },
-                            PortalBoxSlot::ContentItem(item) => GridBoxItem {
-                                slot: item.slot,
-                                ..Default::default()
-                            },
-                            PortalBoxSlot::FlexItem(item) => GridBoxItem {
-                                slot: item.slot,
-                                ..Default::default()
-                            },
-                            PortalBoxSlot::GridItem(item) => item,
-                        });
-                        for item in &mut b.items {
-                            if !Self::inject_portals(&mut item.slot, portals) {
-                                return false;
-                            }
-                        }
-                    }
-                    WidgetUnit::SizeBox(b) => {
-                        match slot {
-                            PortalBoxSlot::Slot(slot) => b.slot = Box::new(slot),
-                            PortalBoxSlot::ContentItem(item) => b.slot = Box::new(item.slot),
-                            PortalBoxSlot::FlexItem(item) => b.slot = Box::new(item.slot),
-                            PortalBoxSlot::GridItem(item) => b.slot = Box::new(item.slot),
-                        }
-                        if !Self::inject_portals(&mut b.slot, portals) {
-                            return false;
-                        }
-                    }
+        if let Some(data) = unit.as_data() {
+            let id = data.id().to_owned();
+            let mut index = 0;
+            while index < portals.len() {
+                if portals[index].0 == id {
+                    // portals are in injection order, so it has to be kept.
+                    let slot = portals.remove(index).1;
+                    Self::inject_portal(unit, slot);
+                } else {
+                    index += 1;
+                }
+            }
+        }
+        match unit {
+            WidgetUnit::None
+            | WidgetUnit::PortalBox(_)
+            | WidgetUnit::ImageBox(_)
+            | WidgetUnit::TextBox(_) => {}
+            WidgetUnit::AreaBox(b) => Self::inject_portals(&mut b.slot, portals),
+            WidgetUnit::ContentBox(b) => {
+                for item in &mut b.items {
+                    Self::inject_portals(&mut item.slot, portals);
+                }
+            }
+            WidgetUnit::FlexBox(b) => {
+                for item in &mut b.items {
+                    Self::inject_portals(&mut item.slot, portals);
+                }
+            }
+            WidgetUnit::GridBox(b) => {
+                for item in &mut b.items {
+                    Self::inject_portals(&mut item.slot, portals);
                 }
-            } else {
-                break;
             }
+            WidgetUnit::SizeBox(b) => Self::inject_portals(&mut b.slot, portals),
         }
-        true
     }
 
-    fn node_to_prefab(&self, data: &WidgetNode) -> Result<WidgetNodePrefab, ApplicationError> {
-        Ok(match data {
-            WidgetNode::None => WidgetNodePrefab::None,
-            WidgetNode::Component(data) => {
-                WidgetNodePrefab::Component(self.component_to_prefab(data)?)
+    fn inject_portal(unit: &mut WidgetUnit, slot: PortalBoxSlot) {
+        match unit {
+            WidgetUnit::None
+            | WidgetUnit::PortalBox(_)
+            | WidgetUnit::ImageBox(_)
+            | WidgetUnit::TextBox(_) => {}
+            WidgetUnit::AreaBox(b) => match slot {
+                PortalBoxSlot::Slot(slot) => b.slot = Box::new(slot),
+                PortalBoxSlot::ContentItem(item) => b.slot = Box::new(item.slot),
+                PortalBoxSlot::FlexItem(item) => b.slot = Box::new(item.slot),
+                PortalBoxSlot::GridItem(item) => b.slot = Box::new(item.slot),
+            },
+            WidgetUnit::ContentBox(b) => b.items.push(match slot {
+                PortalBoxSlot::Slot(slot) => ContentBoxItem {
+                    slot,
+                    ..Default::default()
+                },
+                PortalBoxSlot::ContentItem(item) => item,
+                PortalBoxSlot::FlexItem(item) => ContentBoxItem {
+                    slot: item.slot,
+                    ..Default::default()
+                },
+                PortalBoxSlot::GridItem(item) => ContentBoxItem {
+                    slot: item.slot,
+                    ..Default::default()
+                },
+            }),
+            WidgetUnit::FlexBox(b) => b.items.push(match slot {
+                PortalBoxSlot::Slot(slot) => FlexBoxItem {
+                    slot,
+                    ..Default::default()
+                },
+                PortalBoxSlot::ContentItem(item) => FlexBoxItem {
+                    slot: item.slot,
+                    ..Default::default()
+                },
+                PortalBoxSlot::FlexItem(item) => item,
+                PortalBoxSlot::GridItem(item) => FlexBoxItem {
+                    slot: item.slot,
+                    ..Default::default()
+                },
+            }),
+            WidgetUnit::GridBox(b) => b.items.push(match slot {
+                PortalBoxSlot::Slot(slot) => GridBoxItem {
+                    slot,
+                    ..Default::default()
+                },
+                PortalBoxSlot::ContentItem(item) => GridBoxItem {
+                    slot: item.slot,
+                    ..Default::default()
+                },
+                PortalBoxSlot::FlexItem(item) => GridBoxItem {
+                    slot: item.slot,
+                    ..Default::default()
+                },
+                PortalBoxSlot::GridItem(item) => item,
+            }),
+            WidgetUnit::SizeBox(b) => match slot {
+                PortalBoxSlot::Slot(slot) => b.slot = Box::new(slot),
+                PortalBoxSlot::ContentItem(item) => b.slot = Box::new(item.slot),
+                PortalBoxSlot::FlexItem(item) => b.slot = Box::new(item.slot),
+                PortalBoxSlot::GridItem(item) => b.slot = Box::new(item.slot),
+            },
+        }
+    }
+
+    fn node_to_prefab(&self, data: &WidgetNode) -> Result<WidgetNodePrefab, ApplicationError> {
+        Ok(match data {
+            WidgetNode::None => WidgetNodePrefab::None,
+            WidgetNode::Component(data) => {
+                WidgetNodePrefab::Component(self.component_to_prefab(data)?)
             }
             WidgetNode::Unit(data) => WidgetNodePrefab::Unit(self.unit_to_prefab(data)?),
             WidgetNode::Tuple(data) => WidgetNodePrefab::Tuple(self.tuple_to_prefab(data)?),
@@ -1509,7 +2962,12 @@ impl Application {
                     })
                 }
             }),
-            owner: data.owner.to_owned(),
+            owner: data
+                .owner_ref
+                .as_ref()
+                .and_then(|owner| owner.read())
+                .unwrap_or_else(|| data.owner.to_owned()),
+            layer: data.layer,
         })
     }
 
@@ -1531,6 +2989,9 @@ impl Application {
                 })
                 .collect::<Result<_, ApplicationError>>()?,
             clipping: data.clipping,
+            clip_x: data.clip_x,
+            clip_y: data.clip_y,
+            padding: data.padding,
             transform: data.transform,
         })
     }
@@ -1555,6 +3016,7 @@ impl Application {
             direction: data.direction,
             separation: data.separation,
             wrap: data.wrap,
+            padding: data.padding,
             transform: data.transform,
         })
     }
@@ -1578,6 +3040,9 @@ impl Application {
                 .collect::<Result<_, ApplicationError>>()?,
             cols: data.cols,
             rows: data.rows,
+            col_gap: data.col_gap,
+            row_gap: data.row_gap,
+            padding: data.padding,
             transform: data.transform,
         })
     }
@@ -1626,11 +3091,149 @@ impl Application {
             vertical_align: data.vertical_align,
             direction: data.direction,
             font: data.font.clone(),
+            font_size_mode: data.font_size_mode,
+            line_height: data.line_height,
+            letter_spacing: data.letter_spacing,
+            wrap: data.wrap,
+            auto_size: data.auto_size,
+            overflow: data.overflow,
             color: data.color,
             transform: data.transform,
         })
     }
 
+    fn validate_node_prefab(
+        &self,
+        data: &WidgetNodePrefab,
+        path: &str,
+        errors: &mut Vec<PrefabValidationError>,
+    ) {
+        match data {
+            WidgetNodePrefab::None => {}
+            WidgetNodePrefab::Component(data) => {
+                if !self.component_mappings.contains_key(&data.type_name) {
+                    errors.push(PrefabValidationError::ComponentMappingNotFound {
+                        path: Self::prefab_path(path, ""),
+                        type_name: data.type_name.to_owned(),
+                    });
+                }
+                self.validate_props_prefab(&data.props, path, errors);
+                if let Some(shared_props) = &data.shared_props {
+                    self.validate_props_prefab(shared_props, path, errors);
+                }
+                for (index, item) in data.listed_slots.iter().enumerate() {
+                    let path = format!("{}/listed_slots/{}", path, index);
+                    self.validate_node_prefab(item, &path, errors);
+                }
+                for (name, item) in &data.named_slots {
+                    let path = format!("{}/named_slots/{}", path, name);
+                    self.validate_node_prefab(item, &path, errors);
+                }
+            }
+            WidgetNodePrefab::Unit(data) => {
+                fn items<'a>(
+                    items: impl Iterator<Item = &'a WidgetNodePrefab>,
+                ) -> Vec<(&'a WidgetNodePrefab, String)> {
+                    items
+                        .enumerate()
+                        .map(|(index, slot)| (slot, format!("items/{}/slot", index)))
+                        .collect()
+                }
+                let (props, slots) = match data {
+                    WidgetUnitNodePrefab::None => (None, vec![]),
+                    WidgetUnitNodePrefab::AreaBox(data) => {
+                        (None, vec![(&*data.slot, "slot".to_owned())])
+                    }
+                    WidgetUnitNodePrefab::PortalBox(data) => {
+                        let slot = match &*data.slot {
+                            PortalBoxSlotNodePrefab::Slot(slot) => (slot, "slot".to_owned()),
+                            PortalBoxSlotNodePrefab::ContentItem(item) => {
+                                (&item.slot, "slot/slot".to_owned())
+                            }
+                            PortalBoxSlotNodePrefab::FlexItem(item) => {
+                                (&item.slot, "slot/slot".to_owned())
+                            }
+                            PortalBoxSlotNodePrefab::GridItem(item) => {
+                                (&item.slot, "slot/slot".to_owned())
+                            }
+                        };
+                        (None, vec![slot])
+                    }
+                    WidgetUnitNodePrefab::ContentBox(data) => (
+                        Some(&data.props),
+                        items(data.items.iter().map(|item| &item.slot)),
+                    ),
+                    WidgetUnitNodePrefab::FlexBox(data) => (
+                        Some(&data.props),
+                        items(data.items.iter().map(|item| &item.slot)),
+                    ),
+                    WidgetUnitNodePrefab::GridBox(data) => (
+                        Some(&data.props),
+                        items(data.items.iter().map(|item| &item.slot)),
+                    ),
+                    WidgetUnitNodePrefab::SizeBox(data) => {
+                        (Some(&data.props), vec![(&*data.slot, "slot".to_owned())])
+                    }
+                    WidgetUnitNodePrefab::ImageBox(data) => (Some(&data.props), vec![]),
+                    WidgetUnitNodePrefab::TextBox(data) => (Some(&data.props), vec![]),
+                };
+                if let Some(props) = props {
+                    self.validate_props_prefab(props, path, errors);
+                }
+                for (slot, child) in slots {
+                    self.validate_node_prefab(slot, &Self::prefab_path(path, &child), errors);
+                }
+            }
+            WidgetNodePrefab::Tuple(data) => {
+                for (index, item) in data.iter().enumerate() {
+                    let path = format!("{}/{}", path, index);
+                    self.validate_node_prefab(item, &path, errors);
+                }
+            }
+        }
+    }
+
+    fn validate_props_prefab(
+        &self,
+        data: &PrefabValue,
+        path: &str,
+        errors: &mut Vec<PrefabValidationError>,
+    ) {
+        if data.is_null() {
+            return;
+        }
+        match PropsGroupPrefab::from_prefab(data.to_owned()) {
+            Ok(group) => {
+                let mut names = group.data.keys().collect::<Vec<_>>();
+                names.sort();
+                for name in names {
+                    if !self.props_registry.is_registered(name) {
+                        errors.push(PrefabValidationError::PropsNotRegistered {
+                            path: Self::prefab_path(path, ""),
+                            name: name.to_owned(),
+                        });
+                    }
+                }
+            }
+            Err(error) => errors.push(PrefabValidationError::Prefab {
+                path: Self::prefab_path(path, ""),
+                error,
+            }),
+        }
+    }
+
+    fn prefab_path(path: &str, child: &str) -> String {
+        if child.is_empty() {
+            if path.is_empty() {
+                "/".to_owned()
+            } else {
+                path.to_owned()
+            }
+        } else {
+            format!("{}/{}", path, child)
+        }
+    }
+
     fn node_from_prefab(&self, data: WidgetNodePrefab) -> Result<WidgetNode, ApplicationError> {
         Ok(match data {
             WidgetNodePrefab::None => WidgetNode::None,
@@ -1758,6 +3361,8 @@ impl Application {
                 }
             }),
             owner: data.owner,
+            owner_ref: None,
+            layer: data.layer,
         })
     }
 
@@ -1779,6 +3384,9 @@ impl Application {
                 })
                 .collect::<Result<_, ApplicationError>>()?,
             clipping: data.clipping,
+            clip_x: data.clip_x,
+            clip_y: data.clip_y,
+            padding: data.padding,
             transform: data.transform,
         })
     }
@@ -1803,6 +3411,7 @@ impl Application {
             direction: data.direction,
             separation: data.separation,
             wrap: data.wrap,
+            padding: data.padding,
             transform: data.transform,
         })
     }
@@ -1826,6 +3435,9 @@ impl Application {
                 .collect::<Result<_, ApplicationError>>()?,
             cols: data.cols,
             rows: data.rows,
+            col_gap: data.col_gap,
+            row_gap: data.row_gap,
+            padding: data.padding,
             transform: data.transform,
         })
     }
@@ -1874,6 +3486,12 @@ impl Application {
             vertical_align: data.vertical_align,
             direction: data.direction,
             font: data.font,
+            font_size_mode: data.font_size_mode,
+            line_height: data.line_height,
+            letter_spacing: data.letter_spacing,
+            wrap: data.wrap,
+            auto_size: data.auto_size,
+            overflow: data.overflow,
             color: data.color,
             transform: data.transform,
         })
@@ -2034,3 +3652,1566 @@ impl<'a> ProcessContext<'a> {
             || self.mutable.contains_key(&t)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        layout::default_layout_engine::DefaultLayoutEngine,
+        pre_hooks, widget,
+        widget::component::{
+            containers::{
+                content_box::content_box,
+                portal_box::{portal_box, PortalsContainer},
+                size_box::size_box,
+            },
+            image_box::image_box,
+            text_box::{text_box, TextBoxProps},
+            use_interval, use_prop_change,
+        },
+        widget::unit::content::ContentBoxItemLayout,
+        widget::WidgetRef,
+        PropsData,
+    };
+
+    fn exiting_item(context: WidgetContext) -> WidgetNode {
+        context.life_cycle.exit(1.0);
+        image_box(context)
+    }
+
+    fn animated_item(context: WidgetContext) -> WidgetNode {
+        context.life_cycle.mount(|context| {
+            let _ = context.animator.change(
+                "anim",
+                Some(Animation::Value(AnimatedValue {
+                    name: "value".to_owned(),
+                    duration: 10.0,
+                })),
+            );
+        });
+        image_box(context)
+    }
+
+    fn stateful_item(context: WidgetContext) -> WidgetNode {
+        let text = context.state.read_cloned_or_default::<String>();
+        context.props.write(TextBoxProps {
+            text,
+            ..Default::default()
+        });
+        text_box(context)
+    }
+
+    fn items_count(application: &Application) -> usize {
+        match application.rendered_tree() {
+            WidgetUnit::ContentBox(unit) => unit.items.len(),
+            _ => 0,
+        }
+    }
+
+    #[test]
+    fn test_exit_animation() {
+        let mut application = Application::new();
+        application.animations_delta_time = 0.5;
+        application.apply(widget! {
+            (#{"app"} content_box [
+                (#{"a"} exiting_item)
+                (#{"b"} exiting_item)
+            ])
+        });
+        application.process();
+        let id = WidgetId::new("exiting_item", &["app".to_owned(), "b".to_owned()]);
+        assert!(application.state_read(&id).is_some());
+        assert_eq!(items_count(&application), 2);
+
+        application.apply(widget! {
+            (#{"app"} content_box [
+                (#{"a"} exiting_item)
+            ])
+        });
+        application.process();
+        assert!(application.is_exiting(&id));
+        assert!(application.state_read(&id).is_some());
+        assert_eq!(items_count(&application), 2);
+        application.process();
+        assert!(application.is_exiting(&id));
+        assert!(application.state_read(&id).is_some());
+        assert_eq!(items_count(&application), 2);
+        application.process();
+        assert!(!application.is_exiting(&id));
+        assert!(application.state_read(&id).is_none());
+        assert_eq!(items_count(&application), 1);
+        assert!(!application.process());
+    }
+
+    #[test]
+    fn test_min_process_interval() {
+        let mut application = Application::new();
+        application.apply(widget! { (#{"app"} animated_item) });
+        assert!(application.process());
+        let id = WidgetId::new("animated_item", &["app".to_owned()]);
+        let time = |application: &Application| {
+            application.animators[&id]
+                .value_progress("anim", "value")
+                .unwrap()
+                .time
+        };
+
+        application.set_min_process_interval(1.0);
+        application.animations_delta_time = 0.25;
+        assert!(!application.process());
+        assert!(!application.process());
+        assert!(!application.process());
+        assert!(application.process());
+        assert!((time(&application) - 1.0).abs() < 1.0e-6);
+
+        assert!(!application.process());
+        application.mark_dirty();
+        assert!(application.process());
+        assert!((time(&application) - 1.5).abs() < 1.0e-6);
+
+        application.change_notifier().change();
+        assert!(application.process());
+        assert!((time(&application) - 1.75).abs() < 1.0e-6);
+    }
+
+    #[derive(PropsData, Debug, Default, Clone, Serialize, Deserialize)]
+    #[props_data(crate::props::PropsData)]
+    #[prefab(crate::Prefab)]
+    struct Counter(usize);
+
+    #[test]
+    fn test_state_update() {
+        let mut application = Application::new();
+        application.apply(widget! { (#{"app"} image_box) });
+        application.process();
+        let id = WidgetId::new("image_box", &["app".to_owned()]);
+        let counter = |application: &Application| {
+            application
+                .state_read(&id)
+                .unwrap()
+                .read_cloned_or_default::<Counter>()
+                .0
+        };
+
+        application.state_update(&id, |state| state.write(Counter(1)));
+        application.state_update(&id, |state| {
+            state.mutate_cloned(|Counter(value): &mut Counter| *value += 10)
+        });
+        application.process();
+        assert_eq!(counter(&application), 11);
+
+        // mutating methods other than update replace pending change.
+        application.state_update(&id, |state| state.write(Counter(1)));
+        application.state_mutate_cloned(&id, |state| {
+            state.mutate_cloned(|Counter(value): &mut Counter| *value += 10)
+        });
+        application.process();
+        assert_eq!(counter(&application), 21);
+
+        let missing = WidgetId::new("image_box", &["missing".to_owned()]);
+        application.state_update(&missing, |state| state.write(Counter(1)));
+        assert!(application.state_read(&missing).is_none());
+    }
+
+    #[test]
+    fn test_render_changed_callbacks() {
+        let calls = Arc::new(std::sync::Mutex::new(vec![]));
+        let mut application = Application::new();
+        for index in 0..2 {
+            let calls = calls.clone();
+            application.on_render_changed(move || calls.lock().unwrap().push(index));
+        }
+        application.apply(widget! { (#{"app"} image_box) });
+        assert!(application.process());
+        assert!(application.does_render_changed());
+        assert_eq!(*calls.lock().unwrap(), vec![0, 1]);
+
+        assert!(!application.process());
+        assert!(!application.does_render_changed());
+        assert_eq!(*calls.lock().unwrap(), vec![0, 1]);
+
+        application.mark_dirty();
+        assert!(application.process());
+        assert_eq!(*calls.lock().unwrap(), vec![0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn test_disabled_portals_teleport() {
+        let idref = WidgetRef::default();
+        let mut application = Application::new();
+        application.set_teleport_portals(false);
+        application.apply(widget! {
+            (#{"app"} | {idref.clone()} content_box | {PortalsContainer(idref)} [
+                (#{"panel"} size_box {
+                    content = (#{"modal"} portal_box {
+                        content = (#{"dialog"} image_box)
+                    })
+                })
+            ])
+        });
+        application.process();
+        let portal = match application.rendered_tree() {
+            WidgetUnit::ContentBox(unit) => match &unit.items[0].slot {
+                WidgetUnit::SizeBox(unit) => match &*unit.slot {
+                    WidgetUnit::PortalBox(unit) => Some(unit.id.path().to_owned()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        };
+        assert_eq!(portal.as_deref(), Some("app/panel/modal"));
+
+        application.set_teleport_portals(true);
+        application.mark_dirty();
+        application.process();
+        match application.rendered_tree() {
+            WidgetUnit::ContentBox(unit) => {
+                assert_eq!(unit.items.len(), 2);
+                assert_eq!(
+                    unit.items[1].slot.as_data().map(|data| data.id().key()),
+                    Some("dialog")
+                );
+            }
+            _ => panic!("Expected content box"),
+        }
+    }
+
+    #[test]
+    fn test_portal_owner_after_portal() {
+        let idref = WidgetRef::default();
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"app"} content_box | {PortalsContainer(idref.clone())} [
+                (#{"modal"} portal_box {
+                    content = (#{"dialog"} image_box)
+                })
+                (#{"panel"} size_box {
+                    content = (#{"layer"} | {idref} content_box [
+                        (#{"background"} image_box)
+                    ])
+                })
+            ])
+        });
+        application.process();
+        let keys = match application.rendered_tree() {
+            WidgetUnit::ContentBox(unit) => match &unit.items[1].slot {
+                WidgetUnit::SizeBox(unit) => match &*unit.slot {
+                    WidgetUnit::ContentBox(unit) => unit
+                        .items
+                        .iter()
+                        .filter_map(|item| item.slot.as_data())
+                        .map(|data| data.id().key().to_owned())
+                        .collect::<Vec<_>>(),
+                    _ => vec![],
+                },
+                _ => vec![],
+            },
+            _ => vec![],
+        };
+        assert_eq!(keys, vec!["background", "dialog"]);
+    }
+
+    #[test]
+    fn test_weak_change_notifier() {
+        let mut application = Application::new();
+        let weak = application.change_notifier().downgrade();
+        let mut notifier = weak.upgrade().unwrap();
+        notifier.change();
+        assert!(application.change_notifier().has_changed());
+        assert!(application.process());
+
+        drop(notifier);
+        drop(application);
+        assert!(weak.upgrade().is_none());
+        assert!(WeakChangeNotifier::default().upgrade().is_none());
+    }
+
+    #[test]
+    fn test_active_animations() {
+        let mut application = Application::new();
+        application.animations_delta_time = 4.0;
+        assert!(!application.has_active_animations());
+        application.apply(widget! {
+            (#{"app"} content_box [
+                (#{"a"} animated_item)
+                (#{"b"} animated_item)
+            ])
+        });
+        application.process();
+        assert_eq!(application.active_animation_count(), 2);
+        assert!(application.has_active_animations());
+
+        let id = WidgetId::new("animated_item", &["app".to_owned(), "a".to_owned()]);
+        application
+            .animator_states_mut(&id)
+            .unwrap()
+            .set_animation_paused("anim", true);
+        assert_eq!(application.active_animation_count(), 1);
+
+        while application.process() {}
+        assert_eq!(application.active_animation_count(), 0);
+        assert!(!application.has_active_animations());
+    }
+
+    #[test]
+    fn test_time_scale() {
+        let mut application = Application::new();
+        application.apply(widget! { (#{"app"} animated_item) });
+        application.process();
+        let id = WidgetId::new("animated_item", &["app".to_owned()]);
+        let factor = |application: &Application| {
+            application.animators[&id]
+                .value_progress_factor("anim", "value")
+                .unwrap()
+        };
+
+        application.set_time_scale(0.5);
+        application.animations_delta_time = 2.5;
+        for _ in 0..4 {
+            assert!(application.process());
+        }
+        assert!((factor(&application) - 0.5).abs() < 1.0e-6);
+
+        application.set_time_scale(0.0);
+        assert!(!application.process());
+        assert!((factor(&application) - 0.5).abs() < 1.0e-6);
+        assert!(application.animators[&id].in_progress());
+    }
+
+    #[test]
+    fn test_apply_with() {
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"app"} content_box [
+                (#{"a"} stateful_item)
+            ])
+        });
+        application.process();
+        let a = WidgetId::new("stateful_item", &["app".to_owned(), "a".to_owned()]);
+        let b = WidgetId::new("stateful_item", &["app".to_owned(), "b".to_owned()]);
+        application.apply_with(|transaction| {
+            transaction
+                .tree(widget! {
+                    (#{"app"} content_box [
+                        (#{"a"} stateful_item)
+                        (#{"b"} stateful_item)
+                    ])
+                })
+                .state(&a, "A".to_owned())
+                .state(&b, "B".to_owned());
+        });
+        assert!(application.process());
+        let texts = match application.rendered_tree() {
+            WidgetUnit::ContentBox(unit) => unit
+                .items
+                .iter()
+                .filter_map(|item| match &item.slot {
+                    WidgetUnit::TextBox(unit) => Some(unit.text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>(),
+            _ => vec![],
+        };
+        assert_eq!(texts, vec!["A", "B"]);
+        assert_eq!(
+            application
+                .state_read(&b)
+                .unwrap()
+                .read_cloned_or_default::<String>(),
+            "B"
+        );
+    }
+
+    fn use_message_receiver(context: &mut WidgetContext) {
+        context.life_cycle.change(|context| {
+            for msg in context.messenger.messages {
+                if let Some(value) = msg.as_any().downcast_ref::<i32>() {
+                    context.signals.write(*value);
+                }
+            }
+        });
+    }
+
+    #[pre_hooks(use_message_receiver)]
+    fn message_receiver(mut context: WidgetContext) -> WidgetNode {
+        image_box(context)
+    }
+
+    fn use_message_sender(context: &mut WidgetContext) {
+        context.life_cycle.change(|context| {
+            for msg in context.messenger.messages {
+                if msg.as_any().downcast_ref::<()>().is_some() {
+                    let id = WidgetId::new(
+                        "message_receiver",
+                        &["app".to_owned(), "receiver".to_owned()],
+                    );
+                    for value in 4..=6 {
+                        context.messenger.write(id.to_owned(), value);
+                    }
+                }
+            }
+        });
+    }
+
+    #[pre_hooks(use_message_sender)]
+    fn message_sender(mut context: WidgetContext) -> WidgetNode {
+        image_box(context)
+    }
+
+    #[test]
+    fn test_messages_order() {
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"app"} content_box [
+                (#{"sender"} message_sender)
+                (#{"receiver"} message_receiver)
+            ])
+        });
+        application.process();
+        let receiver = WidgetId::new(
+            "message_receiver",
+            &["app".to_owned(), "receiver".to_owned()],
+        );
+        let sender = WidgetId::new("message_sender", &["app".to_owned(), "sender".to_owned()]);
+        let received = |application: &Application| {
+            application
+                .signals()
+                .iter()
+                .filter_map(|(_, msg)| msg.as_any().downcast_ref::<i32>().copied())
+                .collect::<Vec<_>>()
+        };
+
+        for value in 1..=3 {
+            application.send_message(&receiver, value);
+        }
+        application.process();
+        assert_eq!(received(&application), vec![1, 2, 3]);
+
+        application.send_message(&sender, ());
+        application.process();
+        application.process();
+        assert_eq!(received(&application), vec![4, 5, 6]);
+    }
+
+    fn use_ticking(context: &mut WidgetContext) {
+        context.life_cycle.change(|context| {
+            for msg in context.messenger.messages {
+                if msg.as_any().downcast_ref::<IntervalMessage>().is_some() {
+                    context.signals.write(());
+                }
+            }
+        });
+    }
+
+    #[pre_hooks(use_ticking)]
+    fn ticking(mut context: WidgetContext) -> WidgetNode {
+        context.use_hook(use_interval(1.0));
+        image_box(context)
+    }
+
+    fn effect_item(mut context: WidgetContext) -> WidgetNode {
+        let value = context.props.read_cloned_or_default::<u32>();
+        context.use_effect_with_cleanup(value, move |context| {
+            context.signals.write(format!("run {}", value));
+            move |context: WidgetUnmountContext| {
+                context.signals.write(format!("cleanup {}", value));
+            }
+        });
+        image_box(context)
+    }
+
+    fn echo_item(context: WidgetContext) -> WidgetNode {
+        context.life_cycle.mount(|context| {
+            context
+                .messenger
+                .write_delayed(context.id.to_owned(), 1u32, 1);
+        });
+        context.life_cycle.change(|context| {
+            for msg in context.messenger.messages {
+                if let Some(value) = msg.as_any().downcast_ref::<u32>() {
+                    context.signals.write(*value);
+                }
+            }
+        });
+        image_box(context)
+    }
+
+    fn pairs(context: WidgetContext) -> WidgetNode {
+        let WidgetContext {
+            key, listed_slots, ..
+        } = context;
+        let items = listed_slots
+            .into_iter()
+            .flat_map(|item| {
+                let [a, b] = item.unpack_tuple();
+                [a, b]
+            })
+            .collect::<Vec<_>>();
+        widget! {
+            (#{key} content_box |[items]|)
+        }
+    }
+
+    #[test]
+    fn test_keyed_tuple() {
+        let tree = |keys: [&str; 2]| {
+            let tuples = keys
+                .iter()
+                .map(|key| {
+                    WidgetNode::pack_tuple_keyed(
+                        key,
+                        [
+                            WidgetComponent::new(stateful_item, "stateful_item").into(),
+                            WidgetComponent::new(image_box, "image_box").into(),
+                        ],
+                    )
+                })
+                .collect::<Vec<_>>();
+            widget! { (#{"root"} pairs |[tuples]|) }
+        };
+        let items = |application: &Application| -> Vec<(String, String)> {
+            match application.rendered_tree() {
+                WidgetUnit::ContentBox(unit) => unit
+                    .items
+                    .iter()
+                    .map(|item| match &item.slot {
+                        WidgetUnit::TextBox(unit) => (unit.id.key().to_owned(), unit.text.clone()),
+                        unit => (unit.as_data().unwrap().id().key().to_owned(), String::new()),
+                    })
+                    .collect(),
+                _ => vec![],
+            }
+        };
+
+        let mut application = Application::new();
+        application.apply(tree(["x", "y"]));
+        application.process();
+        let id = match application.rendered_tree() {
+            WidgetUnit::ContentBox(unit) => unit.items[0].slot.as_data().unwrap().id().to_owned(),
+            _ => unreachable!(),
+        };
+        assert_eq!(id.key(), "x-0");
+        application.apply_with(|transaction| {
+            transaction.state(&id, "hello".to_owned());
+        });
+        application.process();
+
+        application.apply(tree(["y", "x"]));
+        application.process();
+        assert_eq!(
+            items(&application),
+            vec![
+                ("y-0".to_owned(), "".to_owned()),
+                ("y-1".to_owned(), "".to_owned()),
+                ("x-0".to_owned(), "hello".to_owned()),
+                ("x-1".to_owned(), "".to_owned()),
+            ]
+        );
+    }
+
+    fn counted_item(context: WidgetContext) -> WidgetNode {
+        if let Some(calls) = context.process_context.owned_mut::<usize>() {
+            *calls += 1;
+        }
+        context.life_cycle.mount(|context| {
+            let _ = context.state.write("mounted".to_owned());
+        });
+        widget! {
+            (#{"inner"} stateful_item)
+        }
+    }
+
+    #[test]
+    fn test_deferred_slot() {
+        let tree = |deferred: bool| {
+            let mut props = Props::default();
+            if deferred {
+                props.write(DeferredSlot);
+            }
+            widget! {
+                (#{"app"} content_box [
+                    (#{"item"} counted_item: {props})
+                ])
+            }
+        };
+        let calls = |application: &mut Application| {
+            let mut context = ProcessContext::new();
+            context.insert_owned(0_usize);
+            application.forced_process_with_context(&mut context);
+            context.owned_mut::<usize>().copied().unwrap()
+        };
+        let id = WidgetId::new("counted_item", &["app".to_owned(), "item".to_owned()]);
+        let inner = WidgetId::new(
+            "stateful_item",
+            &["app".to_owned(), "item".to_owned(), "inner".to_owned()],
+        );
+        let mut application = Application::new();
+        application.apply(tree(false));
+        assert_eq!(calls(&mut application), 1);
+        assert_eq!(calls(&mut application), 1);
+        assert!(application.state_read(&inner).is_some());
+
+        application.apply(tree(true));
+        assert_eq!(calls(&mut application), 0);
+        assert_eq!(calls(&mut application), 0);
+        assert_eq!(
+            application
+                .state_read(&id)
+                .unwrap()
+                .read_cloned::<String>()
+                .unwrap(),
+            "mounted"
+        );
+        assert!(application.state_read(&inner).is_some());
+
+        application.apply(tree(false));
+        assert_eq!(calls(&mut application), 1);
+        assert!(application.state_read(&inner).is_some());
+    }
+
+    #[test]
+    fn test_deferred_slot_messages() {
+        let tree = |deferred: bool| {
+            let mut props = Props::default();
+            if deferred {
+                props.write(DeferredSlot);
+            }
+            widget! {
+                (#{"app"} content_box [
+                    (#{"item"} content_box: {props} [
+                        (#{"receiver"} message_receiver)
+                    ])
+                ])
+            }
+        };
+        let receiver = WidgetId::new(
+            "message_receiver",
+            &["app".to_owned(), "item".to_owned(), "receiver".to_owned()],
+        );
+        let received = |application: &Application| {
+            application
+                .signals()
+                .iter()
+                .filter_map(|(_, msg)| msg.as_any().downcast_ref::<i32>().copied())
+                .collect::<Vec<_>>()
+        };
+        let mut application = Application::new();
+        application.apply(tree(false));
+        application.process();
+        application.apply(tree(true));
+        application.process();
+
+        application.send_message(&receiver, 1);
+        assert!(application.process());
+        assert!(received(&application).is_empty());
+        application.send_message(&receiver, 2);
+        assert!(application.process());
+        assert!(received(&application).is_empty());
+        // kept messages alone do not make application process.
+        assert!(!application.process());
+
+        application.apply(tree(false));
+        application.process();
+        assert_eq!(received(&application), vec![1, 2]);
+    }
+
+    fn use_unmount_log(context: &mut WidgetContext) {
+        context.life_cycle.unmount(|context| {
+            if let Some(log) = context.process_context.get_mut::<Vec<String>>() {
+                log.push(context.id.path().to_owned());
+            }
+        });
+    }
+
+    #[pre_hooks(use_unmount_log)]
+    fn logged_item(mut context: WidgetContext) -> WidgetNode {
+        content_box(context)
+    }
+
+    #[test]
+    fn test_unmount_order() {
+        let mut log = vec![];
+        for _ in 0..10 {
+            let mut application = Application::new();
+            application.apply(widget! {
+                (#{"app"} content_box [
+                    (#{"b"} logged_item [
+                        (#{"y"} logged_item)
+                        (#{"x"} logged_item [
+                            (#{"deep"} logged_item)
+                        ])
+                    ])
+                    (#{"a"} logged_item)
+                    (#{"c"} logged_item)
+                ])
+            });
+            application.process();
+            application.apply(widget! { (#{"app"} content_box) });
+            let mut unmounted = Vec::<String>::new();
+            {
+                let mut context = ProcessContext::new();
+                context.insert_mut(&mut unmounted);
+                application.process_with_context(&mut context);
+            }
+            log.push(unmounted);
+        }
+        assert_eq!(
+            log[0],
+            vec![
+                "app/b/x/deep",
+                "app/b/x",
+                "app/b/y",
+                "app/a",
+                "app/b",
+                "app/c"
+            ]
+        );
+        assert!(log.iter().all(|unmounted| unmounted == &log[0]));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut application = Application::new();
+        application.register_component("logged_item", logged_item);
+        application.animations_delta_time = 0.5;
+        application.apply(widget! {
+            (#{"app"} logged_item [
+                (#{"a"} logged_item)
+                (#{"b"} exiting_item)
+            ])
+        });
+        application.process();
+        let app = WidgetId::new("logged_item", &["app".to_owned()]);
+        let exiting = WidgetId::new("exiting_item", &["app".to_owned(), "b".to_owned()]);
+        assert!(application.state_read(&app).is_some());
+        assert!(application.state_read(&exiting).is_some());
+
+        let mut unmounted = Vec::<String>::new();
+        {
+            let mut context = ProcessContext::new();
+            context.insert_mut(&mut unmounted);
+            application.reset_with_context(&mut context);
+        }
+        assert_eq!(unmounted, vec!["app/a", "app"]);
+        assert!(application.states.is_empty());
+        assert!(application.unmount_closures.is_empty());
+        assert!(!application.is_exiting(&exiting));
+        assert!(matches!(application.rendered_tree(), WidgetUnit::None));
+        assert!(application.component_mappings.contains_key("logged_item"));
+
+        application.apply(widget! { (#{"app"} logged_item) });
+        application.process();
+        assert!(application.state_read(&app).is_some());
+    }
+
+    #[test]
+    fn test_delayed_message() {
+        fn received(application: &mut Application) -> Vec<u32> {
+            application.process();
+            application
+                .consume_signals()
+                .into_iter()
+                .filter_map(|(_, msg)| msg.as_any().downcast_ref::<u32>().cloned())
+                .collect()
+        }
+
+        let id = WidgetId::new("echo_item", &["echo".to_owned()]);
+        let mut application = Application::new();
+        application.apply(widget! { (#{"echo"} echo_item) });
+        application.send_message_delayed(&id, 2u32, 2);
+        // message sent by widget in first process, delayed by one process.
+        assert!(received(&mut application).is_empty());
+        assert!(received(&mut application).is_empty());
+        assert_eq!(received(&mut application), vec![2, 1]);
+        assert!(received(&mut application).is_empty());
+
+        application.send_message_delayed(&id, 3u32, 0);
+        assert_eq!(received(&mut application), vec![3]);
+
+        // unmounted target drops pending messages.
+        application.send_message_delayed(&id, 4u32, 1);
+        application.apply(widget! {()});
+        application.process();
+        application.apply(widget! { (#{"echo"} echo_item) });
+        assert!(received(&mut application).is_empty());
+        assert!(received(&mut application).is_empty());
+        assert_eq!(received(&mut application), vec![1]);
+    }
+
+    #[test]
+    fn test_effect() {
+        fn signals(application: &mut Application) -> Vec<String> {
+            application
+                .consume_signals()
+                .into_iter()
+                .filter_map(|(_, msg)| msg.as_any().downcast_ref::<String>().cloned())
+                .collect()
+        }
+
+        let mut application = Application::new();
+        application.apply(widget! { (#{"app"} effect_item: {1u32}) });
+        application.process();
+        assert_eq!(signals(&mut application), vec!["run 1"]);
+
+        application.apply(widget! { (#{"app"} effect_item: {1u32}) });
+        application.process();
+        assert!(signals(&mut application).is_empty());
+
+        application.apply(widget! { (#{"app"} effect_item: {2u32}) });
+        application.process();
+        assert_eq!(signals(&mut application), vec!["cleanup 1", "run 2"]);
+
+        application.apply(widget! { (#{"app"} effect_item: {2u32}) });
+        application.process();
+        assert!(signals(&mut application).is_empty());
+
+        application.apply(widget! {()});
+        application.process();
+        assert_eq!(signals(&mut application), vec!["cleanup 2"]);
+    }
+
+    #[test]
+    fn test_interval() {
+        fn ticks(min_process_interval: Scalar) -> usize {
+            let mut application = Application::new();
+            application.apply(widget! { (#{"app"} ticking) });
+            application.process();
+            application.set_min_process_interval(min_process_interval);
+            application.animations_delta_time = 0.25;
+            let mut result = 0;
+            for _ in 0..12 {
+                application.process();
+                result += application.consume_signals().len();
+            }
+            result
+        }
+
+        assert_eq!(ticks(0.0), 3);
+        assert_eq!(ticks(0.5), 3);
+        assert_eq!(ticks(0.75), 3);
+    }
+
+    #[test]
+    fn test_screen_local_coordinates() {
+        let mut application = Application::new();
+        let props = ContentBoxItemLayout {
+            anchors: Rect {
+                left: 0.0,
+                right: 0.5,
+                top: 0.0,
+                bottom: 0.5,
+            },
+            transform: Transform {
+                pivot: Vec2 { x: 0.5, y: 0.5 },
+                translation: Vec2 { x: 50.0, y: 0.0 },
+                rotation: std::f32::consts::FRAC_PI_2 as Scalar,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        application.apply(widget! {
+            (#{"app"} content_box [
+                (#{"image"} image_box: {props})
+            ])
+        });
+        application.process();
+        let mapping =
+            CoordsMapping::new_scaled(Rect::from((200.0, 200.0)), Rect::from((100.0, 100.0)), 1.0);
+        application
+            .layout(&mapping, &mut DefaultLayoutEngine)
+            .unwrap();
+        let id = WidgetId::new("image_box", &["app".to_owned(), "image".to_owned()]);
+        let close = |a: Vec2, b: Vec2| (a.x - b.x).abs() < 1.0e-3 && (a.y - b.y).abs() < 1.0e-3;
+
+        // top-left corner gets rotated around widget center into top-right one, then moved.
+        let screen = application
+            .local_to_screen(&id, Vec2 { x: 0.0, y: 0.0 }, &mapping)
+            .unwrap();
+        assert!(close(screen, Vec2 { x: 200.0, y: 0.0 }));
+        let local = Vec2 { x: 10.0, y: 30.0 };
+        let screen = application.local_to_screen(&id, local, &mapping).unwrap();
+        let result = application.screen_to_local(&id, screen, &mapping).unwrap();
+        assert!(close(result, local));
+
+        let missing = WidgetId::new("image_box", &["app".to_owned(), "missing".to_owned()]);
+        assert!(application
+            .screen_to_local(&missing, screen, &mapping)
+            .is_none());
+    }
+
+    #[test]
+    fn test_child_key_strategy() {
+        let mut application = Application::new();
+        application.set_child_key_strategy(|node: &WidgetNode, index| {
+            node.props()
+                .and_then(|props| props.read::<TextBoxProps>().ok())
+                .map(|props| props.text.to_owned())
+                .unwrap_or_else(|| format!("<{}>", index))
+        });
+        let text = |text: &str| TextBoxProps {
+            text: text.to_owned(),
+            ..Default::default()
+        };
+        application.apply(widget! {
+            (#{"app"} content_box [
+                (text_box: {text("a")})
+                (image_box)
+                (text_box: {text("b")})
+            ])
+        });
+        application.process();
+        let ids = match application.rendered_tree() {
+            WidgetUnit::ContentBox(unit) => unit
+                .items
+                .iter()
+                .filter_map(|item| item.slot.as_data())
+                .map(|data| data.id().as_ref().to_owned())
+                .collect::<Vec<_>>(),
+            _ => vec![],
+        };
+        assert_eq!(
+            ids,
+            vec!["text_box:/app/a", "image_box:/app/<1>", "text_box:/app/b"]
+        );
+    }
+
+    #[test]
+    fn test_register_batch() {
+        let mut application = Application::new();
+        application.register_components([
+            ("content_box", content_box as FnWidget),
+            ("image_box", image_box),
+            ("text_box", text_box),
+        ]);
+        application.register_props_batch([
+            (
+                "TextBoxProps",
+                PropsRegistry::registration::<TextBoxProps>(),
+            ),
+            (
+                "Transform",
+                PropsRegistry::registration_with_default::<Transform>(),
+            ),
+        ]);
+        let prefab = application
+            .serialize_node(&widget! {
+                (content_box [
+                    (image_box: {Transform::default()})
+                    (text_box: {TextBoxProps::default()})
+                ])
+            })
+            .unwrap();
+        let node = application.deserialize_node(prefab).unwrap();
+        let component = node.as_component().unwrap();
+        assert_eq!(component.type_name, "content_box");
+        assert_eq!(component.listed_slots.len(), 2);
+        assert!(application.default_props_prefab("Transform").is_some());
+        assert!(application.default_props_prefab("TextBoxProps").is_none());
+    }
+
+    #[test]
+    fn test_registration_queries() {
+        let mut application = Application::new();
+        assert!(!application.is_component_registered("image_box"));
+        assert!(!application.is_props_registered("TextBoxProps"));
+        assert_eq!(application.registered_component_names().count(), 0);
+
+        application.register_component("image_box", image_box);
+        application.register_props::<TextBoxProps>("TextBoxProps");
+        assert!(application.is_component_registered("image_box"));
+        assert!(!application.is_component_registered("text_box"));
+        assert!(application.is_props_registered("TextBoxProps"));
+        assert_eq!(
+            application.registered_component_names().collect::<Vec<_>>(),
+            vec!["image_box"]
+        );
+
+        application.unregister_component("image_box");
+        application.unregister_props("TextBoxProps");
+        assert!(!application.is_component_registered("image_box"));
+        assert!(!application.is_props_registered("TextBoxProps"));
+    }
+
+    #[test]
+    fn test_hot_swap_component() {
+        mod nested {
+            use crate::widget::{
+                component::image_box::image_box, context::WidgetContext, node::WidgetNode,
+            };
+
+            pub fn swappable(context: WidgetContext) -> WidgetNode {
+                image_box(context)
+            }
+        }
+
+        fn swappable(context: WidgetContext) -> WidgetNode {
+            image_box(context)
+        }
+
+        fn swapped(context: WidgetContext) -> WidgetNode {
+            text_box(context)
+        }
+
+        fn is_text(application: &Application) -> bool {
+            match application.rendered_tree() {
+                WidgetUnit::ContentBox(unit) => {
+                    matches!(unit.items[0].slot, WidgetUnit::TextBox(_))
+                }
+                _ => false,
+            }
+        }
+
+        let mut application = Application::new();
+        application.register_component("swappable", swappable);
+        application.apply(widget! {
+            (#{"app"} content_box [
+                (#{"item"} swappable)
+            ])
+        });
+        application.process();
+        assert!(!is_text(&application));
+
+        application.hot_swap_component("swappable", swapped);
+        assert!(application.process());
+        assert!(is_text(&application));
+        let id = WidgetId::new("swappable", &["app".to_owned(), "item".to_owned()]);
+        assert!(application.state_read(&id).is_some());
+
+        application.register_component("swappable", swappable);
+        application.mark_dirty();
+        application.process();
+        assert!(!is_text(&application));
+
+        // path qualified type names match registered name by last segment.
+        application.hot_swap_component("swappable", swapped);
+        application.apply(widget! {
+            (#{"app"} content_box [
+                (#{"item"} nested::swappable)
+            ])
+        });
+        application.process();
+        assert!(is_text(&application));
+
+        // unregistered component runs its own processor again.
+        application.unregister_component("swappable");
+        application.mark_dirty();
+        application.process();
+        assert!(!is_text(&application));
+    }
+
+    #[test]
+    fn test_frame_time() {
+        fn timed(context: WidgetContext) -> WidgetNode {
+            let (time, delta_time) = (context.time, context.delta_time);
+            if let Some(result) = context.process_context.owned_mut::<Vec<(f64, Scalar)>>() {
+                result.push((time, delta_time));
+            }
+            image_box(context)
+        }
+
+        let times = |application: &mut Application| {
+            let mut context = ProcessContext::new();
+            context.insert_owned(Vec::<(f64, Scalar)>::new());
+            application.forced_process_with_context(&mut context);
+            context.owned_mut::<Vec<(f64, Scalar)>>().cloned().unwrap()
+        };
+
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"app"} content_box [
+                (#{"a"} timed)
+                (#{"b"} timed)
+            ])
+        });
+        application.animations_delta_time = 0.5;
+        assert_eq!(times(&mut application), vec![(0.5, 0.5), (0.5, 0.5)]);
+        application.set_time_scale(0.5);
+        assert_eq!(times(&mut application), vec![(0.75, 0.25), (0.75, 0.25)]);
+        assert_eq!(application.time(), 0.75);
+    }
+
+    #[test]
+    fn test_process_until_stable() {
+        fn ready(context: WidgetContext) -> WidgetNode {
+            context.life_cycle.mount(|context| {
+                let _ = context.state.write(true);
+            });
+            if context.state.read_cloned_or_default::<bool>() {
+                widget! { (#{"content"} image_box) }
+            } else {
+                widget! {()}
+            }
+        }
+
+        fn restless(context: WidgetContext) -> WidgetNode {
+            let value = context.state.read_cloned_or_default::<u32>();
+            let _ = context.state.write(value + 1);
+            image_box(context)
+        }
+
+        let mut application = Application::new();
+        application.apply(widget! { (#{"app"} ready) });
+        assert_eq!(application.process_until_stable(10), 2);
+        assert_eq!(
+            application
+                .rendered_tree()
+                .as_data()
+                .map(|data| data.id().key()),
+            Some("content")
+        );
+        assert_eq!(application.process_until_stable(10), 0);
+
+        application.apply(widget! { (#{"app"} restless) });
+        assert_eq!(application.process_until_stable(5), 5);
+        assert_eq!(application.process_until_stable(0), 0);
+    }
+
+    #[test]
+    fn test_layout_signal() {
+        fn popup_owner(context: WidgetContext) -> WidgetNode {
+            context.life_cycle.mount(|context| {
+                context.signals.write_with_rect("open".to_owned());
+            });
+            image_box(context)
+        }
+
+        let props = ContentBoxItemLayout {
+            anchors: Rect {
+                left: 0.5,
+                right: 1.0,
+                top: 0.25,
+                bottom: 0.5,
+            },
+            ..Default::default()
+        };
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"app"} content_box [
+                (#{"owner"} popup_owner: {props})
+            ])
+        });
+        application.process();
+        assert!(application.signals().is_empty());
+
+        let mapping = CoordsMapping::new(Rect::from((100.0, 100.0)));
+        application
+            .layout(&mapping, &mut DefaultLayoutEngine)
+            .unwrap();
+        let signals = application.consume_signals();
+        assert_eq!(signals.len(), 1);
+        let (id, message) = &signals[0];
+        let signal = message.as_any().downcast_ref::<LayoutSignal>().unwrap();
+        assert_eq!(signal.payload::<String>().unwrap(), "open");
+        assert_eq!(
+            signal.rect,
+            Some(application.layout_data().items.get(id).unwrap().ui_space)
+        );
+        assert_eq!(
+            signal.rect,
+            Some(Rect {
+                left: 50.0,
+                right: 100.0,
+                top: 25.0,
+                bottom: 50.0,
+            })
+        );
+
+        application
+            .layout(&mapping, &mut DefaultLayoutEngine)
+            .unwrap();
+        assert!(application.signals().is_empty());
+    }
+
+    #[test]
+    fn test_processing_order() {
+        fn record(context: &mut WidgetContext) {
+            let key = context.key.to_owned();
+            if let Some(order) = context.process_context.owned_mut::<Vec<String>>() {
+                order.push(key);
+            }
+        }
+
+        fn recorded(mut context: WidgetContext) -> WidgetNode {
+            record(&mut context);
+            let listed_slots = context.take_listed_slots();
+            widget! { (#{"list"} content_box |[listed_slots]|) }
+        }
+
+        fn slotted(mut context: WidgetContext) -> WidgetNode {
+            record(&mut context);
+            let first = context.take_named_slot("first");
+            let second = context.take_named_slot("second");
+            widget! { (#{"slots"} content_box [{first} {second}]) }
+        }
+
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"a"} recorded [
+                (#{"b"} recorded [
+                    (#{"c"} recorded)
+                    (#{"d"} recorded)
+                ])
+                (#{"e"} slotted {
+                    second = (#{"f"} recorded)
+                    first = (#{"g"} recorded [
+                        (#{"h"} recorded)
+                    ])
+                })
+                (#{"i"} recorded)
+            ])
+        });
+        for _ in 0..3 {
+            let mut context = ProcessContext::new();
+            context.insert_owned(Vec::<String>::new());
+            application.forced_process_with_context(&mut context);
+            assert_eq!(
+                context.owned_mut::<Vec<String>>().unwrap(),
+                &["a", "b", "c", "d", "e", "g", "h", "f", "i"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_prop_change() {
+        fn watching(mut context: WidgetContext) -> WidgetNode {
+            let mut changes = vec![];
+            use_prop_change::<u32, _>(&mut context, |old, new| changes.push((*old, *new)));
+            if let Some(result) = context.process_context.owned_mut::<Vec<(u32, u32)>>() {
+                result.extend(changes);
+            }
+            image_box(context)
+        }
+
+        let changes = |application: &mut Application| {
+            let mut context = ProcessContext::new();
+            context.insert_owned(Vec::<(u32, u32)>::new());
+            application.forced_process_with_context(&mut context);
+            context.owned_mut::<Vec<(u32, u32)>>().cloned().unwrap()
+        };
+
+        let mut application = Application::new();
+        application.apply(widget! { (#{"app"} watching: {1u32}) });
+        assert!(changes(&mut application).is_empty());
+        assert!(changes(&mut application).is_empty());
+
+        application.apply(widget! { (#{"app"} watching: {2u32}) });
+        assert_eq!(changes(&mut application), vec![(1, 2)]);
+        assert!(changes(&mut application).is_empty());
+        assert!(changes(&mut application).is_empty());
+    }
+
+    #[test]
+    fn test_render_snapshot() {
+        fn snapshot() -> String {
+            let mut application = Application::new();
+            application.apply(widget! {
+                (#{"root"} content_box [
+                    (#{"image"} image_box)
+                    (#{"text"} text_box: {TextBoxProps {
+                        text: "hello".to_owned(),
+                        ..Default::default()
+                    }})
+                ])
+            });
+            application.process();
+            let mapping = CoordsMapping::new(Rect::from((100.0, 50.0)));
+            application
+                .layout(&mapping, &mut DefaultLayoutEngine)
+                .unwrap();
+            application.render_snapshot(&mapping)
+        }
+
+        let result = snapshot();
+        assert_eq!(result, snapshot());
+        assert_eq!(
+            result,
+            "ContentBox content_box:/root [0.00 0.00 100.00 50.00] clipping=false\n\
+            \x20 ImageBox image_box:/root/image [0.00 0.00 100.00 50.00] width=Fill height=Fill material=color\n\
+            \x20 TextBox text_box:/root/text [0.00 0.00 100.00 50.00] text=\"hello\" font=\"\" size=0\n"
+        );
+    }
+
+    #[test]
+    fn test_serialize_node_round_trip() {
+        fn keys(node: &WidgetNode) -> (Option<String>, Vec<Option<String>>, Vec<String>) {
+            let component = node.as_component().unwrap();
+            let named = component.named_slots.keys().cloned().collect();
+            let listed = component
+                .listed_slots
+                .iter()
+                .map(|node| node.as_component().unwrap().key.clone())
+                .collect();
+            (component.key.clone(), listed, named)
+        }
+
+        let mut application = Application::new();
+        application.setup(crate::widget::setup);
+        let make_node = || -> WidgetNode {
+            WidgetComponent::new(content_box, "content_box")
+                .key("root")
+                .idref(crate::widget::WidgetRef::default())
+                .listed_slots(
+                    ["c", "a", "d", "b"]
+                        .iter()
+                        .map(|key| WidgetComponent::new(image_box, "image_box").key(key)),
+                )
+                .named_slots(["zeta", "alpha", "mu", "beta"].iter().map(|name| {
+                    (
+                        *name,
+                        WidgetComponent::new(text_box, "text_box").with_props(TextBoxProps {
+                            text: name.to_string(),
+                            ..Default::default()
+                        }),
+                    )
+                }))
+                .into()
+        };
+        let node = make_node();
+
+        let prefab = application.serialize_node(&node).unwrap();
+        let restored = application.deserialize_node(prefab.clone()).unwrap();
+        assert!(restored.as_component().unwrap().idref.is_none());
+        assert_eq!(keys(&restored), keys(&node));
+        assert_eq!(application.serialize_node(&restored).unwrap(), prefab);
+        assert_eq!(application.serialize_node(&make_node()).unwrap(), prefab);
+    }
+
+    #[test]
+    fn test_serialize_node_deduplicated() {
+        let mut application = Application::new();
+        application.setup(crate::widget::setup);
+        let props = TextBoxProps {
+            text: "repeated".to_owned(),
+            ..Default::default()
+        };
+        let node: WidgetNode = WidgetComponent::new(content_box, "content_box")
+            .key("root")
+            .listed_slots((0..10).map(|index| {
+                WidgetComponent::new(text_box, "text_box")
+                    .key(index)
+                    .with_props(props.clone())
+            }))
+            .listed_slot(
+                WidgetComponent::new(text_box, "text_box").with_props(TextBoxProps {
+                    text: "unique".to_owned(),
+                    ..Default::default()
+                }),
+            )
+            .into();
+
+        let prefab = application.serialize_node(&node).unwrap();
+        let deduplicated = application.serialize_node_deduplicated(&node).unwrap();
+        assert_eq!(deduplicated["props_table"].as_sequence().unwrap().len(), 1);
+        let size = |value: &PrefabValue| serde_yaml::to_string(value).unwrap().len();
+        assert!(size(&deduplicated) < size(&prefab));
+        assert!(application.validate_prefab(&deduplicated).is_ok());
+
+        let restored = application.deserialize_node(deduplicated).unwrap();
+        assert_eq!(application.serialize_node(&restored).unwrap(), prefab);
+
+        let mut broken = application.serialize_node_deduplicated(&node).unwrap();
+        broken["props_table"] = PrefabValue::Sequence(vec![]);
+        assert!(application.deserialize_node(broken).is_err());
+    }
+
+    #[test]
+    fn test_serialize_unit_transform() {
+        fn unit_transform(node: &WidgetNode) -> Transform {
+            match node.as_unit().unwrap() {
+                WidgetUnitNode::ContentBox(unit) => unit.transform,
+                WidgetUnitNode::FlexBox(unit) => unit.transform,
+                WidgetUnitNode::GridBox(unit) => unit.transform,
+                WidgetUnitNode::SizeBox(unit) => unit.transform,
+                WidgetUnitNode::ImageBox(unit) => unit.transform,
+                WidgetUnitNode::TextBox(unit) => unit.transform,
+                unit => panic!("Unit has no transform: {:?}", unit),
+            }
+        }
+
+        let application = Application::new();
+        let transform = Transform {
+            pivot: Vec2 { x: 0.5, y: 0.25 },
+            align: Vec2 { x: 0.1, y: 0.2 },
+            translation: Vec2 { x: 10.0, y: -20.0 },
+            rotation: 1.5,
+            scale: Vec2 { x: 2.0, y: 0.5 },
+            skew: Vec2 { x: 0.3, y: -0.3 },
+        };
+        let units: Vec<WidgetUnitNode> = vec![
+            ContentBoxNode {
+                transform,
+                ..Default::default()
+            }
+            .into(),
+            FlexBoxNode {
+                transform,
+                ..Default::default()
+            }
+            .into(),
+            GridBoxNode {
+                transform,
+                ..Default::default()
+            }
+            .into(),
+            SizeBoxNode {
+                transform,
+                ..Default::default()
+            }
+            .into(),
+            ImageBoxNode {
+                transform,
+                ..Default::default()
+            }
+            .into(),
+            TextBoxNode {
+                transform,
+                ..Default::default()
+            }
+            .into(),
+        ];
+        for unit in units {
+            let node = WidgetNode::Unit(unit);
+            let prefab = application.serialize_node(&node).unwrap();
+            let restored = application.deserialize_node(prefab).unwrap();
+            assert_eq!(unit_transform(&restored), unit_transform(&node));
+        }
+
+        // missing transform fields get their defaults, scale included.
+        let prefab = serde_yaml::from_str::<PrefabValue>(
+            r#"
+Unit:
+  ImageBox:
+    transform:
+      rotation: 1.5
+"#,
+        )
+        .unwrap();
+        let restored = application.deserialize_node(prefab).unwrap();
+        assert_eq!(
+            unit_transform(&restored),
+            Transform {
+                rotation: 1.5,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_serialize_node_deterministic() {
+        let mut application = Application::new();
+        application.setup(crate::widget::setup);
+        let serialize = |names: &[&str]| {
+            let mut node = WidgetComponent::new(content_box, "content_box");
+            for name in names {
+                node = node.named_slot(name, WidgetComponent::new(image_box, "image_box"));
+            }
+            let prefab = application.serialize_node(&node.into()).unwrap();
+            serde_yaml::to_string(&prefab).unwrap()
+        };
+
+        let names = ["header", "body", "footer", "sidebar", "overlay"];
+        let mut reversed = names;
+        reversed.reverse();
+        let serialized = serialize(&names);
+        assert_eq!(serialized, serialize(&names));
+        assert_eq!(serialized, serialize(&reversed));
+    }
+
+    #[test]
+    fn test_validate_prefab() {
+        let mut application = Application::new();
+        application.setup(crate::widget::setup);
+        let prefab = application
+            .serialize_node(&widget! {
+                (content_box [
+                    (image_box)
+                    (text_box: {TextBoxProps::default()})
+                ])
+            })
+            .unwrap();
+        assert!(application.validate_prefab(&prefab).is_ok());
+
+        application.unregister_component("image_box");
+        application.unregister_component("text_box");
+        application.unregister_props("TextBoxProps");
+        let errors = application.validate_prefab(&prefab).unwrap_err();
+        let errors = errors
+            .iter()
+            .map(|error| match error {
+                PrefabValidationError::ComponentMappingNotFound { path, type_name } => {
+                    format!("{}: {}", path, type_name)
+                }
+                PrefabValidationError::PropsNotRegistered { path, name } => {
+                    format!("{}: {}", path, name)
+                }
+                PrefabValidationError::Prefab { path, error } => format!("{}: {:?}", path, error),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                "/listed_slots/0: image_box".to_owned(),
+                "/listed_slots/1: text_box".to_owned(),
+                "/listed_slots/1: TextBoxProps".to_owned(),
+            ]
+        );
+        assert!(application.deserialize_node(prefab).is_err());
+    }
+
+    #[test]
+    fn test_apply_overrides() {
+        let mut application = Application::new();
+        application.setup(crate::widget::setup);
+        let text = |text: &str| TextBoxProps {
+            text: text.to_owned(),
+            ..Default::default()
+        };
+        let base = application
+            .serialize_node(&widget! {
+                (#{"dialog"} content_box [
+                    (#{"title"} text_box: {text("Title")})
+                    (#{"body"} text_box: {text("Body")})
+                ])
+            })
+            .unwrap();
+        let overrides = serde_yaml::from_str::<PrefabValue>(
+            r#"
+            Component:
+              listed_slots:
+                - Component:
+                    key: body
+                    props:
+                      data:
+                        TextBoxProps:
+                          color:
+                            r: 1.0
+                            g: 0.0
+                            b: 0.0
+                            a: 1.0
+            "#,
+        )
+        .unwrap();
+
+        let mut merged = Application::apply_overrides(base.clone(), overrides);
+        let node = application.deserialize_node(merged.clone()).unwrap();
+        let component = node.as_component().unwrap().listed_slots[1]
+            .as_component()
+            .unwrap();
+        let props = component.props.read_cloned::<TextBoxProps>().unwrap();
+        assert_eq!(component.key.as_deref(), Some("body"));
+        assert_eq!(props.text, "Body");
+        assert_eq!(
+            (props.color.r, props.color.g, props.color.b),
+            (1.0, 0.0, 0.0)
+        );
+
+        // Everything except overridden color stays the same.
+        fn color(prefab: &mut PrefabValue) -> &mut PrefabValue {
+            &mut prefab["Component"]["listed_slots"][1]["Component"]["props"]["data"]
+                ["TextBoxProps"]["color"]
+        }
+        *color(&mut merged) = color(&mut base.clone()).clone();
+        assert_eq!(merged, base);
+    }
+}
@@ -1,33 +1,73 @@
 use crate::{
     application::Application,
     interactive::InteractionsEngine,
+    layout::CoordsMapping,
     messenger::MessageData,
     widget::{
         component::{
-            interactive::navigation::{NavDirection, NavJump, NavScroll, NavSignal, NavType},
+            interactive::{
+                navigation::{
+                    NavBackUnhandled, NavDirection, NavEvent, NavEventPhase, NavGesture, NavJump,
+                    NavScroll, NavSignal, NavTextChange, NavType,
+                },
+                scroll_view::{ScrollViewMetricsMessage, ScrollViewState},
+            },
             RelativeLayoutListenerSignal, ResizeListenerSignal,
         },
-        unit::WidgetUnit,
-        utils::{lerp, Rect, Vec2},
+        unit::{content::ContentBoxItem, WidgetUnit},
+        utils::{lerp, Rect, Transform, Vec2},
         WidgetId,
     },
-    Scalar,
+    Prefab, Scalar,
 };
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PointerButton {
     Trigger,
     Context,
 }
 
-#[derive(Debug, Clone)]
+pub type PointerId = u32;
+
+/// Identifier of the pointer used by single pointer interactions.
+pub const PRIMARY_POINTER: PointerId = 0;
+
+/// Time in seconds that key repeat times are rounded with, so accumulated frame times that miss
+/// repeat time by floating point error still trigger it.
+const KEY_REPEAT_TOLERANCE: Scalar = 1.0e-4;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Interaction {
     None,
     Navigate(NavSignal),
+    /// Primary pointer down.
     PointerDown(PointerButton, Vec2),
+    /// Primary pointer up.
     PointerUp(PointerButton, Vec2),
+    /// Primary pointer move.
     PointerMove(Vec2),
+    /// Primary pointer left the window.
+    PointerLeave,
+    /// Primary pointer scroll (for example mouse wheel) at position, by delta in layout units.
+    ///
+    /// Scrolls innermost scroll view under pointer that can still move along dominant axis of the
+    /// delta, so nested scroll view that reached its scroll limit passes scrolling to ancestors.
+    PointerScroll(Vec2, Vec2),
+    MultiPointerDown(PointerId, PointerButton, Vec2),
+    MultiPointerUp(PointerId, PointerButton, Vec2),
+    MultiPointerMove(PointerId, Vec2),
+    MultiPointerLeave(PointerId),
+    /// Key pressed: text change is sent to focused text input right away, then repeated while
+    /// the key is held (see [`KeyRepeatSettings`]).
+    ///
+    /// Repeated key down events of already held key (sent by system key repeat) are ignored.
+    /// Held keys are released when focused text input changes, so a lost key up does not make
+    /// the key repeat forever.
+    KeyDown(NavTextChange),
+    /// Key released: stops repeating its text change.
+    KeyUp(NavTextChange),
 }
 
 impl Default for Interaction {
@@ -36,6 +76,30 @@ impl Default for Interaction {
     }
 }
 
+impl Prefab for Interaction {}
+
+/// Interactions performed by [`DefaultInteractionsEngine`] in single interactions pass, along
+/// with delta time of that pass.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct InteractionFrame {
+    #[serde(default)]
+    pub delta_time: Scalar,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub interactions: Vec<Interaction>,
+}
+
+/// Interactions recorded by [`DefaultInteractionsEngine`] (see
+/// [`start_recording`][DefaultInteractionsEngine::start_recording]) frame by frame, including
+/// frames without interactions, so time dependent ones (long press, key repeat) keep their timing.
+///
+/// Converts to and from [`PrefabValue`][crate::PrefabValue], so recorded sessions can be stored
+/// and [replayed][DefaultInteractionsEngine::replay] later, for example in tests.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct InteractionLog(#[serde(default)] pub Vec<InteractionFrame>);
+
+impl Prefab for InteractionLog {}
+
 impl Interaction {
     pub fn is_none(&self) -> bool {
         matches!(self, Self::None)
@@ -45,6 +109,53 @@ impl Interaction {
     pub fn is_some(&self) -> bool {
         !self.is_none()
     }
+
+    /// Converts pointer positions given in physical (framebuffer) pixels into virtual units.
+    pub fn physical_to_virtual(self, mapping: &CoordsMapping) -> Self {
+        match self {
+            Self::PointerDown(button, position) => {
+                Self::PointerDown(button, mapping.physical_to_virtual_vec2(position, false))
+            }
+            Self::PointerUp(button, position) => {
+                Self::PointerUp(button, mapping.physical_to_virtual_vec2(position, false))
+            }
+            Self::PointerMove(position) => {
+                Self::PointerMove(mapping.physical_to_virtual_vec2(position, false))
+            }
+            Self::PointerScroll(position, delta) => Self::PointerScroll(
+                mapping.physical_to_virtual_vec2(position, false),
+                mapping.physical_to_virtual_vec2(delta, true),
+            ),
+            Self::MultiPointerDown(pointer, button, position) => Self::MultiPointerDown(
+                pointer,
+                button,
+                mapping.physical_to_virtual_vec2(position, false),
+            ),
+            Self::MultiPointerUp(pointer, button, position) => Self::MultiPointerUp(
+                pointer,
+                button,
+                mapping.physical_to_virtual_vec2(position, false),
+            ),
+            Self::MultiPointerMove(pointer, position) => {
+                Self::MultiPointerMove(pointer, mapping.physical_to_virtual_vec2(position, false))
+            }
+            _ => self,
+        }
+    }
+
+    fn into_multi_pointer(self) -> Self {
+        match self {
+            Self::PointerDown(button, position) => {
+                Self::MultiPointerDown(PRIMARY_POINTER, button, position)
+            }
+            Self::PointerUp(button, position) => {
+                Self::MultiPointerUp(PRIMARY_POINTER, button, position)
+            }
+            Self::PointerMove(position) => Self::MultiPointerMove(PRIMARY_POINTER, position),
+            Self::PointerLeave => Self::MultiPointerLeave(PRIMARY_POINTER),
+            _ => self,
+        }
+    }
 }
 
 #[derive(Debug, Default, Copy, Clone)]
@@ -66,10 +177,105 @@ impl DefaultInteractionsEngineResult {
     }
 }
 
-/// Single pointer + Keyboard + Gamepad
+#[derive(Debug, Copy, Clone)]
+pub struct GestureSettings {
+    /// Time in seconds the pointer has to be held down to fire long-press.
+    pub long_press_time: Scalar,
+    /// Maximum distance the pointer can move while being held down to still fire long-press.
+    pub long_press_tolerance: Scalar,
+    /// Minimum distance the pointer has to travel to fire swipe.
+    pub swipe_distance: Scalar,
+    /// Maximum time in seconds between pointer down and up to fire swipe.
+    pub swipe_time: Scalar,
+}
+
+impl Default for GestureSettings {
+    fn default() -> Self {
+        Self {
+            long_press_time: 0.5,
+            long_press_tolerance: 10.0,
+            swipe_distance: 50.0,
+            swipe_time: 0.3,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct KeyRepeatSettings {
+    /// Time in seconds the key has to be held before it starts repeating.
+    pub initial_delay: Scalar,
+    /// Repeats per second of held key, zero disables repeating.
+    pub rate: Scalar,
+    /// Most repeats sent in a single frame, so a long frame does not burst many text changes.
+    /// Repeats over the limit are dropped.
+    pub max_per_frame: usize,
+}
+
+impl Default for KeyRepeatSettings {
+    fn default() -> Self {
+        Self {
+            initial_delay: 0.5,
+            rate: 20.0,
+            max_per_frame: 4,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct PointerState {
+    position: Vec2,
+    targets: HashMap<PointerButton, WidgetId>,
+    hovered: Option<WidgetId>,
+}
+
+#[derive(Debug, Clone)]
+struct GestureState {
+    id: WidgetId,
+    button: PointerButton,
+    start_position: Vec2,
+    position: Vec2,
+    start_time: Scalar,
+    long_press_allowed: bool,
+}
+
+impl GestureState {
+    fn delta(&self) -> Vec2 {
+        Vec2 {
+            x: self.position.x - self.start_position.x,
+            y: self.position.y - self.start_position.y,
+        }
+    }
+
+    fn distance(&self) -> Scalar {
+        let delta = self.delta();
+        (delta.x * delta.x + delta.y * delta.y).sqrt()
+    }
+
+    fn direction(&self) -> NavDirection {
+        let delta = self.delta();
+        if delta.x.abs() >= delta.y.abs() {
+            if delta.x >= 0.0 {
+                NavDirection::Right
+            } else {
+                NavDirection::Left
+            }
+        } else if delta.y >= 0.0 {
+            NavDirection::Down
+        } else {
+            NavDirection::Up
+        }
+    }
+}
+
+/// Pointers + Keyboard + Gamepad
 #[derive(Debug, Default)]
 pub struct DefaultInteractionsEngine {
     pub deselect_when_no_button_found: bool,
+    pub gestures: GestureSettings,
+    pub key_repeat: KeyRepeatSettings,
+    /// Propagates pointer button actions of buttons to their ancestors as [`NavEvent`]s, in the
+    /// same interactions pass as buttons get them (see [`NavEventPhase`]).
+    pub event_propagation: bool,
     resize_listeners: HashMap<WidgetId, Vec2>,
     relative_layout_listeners: HashMap<WidgetId, (WidgetId, Vec2, Rect)>,
     interactions_queue: VecDeque<Interaction>,
@@ -77,12 +283,30 @@ pub struct DefaultInteractionsEngine {
     items_owners: HashMap<WidgetId, WidgetId>,
     buttons: HashMap<WidgetId, bool>,
     text_inputs: HashSet<WidgetId>,
-    scroll_views: HashSet<WidgetId>,
+    disabled: HashSet<WidgetId>,
+    /// With content and viewport sizes last sent to scroll view.
+    scroll_views: HashMap<WidgetId, (Vec2, Vec2)>,
     scroll_view_contents: HashSet<WidgetId>,
+    /// In order of registration.
+    back_handlers: Vec<WidgetId>,
+    /// In order of registration, with item that was selected when trap got registered.
+    focus_traps: Vec<(WidgetId, Option<WidgetId>)>,
     selected_chain: Vec<WidgetId>,
     locked_widget: Option<WidgetId>,
+    pointers: HashMap<PointerId, PointerState>,
+    pointer_captures: HashMap<(PointerId, PointerButton), WidgetId>,
     focused_text_input: Option<WidgetId>,
     sorted_items_ids: Vec<WidgetId>,
+    gesture_states: HashMap<PointerId, GestureState>,
+    event_stops: HashSet<(WidgetId, NavEventPhase)>,
+    /// Held keys with time they got pressed at and number of their repeats so far.
+    held_keys: Vec<(NavTextChange, Scalar, usize)>,
+    time: Scalar,
+    recording: Option<InteractionLog>,
+    replaying: VecDeque<InteractionFrame>,
+    /// Last selection changing input came from pointer rather than keyboard or gamepad.
+    pointer_modality: bool,
+    focus_visible: bool,
 }
 
 impl DefaultInteractionsEngine {
@@ -103,6 +327,9 @@ impl DefaultInteractionsEngine {
     ) -> Self {
         Self {
             deselect_when_no_button_found: false,
+            gestures: Default::default(),
+            key_repeat: Default::default(),
+            event_propagation: false,
             resize_listeners: HashMap::with_capacity(resize_listeners),
             relative_layout_listeners: HashMap::with_capacity(relative_layout_listeners),
             interactions_queue: VecDeque::with_capacity(interactions_queue),
@@ -110,12 +337,25 @@ impl DefaultInteractionsEngine {
             items_owners: Default::default(),
             buttons: HashMap::with_capacity(buttons),
             text_inputs: HashSet::with_capacity(text_inputs),
-            scroll_views: HashSet::with_capacity(scroll_views),
+            disabled: Default::default(),
+            scroll_views: HashMap::with_capacity(scroll_views),
             scroll_view_contents: HashSet::with_capacity(scroll_views),
+            back_handlers: Default::default(),
+            focus_traps: Default::default(),
             selected_chain: Vec::with_capacity(selected_chain),
             locked_widget: None,
+            pointers: Default::default(),
+            pointer_captures: Default::default(),
             focused_text_input: None,
             sorted_items_ids: vec![],
+            gesture_states: Default::default(),
+            event_stops: Default::default(),
+            held_keys: vec![],
+            time: 0.0,
+            recording: None,
+            replaying: Default::default(),
+            pointer_modality: false,
+            focus_visible: false,
         }
     }
 
@@ -123,6 +363,30 @@ impl DefaultInteractionsEngine {
         self.locked_widget.as_ref()
     }
 
+    pub fn pointer_position(&self, pointer: PointerId) -> Option<Vec2> {
+        self.pointers.get(&pointer).map(|state| state.position)
+    }
+
+    pub fn pointer_target(&self, pointer: PointerId, button: PointerButton) -> Option<&WidgetId> {
+        self.pointers.get(&pointer)?.targets.get(&button)
+    }
+
+    pub fn hovered_widget(&self, pointer: PointerId) -> Option<&WidgetId> {
+        self.pointers.get(&pointer)?.hovered.as_ref()
+    }
+
+    pub fn pointer_capture(&self, pointer: PointerId, button: PointerButton) -> Option<&WidgetId> {
+        self.pointer_captures.get(&(pointer, button))
+    }
+
+    pub fn release_pointer_capture(
+        &mut self,
+        pointer: PointerId,
+        button: PointerButton,
+    ) -> Option<WidgetId> {
+        self.pointer_captures.remove(&(pointer, button))
+    }
+
     pub fn selected_chain(&self) -> &[WidgetId] {
         &self.selected_chain
     }
@@ -160,7 +424,7 @@ impl DefaultInteractionsEngine {
         self.selected_chain
             .iter()
             .rev()
-            .find(|id| self.scroll_views.contains(id))
+            .find(|id| self.scroll_views.contains_key(*id))
     }
 
     pub fn selected_scroll_view_content(&self) -> Option<&WidgetId> {
@@ -170,16 +434,83 @@ impl DefaultInteractionsEngine {
             .find(|id| self.scroll_view_contents.contains(id))
     }
 
+    /// Back handler that would receive [`NavSignal::Back`] now
+    pub fn selected_back_handler(&self) -> Option<&WidgetId> {
+        match self.selected_item() {
+            Some(selected) => self
+                .back_handlers
+                .iter()
+                .filter(|id| id.path() == selected.path() || id.is_ancestor_of(selected))
+                .max_by(|a, b| a.depth().cmp(&b.depth())),
+            None => self.back_handlers.last(),
+        }
+    }
+
+    /// Focus trap that currently keeps selection within its subtree
+    pub fn active_focus_trap(&self) -> Option<&WidgetId> {
+        self.focus_traps.last().map(|(id, _)| id)
+    }
+
+    /// Tells if widget can be selected while active focus trap is there
+    pub fn is_within_focus_trap(&self, id: &WidgetId) -> bool {
+        self.active_focus_trap()
+            .map(|trap| trap.path() == id.path() || trap.is_ancestor_of(id))
+            .unwrap_or(true)
+    }
+
+    /// Tells if focus of selected item should be visibly indicated (like CSS `:focus-visible`),
+    /// that is when it got selected with keyboard or gamepad (or by widgets and host) rather than
+    /// with pointer. Selected items get it as [`NavSignal::FocusVisible`].
+    pub fn focus_visible(&self) -> bool {
+        self.focus_visible && !self.selected_chain.is_empty()
+    }
+
     pub fn focused_text_input(&self) -> Option<&WidgetId> {
         self.focused_text_input.as_ref()
     }
 
+    pub fn is_disabled(&self, id: &WidgetId) -> bool {
+        self.disabled.contains(id)
+    }
+
     pub fn interact(&mut self, interaction: Interaction) {
         if interaction.is_some() {
             self.interactions_queue.push_back(interaction);
         }
     }
 
+    /// Starts recording interactions fed to the engine (in virtual space), dropping previous
+    /// recording if any.
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Default::default());
+    }
+
+    /// Stops recording and returns interactions recorded since
+    /// [`start_recording`][Self::start_recording], one frame per interactions pass.
+    pub fn stop_recording(&mut self) -> InteractionLog {
+        self.recording.take().unwrap_or_default()
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Replays `log` frame by frame: each next interactions pass performs interactions of next
+    /// recorded frame (after ones fed with [`interact`][Self::interact]) and uses its recorded
+    /// delta time instead of application one, until [`is_replaying`][Self::is_replaying] is
+    /// `false`. Replaces log being replayed if any.
+    pub fn replay(&mut self, log: InteractionLog) {
+        self.replaying = log.0.into();
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        !self.replaying.is_empty()
+    }
+
+    pub fn interact_physical(&mut self, interaction: Interaction, mapping: &CoordsMapping) {
+        self.interact(interaction.physical_to_virtual(mapping));
+    }
+
     pub fn clear_queue(&mut self, put_unselect: bool) {
         self.interactions_queue.clear();
         if put_unselect {
@@ -188,6 +519,62 @@ impl DefaultInteractionsEngine {
         }
     }
 
+    /// Sends event to ancestors of the target, from the root down in capture phase and then back
+    /// up in bubble phase, until it reaches widget that stops it.
+    fn propagate_event(&self, app: &mut Application, target: WidgetId, signal: NavSignal) {
+        let mut ancestors = vec![];
+        if !Self::find_ancestors(app.rendered_tree(), &target, &mut ancestors) {
+            return;
+        }
+        let mut send = |id: &WidgetId, phase| {
+            app.send_message(
+                id,
+                NavEvent {
+                    target: target.to_owned(),
+                    phase,
+                    signal: signal.to_owned(),
+                },
+            );
+            !self.event_stops.contains(&(id.to_owned(), phase))
+        };
+        if !ancestors.iter().all(|id| send(id, NavEventPhase::Capture)) {
+            return;
+        }
+        if self
+            .event_stops
+            .contains(&(target.to_owned(), NavEventPhase::Bubble))
+        {
+            return;
+        }
+        for id in ancestors.iter().rev() {
+            if !send(id, NavEventPhase::Bubble) {
+                return;
+            }
+        }
+    }
+
+    fn find_ancestors(unit: &WidgetUnit, target: &WidgetId, result: &mut Vec<WidgetId>) -> bool {
+        if let Some(data) = unit.as_data() {
+            let id = data.id();
+            if id == target {
+                return true;
+            }
+            let pushed = id.is_valid() && result.last() != Some(id);
+            if pushed {
+                result.push(id.to_owned());
+            }
+            for child in data.get_children() {
+                if Self::find_ancestors(child, target, result) {
+                    return true;
+                }
+            }
+            if pushed {
+                result.pop();
+            }
+        }
+        false
+    }
+
     fn cache_sorted_items_ids(&mut self, app: &Application) {
         self.sorted_items_ids = Vec::with_capacity(self.items_owners.len());
         self.cache_sorted_items_ids_inner(app.rendered_tree());
@@ -233,6 +620,14 @@ impl DefaultInteractionsEngine {
         if self.locked_widget.is_some() || self.selected_chain.last() == id.as_ref() {
             return false;
         }
+        if let Some(id) = &id {
+            if self.disabled.contains(id) {
+                return false;
+            }
+            if !self.is_within_focus_trap(id) {
+                return false;
+            }
+        }
         match (self.selected_chain.is_empty(), id) {
             (false, None) => {
                 for id in std::mem::take(&mut self.selected_chain).iter().rev() {
@@ -284,6 +679,10 @@ impl DefaultInteractionsEngine {
             }
             _ => {}
         }
+        self.focus_visible = !self.pointer_modality && !self.selected_chain.is_empty();
+        for id in &self.selected_chain {
+            app.send_message(id, NavSignal::FocusVisible(self.focus_visible));
+        }
         true
     }
 
@@ -291,12 +690,13 @@ impl DefaultInteractionsEngine {
         if self.focused_text_input == id {
             return;
         }
+        self.held_keys.clear();
         if let Some(focused) = &self.focused_text_input {
             app.send_message(focused, NavSignal::FocusTextInput(().into()));
         }
         self.focused_text_input = None;
         if let Some(id) = id {
-            if self.text_inputs.contains(&id) {
+            if self.text_inputs.contains(&id) && !self.disabled.contains(&id) {
                 app.send_message(&id, NavSignal::FocusTextInput(id.to_owned().into()));
                 self.focused_text_input = Some(id);
             }
@@ -347,8 +747,17 @@ impl DefaultInteractionsEngine {
         false
     }
 
+    fn send_back(&self, app: &mut Application) {
+        if let Some(id) = self.selected_back_handler() {
+            app.send_message(id, NavSignal::Back);
+        } else {
+            let id = self.selected_item().cloned().unwrap_or_default();
+            app.send_signal(&id, NavBackUnhandled);
+        }
+    }
+
     fn find_scroll_view_content(&self, id: &&WidgetId) -> Option<WidgetId> {
-        if self.scroll_views.contains(id) {
+        if self.scroll_views.contains_key(*id) {
             if let Some(items) = self.containers.get(id) {
                 for item in items {
                     if self.scroll_view_contents.contains(item) {
@@ -360,6 +769,55 @@ impl DefaultInteractionsEngine {
         None
     }
 
+    /// Finds innermost scroll view containing button under pointer, that is not at its scroll
+    /// limit along dominant axis of `delta`.
+    fn find_scroll_target(
+        &self,
+        app: &Application,
+        position: Vec2,
+        delta: Vec2,
+    ) -> Option<WidgetId> {
+        let (target, _) = self.find_button(app, position.x, position.y)?;
+        let mut ancestors = vec![];
+        if !Self::find_ancestors(app.rendered_tree(), &target, &mut ancestors) {
+            return None;
+        }
+        ancestors.push(target);
+        ancestors
+            .into_iter()
+            .rev()
+            .find(|id| self.can_scroll(app, id, delta))
+    }
+
+    fn can_scroll(&self, app: &Application, id: &WidgetId, delta: Vec2) -> bool {
+        if self.disabled.contains(id) {
+            return false;
+        }
+        let (content, viewport) = match self.scroll_views.get(id) {
+            Some(metrics) => *metrics,
+            None => return false,
+        };
+        let value = app
+            .state_read(id)
+            .and_then(|state| state.read::<ScrollViewState>().ok())
+            .map(|state| state.value)
+            .unwrap_or_default();
+        let (delta, value, distance) = if delta.x.abs() > delta.y.abs() {
+            (delta.x, value.x, content.x - viewport.x)
+        } else {
+            (delta.y, value.y, content.y - viewport.y)
+        };
+        if distance <= 1.0e-6 {
+            false
+        } else if delta > 0.0 {
+            value < 1.0 - 1.0e-6
+        } else if delta < 0.0 {
+            value > 1.0e-6
+        } else {
+            false
+        }
+    }
+
     fn get_item_point(app: &Application, id: &WidgetId) -> Option<Vec2> {
         if let Some(layout) = app.layout_data().items.get(id) {
             let x = (layout.ui_space.left + layout.ui_space.right) * 0.5;
@@ -418,6 +876,33 @@ impl DefaultInteractionsEngine {
             .map(|m| m.0.to_owned())
     }
 
+    fn select_first_trapped_item(&mut self, app: &mut Application) {
+        let trap = match self.active_focus_trap() {
+            Some(trap) => trap.to_owned(),
+            None => return,
+        };
+        if let Some(id) = self.selected_item() {
+            if self.is_within_focus_trap(id) {
+                return;
+            }
+        }
+        self.cache_sorted_items_ids(app);
+        // items of the trap itself go first, so nested containers do not take over.
+        let found = self
+            .sorted_items_ids
+            .iter()
+            .filter(|id| {
+                self.items_owners.contains_key(id)
+                    && !self.disabled.contains(id)
+                    && self.is_within_focus_trap(id)
+            })
+            .min_by_key(|id| self.items_owners.get(id) != Some(&trap))
+            .cloned();
+        if let Some(id) = found {
+            self.select_item(app, Some(id));
+        }
+    }
+
     fn find_first_item(&self, items: &HashSet<WidgetId>) -> Option<WidgetId> {
         self.sorted_items_ids
             .iter()
@@ -471,6 +956,11 @@ impl DefaultInteractionsEngine {
     // TODO: refactor this shit!
     fn jump(&mut self, app: &mut Application, id: &WidgetId, data: NavJump) {
         if let Some(items) = self.containers.get(id) {
+            let items = &items
+                .iter()
+                .filter(|id| !self.disabled.contains(id))
+                .cloned()
+                .collect::<HashSet<_>>();
             match data {
                 NavJump::First => {
                     if let Some(id) = self.find_first_item(items) {
@@ -756,24 +1246,108 @@ impl DefaultInteractionsEngine {
         }
     }
 
+    fn update_hover(&mut self, app: &mut Application, pointer: PointerId, x: Scalar, y: Scalar) {
+        let found = self.find_button(app, x, y).map(|(id, _)| id);
+        let state = self.pointers.entry(pointer).or_default();
+        if state.hovered != found {
+            if let Some(id) = &state.hovered {
+                app.send_message(id, NavSignal::PointerLeave);
+            }
+            if let Some(id) = &found {
+                app.send_message(id, NavSignal::PointerEnter);
+            }
+            state.hovered = found;
+        }
+    }
+
+    fn pointer_location(app: &Application, id: &WidgetId, x: Scalar, y: Scalar) -> Option<Vec2> {
+        let rect = app.layout_data().items.get(id)?.ui_space;
+        let Vec2 { x, y } = app.untransform_widget_point(id, Vec2 { x, y })?;
+        let size = rect.size();
+        Some(Vec2 {
+            x: if size.x > 0.0 {
+                (x - rect.left) / size.x
+            } else {
+                0.0
+            },
+            y: if size.y > 0.0 {
+                (y - rect.top) / size.y
+            } else {
+                0.0
+            },
+        })
+    }
+
+    fn send_pointer_location(app: &mut Application, id: &WidgetId, x: Scalar, y: Scalar) -> bool {
+        if let Some(pos) = Self::pointer_location(app, id, x, y) {
+            app.send_message(id, NavSignal::Axis("pointer-x".to_owned(), pos.x));
+            app.send_message(id, NavSignal::Axis("pointer-y".to_owned(), pos.y));
+            true
+        } else {
+            false
+        }
+    }
+
     fn find_button(&self, app: &Application, x: Scalar, y: Scalar) -> Option<(WidgetId, Vec2)> {
-        self.find_button_inner(app, x, y, app.rendered_tree(), app.layout_data().ui_space)
+        let clip = app.layout_data().ui_space;
+        if x < clip.left || x > clip.right || y < clip.top || y > clip.bottom {
+            return None;
+        }
+        self.find_button_inner(app, Vec2 { x, y }, app.rendered_tree())
+    }
+
+    /// Maps `point` into untransformed ui space of `unit`, where it can be tested against layout
+    /// of that unit and its content. Returns [`None`] when unit transform cannot be inverted.
+    fn untransform_point(app: &Application, unit: &WidgetUnit, point: Vec2) -> Option<Vec2> {
+        let transform = match unit.transform() {
+            Some(transform) if *transform != Transform::default() => transform,
+            _ => return Some(point),
+        };
+        match unit
+            .as_data()
+            .and_then(|data| app.layout_data().items.get(data.id()))
+        {
+            Some(item) => transform.inverse_transform_point(item.ui_space, point),
+            None => Some(point),
+        }
+    }
+
+    /// Maps `point` into untransformed ui space of content box item, since item transform is
+    /// applied around its content.
+    fn untransform_item_point(
+        app: &Application,
+        item: &ContentBoxItem,
+        point: Vec2,
+    ) -> Option<Vec2> {
+        if item.layout.transform == Transform::default() {
+            return Some(point);
+        }
+        match item
+            .slot
+            .as_data()
+            .and_then(|data| app.layout_data().items.get(data.id()))
+        {
+            Some(layout) => item
+                .layout
+                .transform
+                .inverse_transform_point(layout.ui_space, point),
+            None => Some(point),
+        }
     }
 
     fn find_button_inner(
         &self,
         app: &Application,
-        x: Scalar,
-        y: Scalar,
+        point: Vec2,
         unit: &WidgetUnit,
-        mut clip: Rect,
     ) -> Option<(WidgetId, Vec2)> {
-        if x < clip.left || x > clip.right || y < clip.top || y > clip.bottom {
-            return None;
-        }
+        let Vec2 { x, y } = Self::untransform_point(app, unit, point)?;
         let mut result = None;
         if let Some(data) = unit.as_data() {
-            if self.buttons.contains_key(data.id()) {
+            if self.buttons.contains_key(data.id())
+                && !self.disabled.contains(data.id())
+                && self.is_within_focus_trap(data.id())
+            {
                 if let Some(layout) = app.layout_data().items.get(data.id()) {
                     let rect = layout.ui_space;
                     if x >= rect.left && x <= rect.right && y >= rect.top && y <= rect.bottom {
@@ -795,40 +1369,46 @@ impl DefaultInteractionsEngine {
                 }
             }
         }
+        let point = Vec2 { x, y };
         match unit {
             WidgetUnit::AreaBox(unit) => {
-                if let Some(id) = self.find_button_inner(app, x, y, &unit.slot, clip) {
+                if let Some(id) = self.find_button_inner(app, point, &unit.slot) {
                     result = Some(id);
                 }
             }
             WidgetUnit::ContentBox(unit) => {
-                if unit.clipping {
-                    if let Some(item) = app.layout_data().items.get(&unit.id) {
-                        clip = item.ui_space;
+                if let Some(item) = app.layout_data().items.get(&unit.id) {
+                    let clip = item.ui_space;
+                    if (unit.clips_x() && (x < clip.left || x > clip.right))
+                        || (unit.clips_y() && (y < clip.top || y > clip.bottom))
+                    {
+                        return result;
                     }
                 }
-                for item in &unit.items {
-                    if let Some(id) = self.find_button_inner(app, x, y, &item.slot, clip) {
-                        result = Some(id);
+                for item in unit.items.iter().filter(|item| item.layout.visible) {
+                    if let Some(point) = Self::untransform_item_point(app, item, point) {
+                        if let Some(id) = self.find_button_inner(app, point, &item.slot) {
+                            result = Some(id);
+                        }
                     }
                 }
             }
             WidgetUnit::FlexBox(unit) => {
                 for item in &unit.items {
-                    if let Some(id) = self.find_button_inner(app, x, y, &item.slot, clip) {
+                    if let Some(id) = self.find_button_inner(app, point, &item.slot) {
                         result = Some(id);
                     }
                 }
             }
             WidgetUnit::GridBox(unit) => {
                 for item in &unit.items {
-                    if let Some(id) = self.find_button_inner(app, x, y, &item.slot, clip) {
+                    if let Some(id) = self.find_button_inner(app, point, &item.slot) {
                         result = Some(id);
                     }
                 }
             }
             WidgetUnit::SizeBox(unit) => {
-                if let Some(id) = self.find_button_inner(app, x, y, &unit.slot, clip) {
+                if let Some(id) = self.find_button_inner(app, point, &unit.slot) {
                     result = Some(id);
                 }
             }
@@ -838,53 +1418,57 @@ impl DefaultInteractionsEngine {
     }
 
     fn does_hover_widget(&self, app: &Application, x: Scalar, y: Scalar) -> bool {
-        self.does_hover_widget_inner(app, x, y, app.rendered_tree())
+        self.does_hover_widget_inner(app, Vec2 { x, y }, app.rendered_tree())
     }
 
-    fn does_hover_widget_inner(
-        &self,
-        app: &Application,
-        x: Scalar,
-        y: Scalar,
-        unit: &WidgetUnit,
-    ) -> bool {
+    fn does_hover_widget_inner(&self, app: &Application, point: Vec2, unit: &WidgetUnit) -> bool {
+        let point = match Self::untransform_point(app, unit, point) {
+            Some(point) => point,
+            None => return false,
+        };
         if let Some(data) = unit.as_data() {
             if let Some(layout) = app.layout_data().items.get(data.id()) {
                 let rect = layout.ui_space;
-                if x >= rect.left && x <= rect.right && y >= rect.top && y <= rect.bottom {
+                if point.x >= rect.left
+                    && point.x <= rect.right
+                    && point.y >= rect.top
+                    && point.y <= rect.bottom
+                {
                     return true;
                 }
             }
         }
         match unit {
             WidgetUnit::AreaBox(unit) => {
-                if self.does_hover_widget_inner(app, x, y, &unit.slot) {
+                if self.does_hover_widget_inner(app, point, &unit.slot) {
                     return true;
                 }
             }
             WidgetUnit::ContentBox(unit) => {
-                for item in &unit.items {
-                    if self.does_hover_widget_inner(app, x, y, &item.slot) {
-                        return true;
+                for item in unit.items.iter().filter(|item| item.layout.visible) {
+                    if let Some(point) = Self::untransform_item_point(app, item, point) {
+                        if self.does_hover_widget_inner(app, point, &item.slot) {
+                            return true;
+                        }
                     }
                 }
             }
             WidgetUnit::FlexBox(unit) => {
                 for item in &unit.items {
-                    if self.does_hover_widget_inner(app, x, y, &item.slot) {
+                    if self.does_hover_widget_inner(app, point, &item.slot) {
                         return true;
                     }
                 }
             }
             WidgetUnit::GridBox(unit) => {
                 for item in &unit.items {
-                    if self.does_hover_widget_inner(app, x, y, &item.slot) {
+                    if self.does_hover_widget_inner(app, point, &item.slot) {
                         return true;
                     }
                 }
             }
             WidgetUnit::SizeBox(unit) => {
-                if self.does_hover_widget_inner(app, x, y, &unit.slot) {
+                if self.does_hover_widget_inner(app, point, &unit.slot) {
                     return true;
                 }
             }
@@ -899,12 +1483,27 @@ impl InteractionsEngine<DefaultInteractionsEngineResult, ()> for DefaultInteract
         &mut self,
         app: &mut Application,
     ) -> Result<DefaultInteractionsEngineResult, ()> {
+        let delta_time = match self.replaying.pop_front() {
+            Some(frame) => {
+                self.interactions_queue.extend(frame.interactions);
+                frame.delta_time
+            }
+            None => app.animations_delta_time,
+        };
+        if let Some(log) = &mut self.recording {
+            log.0.push(InteractionFrame {
+                delta_time,
+                interactions: self.interactions_queue.iter().cloned().collect(),
+            });
+        }
+        self.time += delta_time.max(0.0);
         let mut to_resize = HashSet::new();
         let mut to_relative_layout = HashSet::new();
         let mut to_select = None;
         let mut to_jump = HashMap::new();
         let mut to_focus = None;
         let mut to_send_axis = vec![];
+        let mut to_back = false;
         let mut to_send_custom = vec![];
         for (id, signal) in app.signals() {
             if let Some(signal) = signal.as_any().downcast_ref() {
@@ -967,11 +1566,23 @@ impl InteractionsEngine<DefaultInteractionsEngineResult, ()> for DefaultInteract
                             self.text_inputs.insert(id.to_owned());
                         }
                         NavType::ScrollView => {
-                            self.scroll_views.insert(id.to_owned());
+                            self.scroll_views.insert(id.to_owned(), Default::default());
                         }
                         NavType::ScrollViewContent => {
                             self.scroll_view_contents.insert(id.to_owned());
                         }
+                        NavType::BackHandler => {
+                            self.back_handlers.retain(|item| item != id);
+                            self.back_handlers.push(id.to_owned());
+                        }
+                        NavType::FocusTrap => {
+                            self.focus_traps.retain(|(item, _)| item != id);
+                            let selected = self.selected_item().cloned();
+                            self.focus_traps.push((id.to_owned(), selected));
+                        }
+                        NavType::EventStop(phase) => {
+                            self.event_stops.insert((id.to_owned(), *phase));
+                        }
                     },
                     NavSignal::Unregister(t) => match t {
                         NavType::Container => {
@@ -982,6 +1593,7 @@ impl InteractionsEngine<DefaultInteractionsEngineResult, ()> for DefaultInteract
                             }
                         }
                         NavType::Item => {
+                            self.disabled.remove(id);
                             if let Some(key) = self.items_owners.remove(id) {
                                 if let Some(items) = self.containers.get_mut(&key) {
                                     items.remove(&key);
@@ -994,9 +1606,18 @@ impl InteractionsEngine<DefaultInteractionsEngineResult, ()> for DefaultInteract
                             }
                         }
                         NavType::Button(_) => {
+                            self.disabled.remove(id);
                             self.buttons.remove(id);
+                            self.pointer_captures.retain(|_, v| v != id);
+                            for state in self.pointers.values_mut() {
+                                state.targets.retain(|_, v| v != id);
+                                if state.hovered.as_ref() == Some(id) {
+                                    state.hovered = None;
+                                }
+                            }
                         }
                         NavType::TextInput => {
+                            self.disabled.remove(id);
                             self.text_inputs.remove(id);
                             if let Some(focused) = &self.focused_text_input {
                                 if focused == id {
@@ -1010,6 +1631,25 @@ impl InteractionsEngine<DefaultInteractionsEngineResult, ()> for DefaultInteract
                         NavType::ScrollViewContent => {
                             self.scroll_view_contents.remove(id);
                         }
+                        NavType::BackHandler => {
+                            self.back_handlers.retain(|item| item != id);
+                        }
+                        NavType::FocusTrap => {
+                            if let Some(index) =
+                                self.focus_traps.iter().position(|(item, _)| item == id)
+                            {
+                                let (_, selected) = self.focus_traps.remove(index);
+                                if index == self.focus_traps.len() {
+                                    to_select = Some(match selected {
+                                        Some(selected) => selected.into(),
+                                        None => ().into(),
+                                    });
+                                }
+                            }
+                        }
+                        NavType::EventStop(phase) => {
+                            self.event_stops.remove(&(id.to_owned(), *phase));
+                        }
                     },
                     NavSignal::Select(idref) => to_select = Some(idref.to_owned()),
                     NavSignal::Unselect => to_select = Some(().into()),
@@ -1025,11 +1665,36 @@ impl InteractionsEngine<DefaultInteractionsEngineResult, ()> for DefaultInteract
                             }
                         }
                     }
+                    NavSignal::Disabled(true) => {
+                        let inserted = self.disabled.insert(id.to_owned());
+                        // selected item that got disabled loses selection.
+                        if inserted && self.selected_chain.last() == Some(id) {
+                            to_select = Some(().into());
+                        }
+                    }
+                    NavSignal::Disabled(false) => {
+                        self.disabled.remove(id);
+                    }
+                    NavSignal::CapturePointer(button) => {
+                        let pointer = self
+                            .pointers
+                            .iter()
+                            .find(|(_, state)| state.targets.get(button) == Some(id))
+                            .map(|(pointer, _)| *pointer)
+                            .unwrap_or(PRIMARY_POINTER);
+                        self.pointer_captures
+                            .insert((pointer, *button), id.to_owned());
+                    }
+                    NavSignal::ReleasePointer(button) => {
+                        self.pointer_captures
+                            .retain(|(_, b), v| b != button || v != id);
+                    }
                     NavSignal::Jump(data) => {
                         to_jump.insert(id.to_owned(), data.to_owned());
                     }
                     NavSignal::FocusTextInput(idref) => to_focus = Some(idref.to_owned()),
                     NavSignal::Axis(name, value) => to_send_axis.push((name.to_owned(), *value)),
+                    NavSignal::Back => to_back = true,
                     NavSignal::Custom(idref, data) => {
                         to_send_custom.push((idref.to_owned(), data.to_owned()))
                     }
@@ -1037,6 +1702,7 @@ impl InteractionsEngine<DefaultInteractionsEngineResult, ()> for DefaultInteract
                 }
             }
         }
+        let mut to_propagate = vec![];
 
         for (k, v) in &mut self.resize_listeners {
             if let Some(item) = app.layout_data().items.get(k) {
@@ -1050,6 +1716,35 @@ impl InteractionsEngine<DefaultInteractionsEngineResult, ()> for DefaultInteract
                 }
             }
         }
+        let scroll_view_metrics = self
+            .scroll_views
+            .keys()
+            .filter_map(|id| {
+                let content = self.find_scroll_view_content(&id)?;
+                let content_size = app.layout_data().items.get(&content)?.local_space.size();
+                let viewport_size = app.layout_data().items.get(id)?.local_space.size();
+                Some((id.to_owned(), content_size, viewport_size))
+            })
+            .collect::<Vec<_>>();
+        for (k, content_size, viewport_size) in scroll_view_metrics {
+            if let Some((c, v)) = self.scroll_views.get_mut(&k) {
+                if (c.x - content_size.x).abs() >= 1.0e-6
+                    || (c.y - content_size.y).abs() >= 1.0e-6
+                    || (v.x - viewport_size.x).abs() >= 1.0e-6
+                    || (v.y - viewport_size.y).abs() >= 1.0e-6
+                {
+                    app.send_message(
+                        &k,
+                        ScrollViewMetricsMessage {
+                            content_size,
+                            viewport_size,
+                        },
+                    );
+                    *c = content_size;
+                    *v = viewport_size;
+                }
+            }
+        }
         for (k, (r, s, v)) in &mut self.relative_layout_listeners {
             if let (Some(item), Some(rect)) = (
                 app.layout_data().items.get(r),
@@ -1073,6 +1768,7 @@ impl InteractionsEngine<DefaultInteractionsEngineResult, ()> for DefaultInteract
         if let Some(idref) = to_select {
             self.select_item(app, idref.read());
         }
+        self.select_first_trapped_item(app);
         if !to_jump.is_empty() {
             self.cache_sorted_items_ids(app);
         }
@@ -1092,9 +1788,21 @@ impl InteractionsEngine<DefaultInteractionsEngineResult, ()> for DefaultInteract
                 self.send_to_selected_item(app, NavSignal::Custom(().into(), data));
             }
         }
+        if to_back {
+            self.send_back(app);
+        }
         let mut result = DefaultInteractionsEngineResult::default();
         while let Some(interaction) = self.interactions_queue.pop_front() {
-            match interaction {
+            match &interaction {
+                Interaction::Navigate(_) | Interaction::KeyDown(_) | Interaction::KeyUp(_) => {
+                    self.pointer_modality = false
+                }
+                Interaction::PointerDown(_, _) | Interaction::MultiPointerDown(_, _, _) => {
+                    self.pointer_modality = true
+                }
+                _ => {}
+            }
+            match interaction.into_multi_pointer() {
                 Interaction::None => {}
                 Interaction::Navigate(msg) => match msg {
                     NavSignal::Select(idref) => {
@@ -1106,6 +1814,7 @@ impl InteractionsEngine<DefaultInteractionsEngineResult, ()> for DefaultInteract
                     NavSignal::Accept(_) | NavSignal::Context(_) | NavSignal::Cancel(_) => {
                         self.send_to_selected_item(app, msg);
                     }
+                    NavSignal::Back => self.send_back(app),
                     NavSignal::Up
                     | NavSignal::Down
                     | NavSignal::Left
@@ -1141,34 +1850,64 @@ impl InteractionsEngine<DefaultInteractionsEngineResult, ()> for DefaultInteract
                     },
                     _ => {}
                 },
-                Interaction::PointerMove(Vec2 { x, y }) => {
-                    if self.locked_widget.is_some() {
+                Interaction::PointerScroll(position, delta) => {
+                    if let Some(id) = self.find_scroll_target(app, position, delta) {
+                        self.jump(app, &id, NavJump::Scroll(NavScroll::Units(delta, true)));
+                    }
+                }
+                Interaction::MultiPointerMove(pointer, Vec2 { x, y }) => {
+                    self.pointers.entry(pointer).or_default().position = Vec2 { x, y };
+                    self.update_hover(app, pointer, x, y);
+                    if let Some(gesture) = self.gesture_states.get_mut(&pointer) {
+                        gesture.position = Vec2 { x, y };
+                        if gesture.distance() > self.gestures.long_press_tolerance {
+                            gesture.long_press_allowed = false;
+                        }
+                    }
+                    let captured = self
+                        .pointer_captures
+                        .iter()
+                        .filter(|((p, _), _)| *p == pointer)
+                        .map(|(_, id)| id.to_owned())
+                        .collect::<HashSet<_>>();
+                    if !captured.is_empty() {
+                        for id in captured {
+                            if Self::send_pointer_location(app, &id, x, y) {
+                                result.captured_pointer_location = true;
+                                result.captured_pointer_action = true;
+                            }
+                        }
+                    } else if pointer != PRIMARY_POINTER {
+                        let targets = self.pointers[&pointer]
+                            .targets
+                            .values()
+                            .filter(|id| self.buttons.get(id).copied().unwrap_or_default())
+                            .cloned()
+                            .collect::<HashSet<_>>();
+                        for id in targets {
+                            if Self::send_pointer_location(app, &id, x, y) {
+                                result.captured_pointer_location = true;
+                                result.captured_pointer_action = true;
+                            }
+                        }
+                        if self.does_hover_widget(app, x, y) {
+                            result.captured_pointer_location = true;
+                        }
+                    } else if self.locked_widget.is_some() {
                         if let Some(id) = self.selected_button(false) {
-                            if let Some(layout) = app.layout_data().items.get(id) {
-                                let rect = layout.ui_space;
-                                let size = rect.size();
-                                let x = if size.x > 0.0 {
-                                    (x - rect.left) / size.x
-                                } else {
-                                    0.0
-                                };
-                                let y = if size.y > 0.0 {
-                                    (y - rect.top) / size.y
-                                } else {
-                                    0.0
-                                };
+                            if let Some(pos) = Self::pointer_location(app, id, x, y) {
                                 result.captured_pointer_location = true;
                                 if self.send_to_selected_button(
                                     app,
                                     true,
-                                    NavSignal::Axis("pointer-x".to_owned(), x),
+                                    NavSignal::Axis("pointer-x".to_owned(), pos.x),
                                 ) {
                                     result.captured_pointer_action = true;
                                 }
                                 if self.send_to_selected_button(
                                     app,
                                     true,
-                                    NavSignal::Axis("pointer-y".to_owned(), y),
+                                    NavSignal::Axis("pointer-y".to_owned(), pos.y),
                                 ) {
                                     result.captured_pointer_action = true;
                                 }
@@ -1176,6 +1915,7 @@ impl InteractionsEngine<DefaultInteractionsEngineResult, ()> for DefaultInteract
                         }
                     } else if let Some((found, pos)) = self.find_button(app, x, y) {
                         result.captured_pointer_location = true;
+                        self.pointer_modality = true;
                         if !self.select_item(app, Some(found)) {
                             if self.send_to_selected_button(
                                 app,
@@ -1201,33 +1941,72 @@ impl InteractionsEngine<DefaultInteractionsEngineResult, ()> for DefaultInteract
                         }
                     }
                 }
-                Interaction::PointerDown(button, Vec2 { x, y }) => {
+                Interaction::MultiPointerDown(pointer, button, Vec2 { x, y }) => {
+                    self.pointers.entry(pointer).or_default().position = Vec2 { x, y };
+                    self.update_hover(app, pointer, x, y);
                     if let Some((found, pos)) = self.find_button(app, x, y) {
-                        self.select_item(app, Some(found));
+                        self.gesture_states.insert(
+                            pointer,
+                            GestureState {
+                                id: found.to_owned(),
+                                button,
+                                start_position: Vec2 { x, y },
+                                position: Vec2 { x, y },
+                                start_time: self.time,
+                                long_press_allowed: true,
+                            },
+                        );
                         result.captured_pointer_location = true;
                         let action = match button {
                             PointerButton::Trigger => NavSignal::Accept(true),
                             PointerButton::Context => NavSignal::Context(true),
                         };
-                        if self.send_to_selected_button(app, false, action) {
-                            result.captured_pointer_action = true;
-                        }
-                        if self.send_to_selected_button(
-                            app,
-                            true,
-                            NavSignal::Axis("pointer-x".to_owned(), pos.x),
-                        ) {
-                            result.captured_pointer_action = true;
+                        if button == PointerButton::Trigger && self.text_inputs.contains(&found) {
+                            if let Some(index) = app.layout_data().text_caret_at(&found, x) {
+                                app.send_message(
+                                    &found,
+                                    NavSignal::TextChange(NavTextChange::MoveCursorTo(index)),
+                                );
+                            }
                         }
-                        if self.send_to_selected_button(
-                            app,
-                            true,
-                            NavSignal::Axis("pointer-y".to_owned(), pos.y),
-                        ) {
+                        let target = if pointer == PRIMARY_POINTER {
+                            self.select_item(app, Some(found));
+                            if self.send_to_selected_button(app, false, action.to_owned()) {
+                                result.captured_pointer_action = true;
+                            }
+                            if self.send_to_selected_button(
+                                app,
+                                true,
+                                NavSignal::Axis("pointer-x".to_owned(), pos.x),
+                            ) {
+                                result.captured_pointer_action = true;
+                            }
+                            if self.send_to_selected_button(
+                                app,
+                                true,
+                                NavSignal::Axis("pointer-y".to_owned(), pos.y),
+                            ) {
+                                result.captured_pointer_action = true;
+                            }
+                            self.selected_button(false).cloned()
+                        } else {
+                            app.send_message(&found, action.to_owned());
+                            if self.buttons.get(&found).copied().unwrap_or_default() {
+                                Self::send_pointer_location(app, &found, x, y);
+                            }
                             result.captured_pointer_action = true;
+                            Some(found)
+                        };
+                        if let Some(target) = target {
+                            to_propagate.push((target.to_owned(), action));
+                            self.pointers
+                                .entry(pointer)
+                                .or_default()
+                                .targets
+                                .insert(button, target);
                         }
                     } else {
-                        if self.deselect_when_no_button_found {
+                        if pointer == PRIMARY_POINTER && self.deselect_when_no_button_found {
                             self.select_item(app, None);
                         }
                         if self.does_hover_widget(app, x, y) {
@@ -1235,17 +2014,1416 @@ impl InteractionsEngine<DefaultInteractionsEngineResult, ()> for DefaultInteract
                         }
                     }
                 }
-                Interaction::PointerUp(button, _) => {
+                Interaction::MultiPointerUp(pointer, button, position) => {
+                    if let Some(mut gesture) = self.gesture_states.remove(&pointer) {
+                        if gesture.button == button {
+                            gesture.position = position;
+                            if self.time - gesture.start_time <= self.gestures.swipe_time
+                                && gesture.distance() >= self.gestures.swipe_distance
+                            {
+                                app.send_message(
+                                    &gesture.id,
+                                    NavSignal::Gesture(NavGesture::Swipe(
+                                        gesture.id.to_owned(),
+                                        gesture.direction(),
+                                        gesture.delta(),
+                                    )),
+                                );
+                            }
+                        } else {
+                            self.gesture_states.insert(pointer, gesture);
+                        }
+                    }
+                    let state = self.pointers.entry(pointer).or_default();
+                    state.position = position;
+                    let target = state.targets.remove(&button);
+                    if pointer != PRIMARY_POINTER && state.targets.is_empty() {
+                        if let Some(hovered) =
+                            self.pointers.remove(&pointer).and_then(|s| s.hovered)
+                        {
+                            app.send_message(&hovered, NavSignal::PointerLeave);
+                        }
+                    }
                     let action = match button {
                         PointerButton::Trigger => NavSignal::Accept(false),
                         PointerButton::Context => NavSignal::Context(false),
                     };
-                    if self.send_to_selected_button(app, false, action) {
+                    let target = match self.pointer_captures.remove(&(pointer, button)) {
+                        Some(id) => Some(id),
+                        None if pointer == PRIMARY_POINTER => {
+                            target.or_else(|| self.selected_button(false).cloned())
+                        }
+                        None => target,
+                    };
+                    if let Some(id) = target {
+                        app.send_message(&id, action.to_owned());
+                        to_propagate.push((id, action));
                         result.captured_pointer_action = true;
                     }
                 }
+                // system key repeat of already held key is ignored.
+                Interaction::KeyDown(change)
+                    if !self.held_keys.iter().any(|(held, _, _)| held == &change) =>
+                {
+                    self.held_keys.push((change, self.time, 0));
+                    if self.send_to_focused_text_input(app, NavSignal::TextChange(change)) {
+                        result.captured_text_change = true;
+                    }
+                }
+                Interaction::KeyUp(change) => {
+                    self.held_keys.retain(|(held, _, _)| held != &change);
+                }
+                Interaction::MultiPointerLeave(pointer) => {
+                    self.gesture_states.remove(&pointer);
+                    if let Some(state) = self.pointers.get_mut(&pointer) {
+                        if let Some(hovered) = state.hovered.take() {
+                            app.send_message(&hovered, NavSignal::PointerLeave);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        if self.event_propagation {
+            for (target, signal) in to_propagate {
+                self.propagate_event(app, target, signal);
+            }
+        }
+        if self.key_repeat.rate > 0.0 {
+            let interval = 1.0 / self.key_repeat.rate;
+            for (change, pressed, repeats) in &mut self.held_keys {
+                let repeating =
+                    self.time - *pressed - self.key_repeat.initial_delay + KEY_REPEAT_TOLERANCE;
+                if repeating < 0.0 {
+                    continue;
+                }
+                // repeats are counted from press time, so they do not accumulate rounding errors.
+                let due = (repeating / interval) as usize + 1;
+                let count = due
+                    .saturating_sub(*repeats)
+                    .min(self.key_repeat.max_per_frame);
+                *repeats = due;
+                if let Some(id) = &self.focused_text_input {
+                    for _ in 0..count {
+                        app.send_message(id, NavSignal::TextChange(*change));
+                        result.captured_text_change = true;
+                    }
+                }
+            }
+        }
+        for gesture in self.gesture_states.values_mut() {
+            if gesture.long_press_allowed
+                && self.time - gesture.start_time >= self.gestures.long_press_time
+            {
+                gesture.long_press_allowed = false;
+                app.send_message(
+                    &gesture.id,
+                    NavSignal::Gesture(NavGesture::LongPress(
+                        gesture.id.to_owned(),
+                        gesture.position,
+                    )),
+                );
             }
         }
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pre_hooks,
+        props::Props,
+        testing::run,
+        widget::{
+            component::{
+                containers::{
+                    content_box::{content_box, nav_content_box, ContentBoxProps},
+                    scroll_box::nav_scroll_box,
+                    size_box::{size_box, SizeBoxProps},
+                },
+                interactive::{
+                    button::{button, ButtonProps},
+                    input_field::{text_input, TextInputProps},
+                    navigation::{
+                        use_nav_back_handler, use_nav_container_active, use_nav_event_stop,
+                        use_nav_item, use_nav_jump_step_pages_active, InteractionState,
+                        InteractiveDisabled, InteractiveVisualState, NavItemActive,
+                    },
+                },
+            },
+            context::WidgetContext,
+            node::WidgetNode,
+            unit::{content::ContentBoxItemLayout, size::SizeBoxSizeValue},
+            FnWidget, WidgetRef,
+        },
+        MessageData,
+    };
+
+    fn use_test_button(context: &mut WidgetContext) {
+        context.life_cycle.change(|context| {
+            for msg in context.messenger.messages {
+                match msg.as_any().downcast_ref() {
+                    Some(NavSignal::Accept(true)) => {
+                        context
+                            .signals
+                            .write(NavSignal::CapturePointer(PointerButton::Trigger));
+                    }
+                    Some(NavSignal::Gesture(gesture)) => {
+                        context
+                            .signals
+                            .write(NavSignal::Gesture(gesture.to_owned()));
+                    }
+                    Some(NavSignal::PointerEnter) => {
+                        context.signals.write(NavSignal::PointerEnter);
+                    }
+                    Some(NavSignal::PointerLeave) => {
+                        context.signals.write(NavSignal::PointerLeave);
+                    }
+                    _ => {}
+                }
+            }
+        });
+    }
+
+    #[pre_hooks(use_test_button)]
+    fn test_button(mut context: WidgetContext) -> WidgetNode {
+        button(context)
+    }
+
+    fn use_handling_button(context: &mut WidgetContext) {
+        context.use_hook(use_nav_event_stop(NavEventPhase::Bubble));
+    }
+
+    #[pre_hooks(use_handling_button)]
+    fn handling_button(mut context: WidgetContext) -> WidgetNode {
+        button(context)
+    }
+
+    fn use_event_panel(context: &mut WidgetContext) {
+        context.life_cycle.change(|context| {
+            for msg in context.messenger.messages {
+                if let Some(event) = msg.as_any().downcast_ref::<NavEvent>() {
+                    context.signals.write(event.to_owned());
+                }
+            }
+        });
+    }
+
+    #[pre_hooks(use_event_panel)]
+    fn event_panel(mut context: WidgetContext) -> WidgetNode {
+        content_box(context)
+    }
+
+    fn use_capture_stopping_panel(context: &mut WidgetContext) {
+        context.use_hook(use_nav_event_stop(NavEventPhase::Capture));
+    }
+
+    #[pre_hooks(use_event_panel, use_capture_stopping_panel)]
+    fn capture_stopping_panel(mut context: WidgetContext) -> WidgetNode {
+        content_box(context)
+    }
+
+    fn use_bubble_stopping_panel(context: &mut WidgetContext) {
+        context.use_hook(use_nav_event_stop(NavEventPhase::Bubble));
+    }
+
+    #[pre_hooks(use_event_panel, use_bubble_stopping_panel)]
+    fn bubble_stopping_panel(mut context: WidgetContext) -> WidgetNode {
+        content_box(context)
+    }
+
+    fn make_application() -> (Application, WidgetId, WidgetId) {
+        let mut application = Application::new();
+        let button_props = Props::new(NavItemActive).with(ContentBoxItemLayout {
+            anchors: Rect {
+                left: 0.0,
+                right: 0.5,
+                top: 0.0,
+                bottom: 0.5,
+            },
+            ..Default::default()
+        });
+        let other_props = Props::new(NavItemActive).with(ContentBoxItemLayout {
+            anchors: Rect {
+                left: 0.5,
+                right: 1.0,
+                top: 0.0,
+                bottom: 0.5,
+            },
+            ..Default::default()
+        });
+        let size_props = SizeBoxProps {
+            width: SizeBoxSizeValue::Fill,
+            height: SizeBoxSizeValue::Fill,
+            ..Default::default()
+        };
+        application.apply(widget! {
+            (#{"app"} nav_content_box [
+                (#{"button"} test_button: {button_props} {
+                    content = (#{"size"} size_box: {size_props.clone()})
+                })
+                (#{"other"} test_button: {other_props} {
+                    content = (#{"size"} size_box: {size_props})
+                })
+            ])
+        });
+        application.forced_process();
+        let find = |key| {
+            application
+                .signals()
+                .iter()
+                .find_map(|(id, msg)| match msg.as_any().downcast_ref() {
+                    Some(NavSignal::Register(NavType::Button(_))) if id.key() == key => {
+                        Some(id.to_owned())
+                    }
+                    _ => None,
+                })
+                .unwrap()
+        };
+        let button = find("button");
+        let other = find("other");
+        (application, button, other)
+    }
+
+    fn button_state(application: &Application, id: &WidgetId) -> ButtonProps {
+        application
+            .state_read(id)
+            .unwrap()
+            .read_cloned_or_default::<ButtonProps>()
+    }
+
+    #[test]
+    fn test_pointer_capture() {
+        let (mut application, id, _) = make_application();
+        let mut interactions = DefaultInteractionsEngine::new();
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerDown(PointerButton::Trigger, Vec2 { x: 25.0, y: 25.0 }),
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+        let state = button_state(&application, &id);
+        assert!(state.trigger);
+        assert_eq!(
+            interactions.pointer_capture(PRIMARY_POINTER, PointerButton::Trigger),
+            Some(&id)
+        );
+
+        let result = run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerMove(Vec2 { x: 75.0, y: 100.0 }),
+        );
+        assert!(result.captured_pointer_action);
+        run(&mut application, &mut interactions, Interaction::None);
+        let state = button_state(&application, &id);
+        assert_eq!(state.pointer, Vec2 { x: 1.5, y: 2.0 });
+
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerUp(PointerButton::Trigger, Vec2 { x: 75.0, y: 100.0 }),
+        );
+        assert!(interactions
+            .pointer_capture(PRIMARY_POINTER, PointerButton::Trigger)
+            .is_none());
+        run(&mut application, &mut interactions, Interaction::None);
+        let state = button_state(&application, &id);
+        assert!(!state.trigger);
+    }
+
+    fn gestures(application: &Application) -> Vec<NavGesture> {
+        application
+            .signals()
+            .iter()
+            .filter_map(|(_, msg)| match msg.as_any().downcast_ref() {
+                Some(NavSignal::Gesture(gesture)) => Some(gesture.to_owned()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_gesture_long_press() {
+        let (mut application, id, _) = make_application();
+        let mut interactions = DefaultInteractionsEngine::new();
+        application.animations_delta_time = 0.1;
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerDown(PointerButton::Trigger, Vec2 { x: 25.0, y: 25.0 }),
+        );
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerMove(Vec2 { x: 27.0, y: 24.0 }),
+        );
+        assert!(gestures(&application).is_empty());
+        let mut fired = vec![];
+        for _ in 0..10 {
+            run(&mut application, &mut interactions, Interaction::None);
+            fired.extend(gestures(&application));
+        }
+        assert_eq!(fired.len(), 1);
+        match &fired[0] {
+            NavGesture::LongPress(source, position) => {
+                assert_eq!(source, &id);
+                assert_eq!(*position, Vec2 { x: 27.0, y: 24.0 });
+            }
+            gesture => panic!("Unexpected gesture: {:?}", gesture),
+        }
+    }
+
+    #[test]
+    fn test_gesture_swipe() {
+        let (mut application, id, _) = make_application();
+        let mut interactions = DefaultInteractionsEngine::new();
+        application.animations_delta_time = 0.05;
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerDown(PointerButton::Trigger, Vec2 { x: 40.0, y: 25.0 }),
+        );
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerMove(Vec2 { x: 20.0, y: 30.0 }),
+        );
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerUp(PointerButton::Trigger, Vec2 { x: -30.0, y: 35.0 }),
+        );
+        let fired = gestures(&application);
+        assert_eq!(fired.len(), 1);
+        match &fired[0] {
+            NavGesture::Swipe(source, direction, delta) => {
+                assert_eq!(source, &id);
+                assert_eq!(*direction, NavDirection::Left);
+                assert_eq!(*delta, Vec2 { x: -70.0, y: 10.0 });
+            }
+            gesture => panic!("Unexpected gesture: {:?}", gesture),
+        }
+    }
+
+    #[test]
+    fn test_multi_pointer() {
+        let (mut application, a, b) = make_application();
+        let mut interactions = DefaultInteractionsEngine::new();
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::MultiPointerDown(0, PointerButton::Trigger, Vec2 { x: 25.0, y: 25.0 }),
+        );
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::MultiPointerDown(1, PointerButton::Trigger, Vec2 { x: 75.0, y: 25.0 }),
+        );
+        assert_eq!(
+            interactions.pointer_target(0, PointerButton::Trigger),
+            Some(&a)
+        );
+        assert_eq!(
+            interactions.pointer_target(1, PointerButton::Trigger),
+            Some(&b)
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+        assert!(button_state(&application, &a).trigger);
+        assert!(button_state(&application, &b).trigger);
+
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::MultiPointerUp(1, PointerButton::Trigger, Vec2 { x: 75.0, y: 25.0 }),
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+        assert!(button_state(&application, &a).trigger);
+        assert!(!button_state(&application, &b).trigger);
+
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerUp(PointerButton::Trigger, Vec2 { x: 25.0, y: 25.0 }),
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+        assert!(!button_state(&application, &a).trigger);
+        assert!(!button_state(&application, &b).trigger);
+    }
+
+    #[test]
+    fn test_hover() {
+        let (mut application, a, b) = make_application();
+        let mut interactions = DefaultInteractionsEngine::new();
+        let mut events = vec![];
+        for interaction in [
+            Interaction::PointerMove(Vec2 { x: 10.0, y: 10.0 }),
+            Interaction::PointerMove(Vec2 { x: 40.0, y: 20.0 }),
+            Interaction::PointerMove(Vec2 { x: 60.0, y: 20.0 }),
+            Interaction::PointerMove(Vec2 { x: 90.0, y: 40.0 }),
+            Interaction::PointerLeave,
+        ] {
+            run(&mut application, &mut interactions, interaction);
+            for (id, msg) in application.signals() {
+                match msg.as_any().downcast_ref() {
+                    Some(NavSignal::PointerEnter) => events.push((id.to_owned(), true)),
+                    Some(NavSignal::PointerLeave) => events.push((id.to_owned(), false)),
+                    _ => {}
+                }
+            }
+        }
+        assert_eq!(
+            events,
+            vec![
+                (a.to_owned(), true),
+                (a, false),
+                (b.to_owned(), true),
+                (b, false)
+            ]
+        );
+        assert!(interactions.hovered_widget(PRIMARY_POINTER).is_none());
+    }
+
+    #[test]
+    fn test_interactions_replay() {
+        let state = |interactions: &DefaultInteractionsEngine| {
+            (
+                interactions.selected_item().cloned(),
+                interactions.hovered_widget(PRIMARY_POINTER).cloned(),
+                interactions.hovered_widget(1).cloned(),
+                interactions.pointer_position(1),
+            )
+        };
+
+        let (mut application, a, b) = make_application();
+        let mut interactions = DefaultInteractionsEngine::new();
+        application.animations_delta_time = 0.1;
+        interactions.start_recording();
+        let mut fired = 0;
+        for interaction in vec![
+            Interaction::PointerMove(Vec2 { x: 10.0, y: 10.0 }),
+            Interaction::PointerDown(PointerButton::Trigger, Vec2 { x: 10.0, y: 10.0 }),
+        ]
+        .into_iter()
+        .chain((0..10).map(|_| Interaction::None))
+        .chain(vec![
+            Interaction::PointerUp(PointerButton::Trigger, Vec2 { x: 10.0, y: 10.0 }),
+            Interaction::MultiPointerMove(1, Vec2 { x: 60.0, y: 20.0 }),
+            Interaction::KeyDown(NavTextChange::InsertCharacter('a')),
+            Interaction::KeyUp(NavTextChange::InsertCharacter('a')),
+        ]) {
+            run(&mut application, &mut interactions, interaction);
+            fired += gestures(&application).len();
+        }
+        let log = interactions.stop_recording();
+        assert!(!interactions.is_recording());
+        assert_eq!(log.0.len(), 16);
+        assert_eq!(
+            log.0
+                .iter()
+                .map(|frame| frame.interactions.len())
+                .sum::<usize>(),
+            6
+        );
+        assert_eq!(fired, 1);
+        let expected = state(&interactions);
+        assert_eq!(
+            expected,
+            (
+                Some(a.to_owned()),
+                Some(a),
+                Some(b),
+                Some(Vec2 { x: 60.0, y: 20.0 })
+            )
+        );
+
+        // replayed frames use recorded delta time, so long press fires as in recorded session.
+        let data = serde_yaml::to_string(&log.to_prefab().unwrap()).unwrap();
+        let log = InteractionLog::from_prefab(serde_yaml::from_str(&data).unwrap()).unwrap();
+        let (mut application, _, _) = make_application();
+        let mut interactions = DefaultInteractionsEngine::new();
+        application.animations_delta_time = 0.0;
+        interactions.replay(log);
+        let mut fired = 0;
+        let mut frames = 0;
+        while interactions.is_replaying() {
+            run(&mut application, &mut interactions, Interaction::None);
+            fired += gestures(&application).len();
+            frames += 1;
+        }
+        assert_eq!(frames, 16);
+        assert_eq!(fired, 1);
+        assert_eq!(state(&interactions), expected);
+    }
+
+    #[test]
+    fn test_clipping_per_axis() {
+        let mut application = Application::new();
+        let clip_props = Props::new(ContentBoxProps {
+            clip_y: true,
+            ..Default::default()
+        })
+        .with(ContentBoxItemLayout {
+            anchors: Rect {
+                left: 0.0,
+                right: 0.5,
+                top: 0.0,
+                bottom: 0.5,
+            },
+            ..Default::default()
+        });
+        let button_props = Props::new(NavItemActive).with(ContentBoxItemLayout {
+            margin: Rect {
+                left: 0.0,
+                right: -50.0,
+                top: 0.0,
+                bottom: -50.0,
+            },
+            ..Default::default()
+        });
+        let size_props = SizeBoxProps {
+            width: SizeBoxSizeValue::Fill,
+            height: SizeBoxSizeValue::Fill,
+            ..Default::default()
+        };
+        application.apply(widget! {
+            (#{"app"} nav_content_box [
+                (#{"clip"} content_box: {clip_props} [
+                    (#{"button"} test_button: {button_props} {
+                        content = (#{"size"} size_box: {size_props})
+                    })
+                ])
+            ])
+        });
+        application.forced_process();
+        let mut interactions = DefaultInteractionsEngine::new();
+        let mut hovered = |position| {
+            run(
+                &mut application,
+                &mut interactions,
+                Interaction::PointerMove(position),
+            );
+            interactions
+                .hovered_widget(PRIMARY_POINTER)
+                .map(|id| id.key().to_owned())
+        };
+        // overflowing horizontally is not clipped.
+        assert_eq!(
+            hovered(Vec2 { x: 75.0, y: 25.0 }),
+            Some("button".to_owned())
+        );
+        // overflowing vertically is clipped.
+        assert_eq!(hovered(Vec2 { x: 25.0, y: 75.0 }), None);
+        assert_eq!(
+            hovered(Vec2 { x: 25.0, y: 25.0 }),
+            Some("button".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_transformed_hit_testing() {
+        let mut application = Application::new();
+        let button_props = Props::new(NavItemActive).with(ContentBoxItemLayout {
+            anchors: Rect {
+                left: 0.0,
+                right: 0.5,
+                top: 0.0,
+                bottom: 0.5,
+            },
+            transform: Transform {
+                translation: Vec2 { x: 50.0, y: 50.0 },
+                scale: Vec2 { x: 0.5, y: 0.5 },
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let size_props = SizeBoxProps {
+            width: SizeBoxSizeValue::Fill,
+            height: SizeBoxSizeValue::Fill,
+            ..Default::default()
+        };
+        application.apply(widget! {
+            (#{"app"} nav_content_box [
+                (#{"button"} test_button: {button_props} {
+                    content = (#{"size"} size_box: {size_props.clone()})
+                })
+            ])
+        });
+        application.forced_process();
+        let mut interactions = DefaultInteractionsEngine::new();
+        let mut hovered = |position| {
+            run(
+                &mut application,
+                &mut interactions,
+                Interaction::PointerMove(position),
+            );
+            interactions
+                .hovered_widget(PRIMARY_POINTER)
+                .map(|id| id.key().to_owned())
+        };
+        // untransformed layout area is not hit.
+        assert_eq!(hovered(Vec2 { x: 25.0, y: 25.0 }), None);
+        // transformed area is hit.
+        assert_eq!(
+            hovered(Vec2 { x: 60.0, y: 70.0 }),
+            Some("button".to_owned())
+        );
+        assert_eq!(hovered(Vec2 { x: 80.0, y: 60.0 }), None);
+
+        // quarter turn around top left corner maps 50x10 area onto x: 50..60, y: 0..50.
+        let button_props = Props::new(NavItemActive).with(ContentBoxItemLayout {
+            anchors: Rect {
+                left: 0.0,
+                right: 0.5,
+                top: 0.0,
+                bottom: 0.1,
+            },
+            transform: Transform {
+                translation: Vec2 { x: 60.0, y: 0.0 },
+                rotation: std::f32::consts::FRAC_PI_2 as Scalar,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"app"} nav_content_box [
+                (#{"button"} test_button: {button_props} {
+                    content = (#{"size"} size_box: {size_props})
+                })
+            ])
+        });
+        application.forced_process();
+        let id = application
+            .signals()
+            .iter()
+            .find_map(|(id, msg)| match msg.as_any().downcast_ref() {
+                Some(NavSignal::Register(NavType::Button(_))) => Some(id.to_owned()),
+                _ => None,
+            })
+            .unwrap();
+        let mut interactions = DefaultInteractionsEngine::new();
+        let mut triggered = |interaction| {
+            run(&mut application, &mut interactions, interaction);
+            run(&mut application, &mut interactions, Interaction::None);
+            button_state(&application, &id).trigger
+        };
+        // untransformed layout area is not hit.
+        assert!(!triggered(Interaction::PointerDown(
+            PointerButton::Trigger,
+            Vec2 { x: 40.0, y: 5.0 },
+        )));
+        assert!(!triggered(Interaction::PointerUp(
+            PointerButton::Trigger,
+            Vec2 { x: 40.0, y: 5.0 },
+        )));
+        // rotated area is hit.
+        assert!(triggered(Interaction::PointerDown(
+            PointerButton::Trigger,
+            Vec2 { x: 55.0, y: 40.0 },
+        )));
+        assert!(!triggered(Interaction::PointerUp(
+            PointerButton::Trigger,
+            Vec2 { x: 55.0, y: 40.0 },
+        )));
+    }
+
+    #[test]
+    fn test_interaction_state() {
+        let (mut application, a, b) = make_application();
+        let mut interactions = DefaultInteractionsEngine::new();
+        let state = |application: &Application, id| {
+            application
+                .state_read(id)
+                .unwrap()
+                .read_cloned_or_default::<InteractionState>()
+        };
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerMove(Vec2 { x: 10.0, y: 10.0 }),
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+        assert_eq!(
+            state(&application, &a),
+            InteractionState {
+                hovered: true,
+                pressed: false,
+                focused: true,
+                focus_visible: false,
+            }
+        );
+        assert_eq!(state(&application, &b), InteractionState::default());
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerDown(PointerButton::Trigger, Vec2 { x: 10.0, y: 10.0 }),
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+        assert!(state(&application, &a).pressed);
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerUp(PointerButton::Trigger, Vec2 { x: 10.0, y: 10.0 }),
+        );
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerMove(Vec2 { x: 60.0, y: 20.0 }),
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+        assert!(!state(&application, &a).hovered);
+        assert!(!state(&application, &a).pressed);
+        assert!(state(&application, &b).hovered);
+    }
+
+    #[test]
+    fn test_focus_visible() {
+        let (mut application, a, b) = make_application();
+        let mut interactions = DefaultInteractionsEngine::new();
+        let state = |application: &Application, id| {
+            application
+                .state_read(id)
+                .unwrap()
+                .read_cloned_or_default::<InteractionState>()
+        };
+        assert!(!interactions.focus_visible());
+
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::Navigate(NavSignal::Select(a.to_owned().into())),
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+        assert!(interactions.focus_visible());
+        assert!(state(&application, &a).focused);
+        assert!(state(&application, &a).focus_visible);
+
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerDown(PointerButton::Trigger, Vec2 { x: 60.0, y: 20.0 }),
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+        assert_eq!(interactions.selected_item(), Some(&b));
+        assert!(!interactions.focus_visible());
+        assert!(state(&application, &b).focused);
+        assert!(!state(&application, &b).focus_visible);
+        assert!(!state(&application, &a).focus_visible);
+    }
+
+    #[derive(MessageData, Debug, Clone)]
+    #[message_data(crate::messenger::MessageData)]
+    struct Themed(InteractiveVisualState);
+
+    fn use_themed(context: &mut WidgetContext) {
+        context.life_cycle.change(|context| {
+            if let Ok(state) = context.props.read_cloned::<InteractiveVisualState>() {
+                context.signals.write(Themed(state));
+            }
+        });
+    }
+
+    #[pre_hooks(use_themed)]
+    fn themed(mut context: WidgetContext) -> WidgetNode {
+        size_box(context)
+    }
+
+    #[test]
+    fn test_interactive_state() {
+        let mut application = Application::new();
+        let button_props = Props::new(NavItemActive).with(ContentBoxItemLayout {
+            anchors: Rect {
+                left: 0.0,
+                right: 0.5,
+                top: 0.0,
+                bottom: 0.5,
+            },
+            ..Default::default()
+        });
+        let size_props = SizeBoxProps {
+            width: SizeBoxSizeValue::Fill,
+            height: SizeBoxSizeValue::Fill,
+            ..Default::default()
+        };
+        application.apply(widget! {
+            (#{"app"} nav_content_box [
+                (#{"button"} button: {button_props} {
+                    content = (#{"themed"} themed: {size_props})
+                })
+            ])
+        });
+        application.forced_process();
+        let mut interactions = DefaultInteractionsEngine::new();
+        let mut states = vec![];
+        for interaction in [
+            Interaction::None,
+            Interaction::PointerMove(Vec2 { x: 60.0, y: 60.0 }),
+            Interaction::PointerMove(Vec2 { x: 10.0, y: 10.0 }),
+            Interaction::None,
+            Interaction::PointerDown(PointerButton::Trigger, Vec2 { x: 10.0, y: 10.0 }),
+            Interaction::None,
+        ] {
+            run(&mut application, &mut interactions, interaction);
+            let state = application
+                .signals()
+                .iter()
+                .find_map(|(_, msg)| msg.as_any().downcast_ref::<Themed>())
+                .unwrap()
+                .0;
+            if states.last() != Some(&state) {
+                states.push(state);
+            }
+        }
+        assert_eq!(
+            states,
+            vec![
+                InteractiveVisualState::Normal,
+                InteractiveVisualState::Hovered,
+                InteractiveVisualState::Pressed
+            ]
+        );
+    }
+
+    #[pre_hooks(use_nav_container_active, use_nav_jump_step_pages_active, use_nav_item)]
+    fn pages_content_box(mut context: WidgetContext) -> WidgetNode {
+        content_box(context)
+    }
+
+    #[test]
+    fn test_disabled() {
+        let mut application = Application::new();
+        let item_props = |left, right, disabled| {
+            Props::new(NavItemActive)
+                .with(InteractiveDisabled(disabled))
+                .with(ContentBoxItemLayout {
+                    anchors: Rect {
+                        left,
+                        right,
+                        top: 0.0,
+                        bottom: 0.5,
+                    },
+                    ..Default::default()
+                })
+        };
+        let size_props = SizeBoxProps {
+            width: SizeBoxSizeValue::Fill,
+            height: SizeBoxSizeValue::Fill,
+            ..Default::default()
+        };
+        let tree = |disabled| {
+            widget! {
+                (#{"app"} pages_content_box [
+                    (#{"a"} button: {item_props(0.0, 0.25, false)} {
+                        content = (#{"size"} size_box: {size_props.clone()})
+                    })
+                    (#{"b"} button: {item_props(0.25, 0.5, disabled)} {
+                        content = (#{"size"} size_box: {size_props.clone()})
+                    })
+                    (#{"c"} button: {item_props(0.5, 0.75, false)} {
+                        content = (#{"size"} size_box: {size_props.clone()})
+                    })
+                ])
+            }
+        };
+        let disabled_signals = |application: &Application| {
+            application
+                .signals()
+                .iter()
+                .filter_map(|(id, msg)| match msg.as_any().downcast_ref() {
+                    Some(NavSignal::Disabled(v)) => Some((id.key().to_owned(), *v)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        // disabled state is signaled only when it changes, so every frame is checked.
+        let step = |application: &mut Application,
+                    interactions: &mut DefaultInteractionsEngine,
+                    interaction| {
+            run(application, interactions, interaction);
+            disabled_signals(application)
+        };
+        application.apply(tree(true));
+        application.forced_process();
+        assert_eq!(disabled_signals(&application), vec![("b".to_owned(), true)]);
+        let buttons = application
+            .signals()
+            .iter()
+            .filter_map(|(id, msg)| match msg.as_any().downcast_ref() {
+                Some(NavSignal::Register(NavType::Button(_))) => {
+                    Some((id.key().to_owned(), id.to_owned()))
+                }
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+        let mut interactions = DefaultInteractionsEngine::new();
+
+        assert!(step(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerDown(PointerButton::Trigger, Vec2 { x: 37.0, y: 25.0 }),
+        )
+        .is_empty());
+        assert!(step(&mut application, &mut interactions, Interaction::None).is_empty());
+        assert!(interactions.is_disabled(&buttons["b"]));
+        assert!(interactions.selected_item().is_none());
+        assert!(!button_state(&application, &buttons["b"]).trigger);
+        assert!(step(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerUp(PointerButton::Trigger, Vec2 { x: 37.0, y: 25.0 }),
+        )
+        .is_empty());
+
+        assert!(step(
+            &mut application,
+            &mut interactions,
+            Interaction::Navigate(NavSignal::Select(buttons["a"].to_owned().into())),
+        )
+        .is_empty());
+        assert_eq!(interactions.selected_item(), Some(&buttons["a"]));
+        assert!(step(
+            &mut application,
+            &mut interactions,
+            Interaction::Navigate(NavSignal::Next),
+        )
+        .is_empty());
+        assert!(step(&mut application, &mut interactions, Interaction::None).is_empty());
+        assert_eq!(interactions.selected_item(), Some(&buttons["c"]));
+        for _ in 0..3 {
+            assert!(step(&mut application, &mut interactions, Interaction::None).is_empty());
+        }
+
+        application.apply(tree(false));
+        assert_eq!(
+            step(&mut application, &mut interactions, Interaction::None),
+            vec![("b".to_owned(), false)]
+        );
+        for _ in 0..3 {
+            assert!(step(&mut application, &mut interactions, Interaction::None).is_empty());
+        }
+        assert!(!interactions.is_disabled(&buttons["b"]));
+    }
+
+    #[test]
+    fn test_event_propagation() {
+        fn events(
+            handling: bool,
+            inner: FnWidget,
+            outer: FnWidget,
+        ) -> Vec<(usize, String, NavEventPhase)> {
+            let mut application = Application::new();
+            let size_props = SizeBoxProps {
+                width: SizeBoxSizeValue::Fill,
+                height: SizeBoxSizeValue::Fill,
+                ..Default::default()
+            };
+            let content = widget! { (#{"size"} size_box: {size_props}) };
+            let child = if handling {
+                widget! { (#{"button"} handling_button: {NavItemActive} { content = {content} }) }
+            } else {
+                widget! { (#{"button"} test_button: {NavItemActive} { content = {content} }) }
+            };
+            let inner = widget! { (#{"inner"} inner [ {child} ]) };
+            let outer = widget! { (#{"outer"} outer [ {inner} ]) };
+            application.apply(widget! {
+                (#{"app"} nav_content_box [
+                    {outer}
+                ])
+            });
+            application.forced_process();
+            let mut interactions = DefaultInteractionsEngine::new();
+            interactions.event_propagation = true;
+            let mut result = vec![];
+            for (frame, interaction) in [
+                Interaction::PointerDown(PointerButton::Trigger, Vec2 { x: 50.0, y: 50.0 }),
+                Interaction::None,
+            ]
+            .iter()
+            .enumerate()
+            {
+                run(&mut application, &mut interactions, interaction.to_owned());
+                for (id, msg) in application.signals() {
+                    if let Some(event) = msg.as_any().downcast_ref::<NavEvent>() {
+                        assert_eq!(event.target.key(), "button");
+                        result.push((frame, id.key().to_owned(), event.phase));
+                    }
+                }
+            }
+            // widgets get all events in the same process, so receivers order within phase only
+            // shows by where stopping widgets cut propagation off.
+            result.sort_by(|a, b| {
+                (a.0, a.2 == NavEventPhase::Bubble, &a.1).cmp(&(
+                    b.0,
+                    b.2 == NavEventPhase::Bubble,
+                    &b.1,
+                ))
+            });
+            result
+        }
+
+        // whole propagation happens in the frame target gets its signal.
+        assert_eq!(
+            events(false, event_panel, event_panel),
+            vec![
+                (0, "inner".to_owned(), NavEventPhase::Capture),
+                (0, "outer".to_owned(), NavEventPhase::Capture),
+                (0, "inner".to_owned(), NavEventPhase::Bubble),
+                (0, "outer".to_owned(), NavEventPhase::Bubble),
+            ]
+        );
+        assert_eq!(
+            events(true, event_panel, event_panel),
+            vec![
+                (0, "inner".to_owned(), NavEventPhase::Capture),
+                (0, "outer".to_owned(), NavEventPhase::Capture),
+            ]
+        );
+        // stopping in capture phase keeps event from descendants and from bubbling.
+        assert_eq!(
+            events(false, event_panel, capture_stopping_panel),
+            vec![(0, "outer".to_owned(), NavEventPhase::Capture)]
+        );
+        assert_eq!(
+            events(false, bubble_stopping_panel, event_panel),
+            vec![
+                (0, "inner".to_owned(), NavEventPhase::Capture),
+                (0, "outer".to_owned(), NavEventPhase::Capture),
+                (0, "inner".to_owned(), NavEventPhase::Bubble),
+            ]
+        );
+    }
+
+    fn use_back_screen(context: &mut WidgetContext) {
+        context.life_cycle.change(|context| {
+            for msg in context.messenger.messages {
+                if msg.as_any().downcast_ref::<()>().is_some() {
+                    let _ = context.state.write(true);
+                }
+            }
+        });
+    }
+
+    #[pre_hooks(use_back_screen)]
+    fn back_screen(mut context: WidgetContext) -> WidgetNode {
+        let WidgetContext { id, state, .. } = context;
+        if state.read_cloned_or_default::<bool>() {
+            widget! {()}
+        } else {
+            widget! { (#{"dialog"} back_dialog: {id.to_owned()}) }
+        }
+    }
+
+    fn use_back_dialog(context: &mut WidgetContext) {
+        context.life_cycle.change(|context| {
+            for msg in context.messenger.messages {
+                if let Some(NavSignal::Back) = msg.as_any().downcast_ref() {
+                    let owner = context.props.read_cloned_or_default::<WidgetId>();
+                    context.messenger.write(owner, ());
+                }
+            }
+        });
+    }
+
+    #[pre_hooks(use_nav_back_handler, use_back_dialog)]
+    fn back_dialog(mut context: WidgetContext) -> WidgetNode {
+        content_box(context)
+    }
+
+    #[test]
+    fn test_text_input_cursor_to() {
+        let mut application = Application::new();
+        let props = TextInputProps {
+            text: "h\u{e9}llo".to_owned(),
+            ..Default::default()
+        };
+        application.apply(widget! {
+            (#{"app"} nav_content_box [
+                (#{"input"} text_input: {props})
+            ])
+        });
+        application.forced_process();
+        let id = application
+            .signals()
+            .iter()
+            .find_map(|(id, msg)| match msg.as_any().downcast_ref() {
+                Some(NavSignal::Register(NavType::TextInput)) => Some(id.to_owned()),
+                _ => None,
+            })
+            .unwrap();
+        let input = |application: &Application| {
+            application
+                .state_read(&id)
+                .unwrap()
+                .read_cloned_or_default::<TextInputProps>()
+        };
+        let mut interactions = DefaultInteractionsEngine::new();
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::Navigate(NavSignal::FocusTextInput(id.to_owned().into())),
+        );
+        // caret after the two byte character.
+        application.send_message(&id, NavSignal::TextChange(NavTextChange::MoveCursorTo(2)));
+        run(&mut application, &mut interactions, Interaction::None);
+        run(&mut application, &mut interactions, Interaction::None);
+        assert_eq!(input(&application).cursor_position, 3);
+
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::KeyDown(NavTextChange::InsertCharacter('x')),
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+        assert_eq!(input(&application).text, "h\u{e9}xllo");
+
+        application.send_message(&id, NavSignal::TextChange(NavTextChange::MoveCursorTo(10)));
+        run(&mut application, &mut interactions, Interaction::None);
+        run(&mut application, &mut interactions, Interaction::None);
+        assert_eq!(input(&application).cursor_position, 7);
+    }
+
+    #[test]
+    fn test_key_repeat() {
+        let mut application = Application::new();
+        let props = TextInputProps {
+            text: "abcdefghijklmnopqrstuvwxyz".to_owned(),
+            cursor_position: 26,
+            ..Default::default()
+        };
+        application.apply(widget! {
+            (#{"app"} nav_content_box [
+                (#{"input"} text_input: {props})
+            ])
+        });
+        application.forced_process();
+        let id = application
+            .signals()
+            .iter()
+            .find_map(|(id, msg)| match msg.as_any().downcast_ref() {
+                Some(NavSignal::Register(NavType::TextInput)) => Some(id.to_owned()),
+                _ => None,
+            })
+            .unwrap();
+        let text = |application: &Application| {
+            application
+                .state_read(&id)
+                .unwrap()
+                .read_cloned_or_default::<TextInputProps>()
+                .text
+                .len()
+        };
+        let mut interactions = DefaultInteractionsEngine::new();
+        // frame time that does not land on repeat times.
+        application.animations_delta_time = 0.13;
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::Navigate(NavSignal::FocusTextInput(id.to_owned().into())),
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::KeyDown(NavTextChange::DeleteLeft),
+        );
+        // system key repeat is ignored.
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::KeyDown(NavTextChange::DeleteLeft),
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+        assert_eq!(text(&application), 25);
+
+        for _ in 0..5 {
+            run(&mut application, &mut interactions, Interaction::None);
+        }
+        application.animations_delta_time = 0.0;
+        run(&mut application, &mut interactions, Interaction::None);
+        let held = text(&application);
+        assert!(held < 23);
+
+        // long frame does not burst repeats.
+        application.animations_delta_time = 2.0;
+        run(&mut application, &mut interactions, Interaction::None);
+        application.animations_delta_time = 0.0;
+        run(&mut application, &mut interactions, Interaction::None);
+        let held_long = text(&application);
+        assert_eq!(held - held_long, interactions.key_repeat.max_per_frame);
+
+        application.animations_delta_time = 0.13;
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::KeyUp(NavTextChange::DeleteLeft),
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+        let released = text(&application);
+        assert!(released <= held_long);
+        for _ in 0..5 {
+            run(&mut application, &mut interactions, Interaction::None);
+        }
+        assert_eq!(text(&application), released);
+
+        // key up lost while text input lost focus does not keep the key repeating.
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::KeyDown(NavTextChange::DeleteLeft),
+        );
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::Navigate(NavSignal::FocusTextInput(().into())),
+        );
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::Navigate(NavSignal::FocusTextInput(id.to_owned().into())),
+        );
+        for _ in 0..10 {
+            run(&mut application, &mut interactions, Interaction::None);
+        }
+        assert_eq!(text(&application), released - 1);
+    }
+
+    #[test]
+    fn test_back() {
+        let backs = |application: &Application| {
+            application
+                .signals()
+                .iter()
+                .filter(|(_, msg)| msg.as_any().is::<NavBackUnhandled>())
+                .count()
+        };
+        let mut application = Application::new();
+        application.apply(widget! { (#{"app"} back_screen) });
+        application.forced_process();
+        let mut interactions = DefaultInteractionsEngine::new();
+        run(&mut application, &mut interactions, Interaction::None);
+        assert_eq!(
+            interactions.selected_back_handler().map(|id| id.key()),
+            Some("dialog")
+        );
+
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::Navigate(NavSignal::Back),
+        );
+        assert_eq!(backs(&application), 0);
+        for _ in 0..3 {
+            run(&mut application, &mut interactions, Interaction::None);
+            assert_eq!(backs(&application), 0);
+        }
+        assert!(interactions.selected_back_handler().is_none());
+        assert!(matches!(application.rendered_tree(), WidgetUnit::None));
+
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::Navigate(NavSignal::Back),
+        );
+        assert_eq!(backs(&application), 1);
+        run(&mut application, &mut interactions, Interaction::None);
+        assert_eq!(backs(&application), 0);
+
+        // handler with key being prefix of selected item parent key does not handle it.
+        let id = |path: &[&str]| {
+            let path = path.iter().map(|part| part.to_string()).collect::<Vec<_>>();
+            WidgetId::new("", &path)
+        };
+        interactions.back_handlers = vec![id(&["app", "menu"]), id(&["app"])];
+        interactions.selected_chain = vec![id(&["app", "menu_bar", "button"])];
+        assert_eq!(
+            interactions.selected_back_handler().map(|id| id.key()),
+            Some("app")
+        );
+        interactions.selected_chain = vec![id(&["app", "menu", "button"])];
+        assert_eq!(
+            interactions.selected_back_handler().map(|id| id.key()),
+            Some("menu")
+        );
+    }
+
+    #[test]
+    fn test_nested_pointer_scroll() {
+        let inner_props = SizeBoxProps {
+            width: SizeBoxSizeValue::Fill,
+            height: SizeBoxSizeValue::Exact(400.0),
+            ..Default::default()
+        };
+        let list_props = SizeBoxProps {
+            width: SizeBoxSizeValue::Exact(400.0),
+            height: SizeBoxSizeValue::Fill,
+            ..Default::default()
+        };
+        let outer_ref = WidgetRef::new();
+        let list_ref = WidgetRef::new();
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"app"} nav_content_box [
+                (#{"outer"} | {outer_ref.clone()} nav_scroll_box {
+                    content = (#{"inner"} size_box: {inner_props} {
+                        content = (#{"list"} | {list_ref.clone()} nav_scroll_box {
+                            content = (#{"items"} size_box: {list_props})
+                        })
+                    })
+                })
+            ])
+        });
+        application.forced_process();
+        let value = |application: &Application, view: &WidgetRef| {
+            application
+                .state_read(&view.read().unwrap())
+                .unwrap()
+                .read_cloned_or_default::<ScrollViewState>()
+                .value
+        };
+        let mut interactions = DefaultInteractionsEngine::new();
+        for _ in 0..5 {
+            run(&mut application, &mut interactions, Interaction::None);
+        }
+        let position = Vec2 { x: 50.0, y: 50.0 };
+
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerScroll(position, Vec2 { x: 0.0, y: 30.0 }),
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+        assert!((value(&application, &outer_ref).y - 0.1).abs() < 1.0e-4);
+        assert_eq!(value(&application, &list_ref).y, 0.0);
+
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerScroll(position, Vec2 { x: 30.0, y: 0.0 }),
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+        assert!((value(&application, &list_ref).x - 0.1).abs() < 1.0e-4);
+        assert_eq!(value(&application, &outer_ref).x, 0.0);
+
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerScroll(position, Vec2 { x: -30.0, y: 10.0 }),
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+        assert!(value(&application, &list_ref).x.abs() < 1.0e-4);
+        assert!((value(&application, &outer_ref).y - 0.1).abs() < 1.0e-4);
+
+        run(
+            &mut application,
+            &mut interactions,
+            Interaction::PointerScroll(position, Vec2 { x: -30.0, y: 0.0 }),
+        );
+        run(&mut application, &mut interactions, Interaction::None);
+        assert_eq!(value(&application, &list_ref).x, 0.0);
+        assert_eq!(value(&application, &outer_ref).x, 0.0);
+    }
+}
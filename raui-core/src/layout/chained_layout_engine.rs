@@ -0,0 +1,164 @@
+//! Layout engine composition
+
+use crate::{
+    layout::{CoordsMapping, Layout, LayoutEngine},
+    widget::unit::WidgetUnit,
+};
+
+/// Custom layout step run by [`ChainedLayoutEngine`] around its inner engine
+pub trait LayoutPass<E> {
+    /// Called before inner engine runs. Returning a tree makes inner engine lay out that tree
+    /// instead of the original one (e.g. with sizes assigned from external source).
+    fn pre_layout(
+        &mut self,
+        _mapping: &CoordsMapping,
+        _tree: &WidgetUnit,
+    ) -> Result<Option<WidgetUnit>, E> {
+        Ok(None)
+    }
+
+    /// Called with layout produced by inner engine, before it gets returned.
+    fn post_layout(
+        &mut self,
+        _mapping: &CoordsMapping,
+        _tree: &WidgetUnit,
+        _layout: &mut Layout,
+    ) -> Result<(), E> {
+        Ok(())
+    }
+}
+
+/// Layout engine that wraps `inner` engine with `pass`
+///
+/// Chains can be nested, with each level adding its own pass around the inner one.
+///
+/// # Example
+/// ```
+/// # use raui_core::prelude::*;
+/// struct Offset(Scalar);
+///
+/// impl LayoutPass<()> for Offset {
+///     fn post_layout(
+///         &mut self,
+///         _: &CoordsMapping,
+///         _: &WidgetUnit,
+///         layout: &mut Layout,
+///     ) -> Result<(), ()> {
+///         for item in layout.items.values_mut() {
+///             item.ui_space.left += self.0;
+///             item.ui_space.right += self.0;
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let mut engine = ChainedLayoutEngine::new(Offset(10.0), DefaultLayoutEngine);
+/// # let mut application = Application::new();
+/// # application.apply(widget! { (image_box) });
+/// # application.process();
+/// # let mapping = CoordsMapping::new(Rect { left: 0.0, right: 100.0, top: 0.0, bottom: 100.0 });
+/// application.layout(&mapping, &mut engine).unwrap();
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ChainedLayoutEngine<P, L> {
+    pub pass: P,
+    pub inner: L,
+}
+
+impl<P, L> ChainedLayoutEngine<P, L> {
+    pub fn new(pass: P, inner: L) -> Self {
+        Self { pass, inner }
+    }
+}
+
+impl<P, L, E> LayoutEngine<E> for ChainedLayoutEngine<P, L>
+where
+    P: LayoutPass<E>,
+    L: LayoutEngine<E>,
+{
+    fn layout(&mut self, mapping: &CoordsMapping, tree: &WidgetUnit) -> Result<Layout, E> {
+        let mut layout = match self.pass.pre_layout(mapping, tree)? {
+            Some(tree) => self.inner.layout(mapping, &tree)?,
+            None => self.inner.layout(mapping, tree)?,
+        };
+        self.pass.post_layout(mapping, tree, &mut layout)?;
+        Ok(layout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        layout::default_layout_engine::DefaultLayoutEngine,
+        widget::{
+            component::containers::{
+                content_box::content_box,
+                size_box::{size_box, SizeBoxProps},
+            },
+            unit::size::SizeBoxSizeValue,
+            utils::Rect,
+        },
+    };
+
+    struct DoubleWidths;
+
+    impl LayoutPass<()> for DoubleWidths {
+        fn post_layout(
+            &mut self,
+            _: &CoordsMapping,
+            _: &WidgetUnit,
+            layout: &mut Layout,
+        ) -> Result<(), ()> {
+            for item in layout.items.values_mut() {
+                item.local_space.right += item.local_space.width();
+                item.ui_space.right += item.ui_space.width();
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_chained_layout_engine() {
+        let size_props = SizeBoxProps {
+            width: SizeBoxSizeValue::Exact(30.0),
+            height: SizeBoxSizeValue::Exact(20.0),
+            ..Default::default()
+        };
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"root"} content_box [
+                (#{"item"} size_box: {size_props})
+            ])
+        });
+        application.process();
+        let mapping = CoordsMapping::new(Rect {
+            left: 0.0,
+            right: 100.0,
+            top: 0.0,
+            bottom: 50.0,
+        });
+        let mut engine = ChainedLayoutEngine::new(DoubleWidths, DefaultLayoutEngine);
+        application.layout(&mapping, &mut engine).unwrap();
+        let layout = application.layout_data();
+        assert_eq!(
+            layout.find("root").unwrap().ui_space,
+            Rect {
+                left: 0.0,
+                right: 200.0,
+                top: 0.0,
+                bottom: 50.0,
+            }
+        );
+        assert_eq!(
+            layout.find("root/item").unwrap().ui_space,
+            Rect {
+                left: 0.0,
+                right: 60.0,
+                top: 0.0,
+                bottom: 20.0,
+            }
+        );
+    }
+}
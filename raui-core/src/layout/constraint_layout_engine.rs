@@ -0,0 +1,433 @@
+//! Constraint based layout engine
+//!
+//! Lays out items of content boxes that have [`LayoutConstraint`]s in their
+//! [`ContentBoxItemLayout`] with Cassowary constraint solver. Items without constraints keep
+//! their anchored areas, so constrained items can be related to them too. Everything else is laid
+//! out the same as with [`DefaultLayoutEngine`].
+//!
+//! Constraints are solved for content boxes nested in content and area boxes - content of other
+//! containers (flex, grid and size boxes) is laid out by [`DefaultLayoutEngine`] as is.
+//!
+//! [`ContentBoxItemLayout`]: crate::widget::unit::content::ContentBoxItemLayout
+
+use crate::{
+    layout::{
+        default_layout_engine::DefaultLayoutEngine, CoordsMapping, Layout, LayoutEngine, LayoutNode,
+    },
+    widget::{
+        unit::{
+            area::AreaBox,
+            content::{
+                ContentBox, LayoutConstraint, LayoutConstraintStrength, LayoutConstraintTarget,
+                LayoutEdge, LayoutRelation,
+            },
+            WidgetUnit,
+        },
+        utils::{Rect, Vec2},
+        WidgetId,
+    },
+    Scalar,
+};
+use cassowary::{
+    strength::{MEDIUM, REQUIRED, STRONG, WEAK},
+    Constraint, Expression, RelationalOperator, Solver, Term, Variable,
+};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConstraintLayoutError {
+    /// Item constraint conflicts with other required constraints of its container.
+    UnsatisfiableConstraint(WidgetId),
+    /// Item constraint refers to sibling key that is not in its container.
+    UnknownSibling { id: WidgetId, key: String },
+}
+
+/// Solver works with `f64`, which is what [`Scalar`] already is with `scalar64` feature.
+#[allow(clippy::useless_conversion)]
+fn solver_value(value: Scalar) -> f64 {
+    f64::from(value)
+}
+
+#[derive(Debug, Copy, Clone)]
+struct EdgeVariables {
+    left: Variable,
+    right: Variable,
+    top: Variable,
+    bottom: Variable,
+}
+
+impl EdgeVariables {
+    fn new() -> Self {
+        Self {
+            left: Variable::new(),
+            right: Variable::new(),
+            top: Variable::new(),
+            bottom: Variable::new(),
+        }
+    }
+
+    fn terms(&self, edge: LayoutEdge, factor: f64) -> Vec<Term> {
+        let term = |variable, coefficient: f64| Term {
+            variable,
+            coefficient: coefficient * factor,
+        };
+        match edge {
+            LayoutEdge::Left => vec![term(self.left, 1.0)],
+            LayoutEdge::Right => vec![term(self.right, 1.0)],
+            LayoutEdge::Top => vec![term(self.top, 1.0)],
+            LayoutEdge::Bottom => vec![term(self.bottom, 1.0)],
+            LayoutEdge::Width => vec![term(self.right, 1.0), term(self.left, -1.0)],
+            LayoutEdge::Height => vec![term(self.bottom, 1.0), term(self.top, -1.0)],
+            LayoutEdge::CenterX => vec![term(self.left, 0.5), term(self.right, 0.5)],
+            LayoutEdge::CenterY => vec![term(self.top, 0.5), term(self.bottom, 0.5)],
+        }
+    }
+
+    fn fix(&self, rect: Rect, strength: f64) -> [Constraint; 4] {
+        let fix = |variable, value: Scalar| {
+            Constraint::new(
+                Expression::new(
+                    vec![Term {
+                        variable,
+                        coefficient: 1.0,
+                    }],
+                    -solver_value(value),
+                ),
+                RelationalOperator::Equal,
+                strength,
+            )
+        };
+        [
+            fix(self.left, rect.left),
+            fix(self.right, rect.right),
+            fix(self.top, rect.top),
+            fix(self.bottom, rect.bottom),
+        ]
+    }
+
+    fn rect(&self, solver: &Solver) -> Rect {
+        let value = |variable| solver.get_value(variable) as Scalar;
+        let left = value(self.left);
+        let top = value(self.top);
+        Rect {
+            left,
+            right: left + (value(self.right) - left).max(0.0),
+            top,
+            bottom: top + (value(self.bottom) - top).max(0.0),
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct ConstraintLayoutEngine;
+
+impl ConstraintLayoutEngine {
+    pub fn layout_node(
+        size_available: Vec2,
+        unit: &WidgetUnit,
+    ) -> Result<Option<LayoutNode>, ConstraintLayoutError> {
+        match unit {
+            WidgetUnit::AreaBox(b) => Self::layout_area_box(size_available, b),
+            WidgetUnit::ContentBox(b) => Self::layout_content_box(size_available, b),
+            _ => Ok(DefaultLayoutEngine::layout_node(size_available, unit)),
+        }
+    }
+
+    pub fn layout_area_box(
+        size_available: Vec2,
+        unit: &AreaBox,
+    ) -> Result<Option<LayoutNode>, ConstraintLayoutError> {
+        if !unit.id.is_valid() {
+            return Ok(None);
+        }
+        let children = Self::layout_node(size_available, &unit.slot)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        let size = children
+            .first()
+            .map(|child| child.local_space.size())
+            .unwrap_or_default();
+        Ok(Some(LayoutNode {
+            id: unit.id.to_owned(),
+            local_space: Rect {
+                left: 0.0,
+                right: size.x,
+                top: 0.0,
+                bottom: size.y,
+            },
+            children,
+        }))
+    }
+
+    pub fn layout_content_box(
+        size_available: Vec2,
+        unit: &ContentBox,
+    ) -> Result<Option<LayoutNode>, ConstraintLayoutError> {
+        if !unit.id.is_valid() {
+            return Ok(None);
+        }
+        let outer_size = size_available;
+        let size_available = DefaultLayoutEngine::padded_size(size_available, unit.padding);
+        let rects = Self::solve_content_box(size_available, unit)?;
+        let mut children = Vec::with_capacity(unit.items.len());
+        for (item, rect) in unit.items.iter().zip(rects) {
+            if let Some(mut child) = Self::layout_node(rect.size(), &item.slot)? {
+                DefaultLayoutEngine::place_content_box_item(&mut child, rect, item.layout.align);
+                children.push(child);
+            }
+        }
+        DefaultLayoutEngine::offset_children(&mut children, unit.padding);
+        Ok(Some(LayoutNode {
+            id: unit.id.to_owned(),
+            local_space: Rect {
+                left: 0.0,
+                right: outer_size.x,
+                top: 0.0,
+                bottom: outer_size.y,
+            },
+            children,
+        }))
+    }
+
+    /// Areas of content box items within its content area of `size_available`.
+    pub fn solve_content_box(
+        size_available: Vec2,
+        unit: &ContentBox,
+    ) -> Result<Vec<Rect>, ConstraintLayoutError> {
+        let anchored = unit
+            .items
+            .iter()
+            .map(|item| DefaultLayoutEngine::content_box_item_rect(size_available, &item.layout))
+            .collect::<Vec<_>>();
+        if unit
+            .items
+            .iter()
+            .all(|item| item.layout.constraints.is_empty())
+        {
+            return Ok(anchored);
+        }
+
+        let mut solver = Solver::new();
+        let parent = EdgeVariables::new();
+        let variables = unit
+            .items
+            .iter()
+            .map(|_| EdgeVariables::new())
+            .collect::<Vec<_>>();
+        let keys = unit
+            .items
+            .iter()
+            .zip(variables.iter())
+            .filter_map(|(item, variables)| {
+                let data = item.slot.as_data()?;
+                Some((data.id().key(), *variables))
+            })
+            .collect::<HashMap<_, _>>();
+        let area = Rect {
+            left: 0.0,
+            right: size_available.x,
+            top: 0.0,
+            bottom: size_available.y,
+        };
+        for constraint in parent.fix(area, REQUIRED) {
+            solver
+                .add_constraint(constraint)
+                .map_err(|_| ConstraintLayoutError::UnsatisfiableConstraint(unit.id.to_owned()))?;
+        }
+        for ((item, rect), variables) in unit.items.iter().zip(anchored).zip(variables.iter()) {
+            let id = item
+                .slot
+                .as_data()
+                .map(|data| data.id().to_owned())
+                .unwrap_or_default();
+            let mut add = |constraint| {
+                solver
+                    .add_constraint(constraint)
+                    .map_err(|_| ConstraintLayoutError::UnsatisfiableConstraint(id.to_owned()))
+            };
+            if item.layout.constraints.is_empty() {
+                for constraint in variables.fix(rect, REQUIRED) {
+                    add(constraint)?;
+                }
+                continue;
+            }
+            // anchored area is used for whatever constraints leave undetermined.
+            for constraint in variables.fix(rect, WEAK) {
+                add(constraint)?;
+            }
+            for edges in [
+                [LayoutEdge::Left, LayoutEdge::Right],
+                [LayoutEdge::Top, LayoutEdge::Bottom],
+            ] {
+                let mut terms = variables.terms(edges[1], 1.0);
+                terms.extend(variables.terms(edges[0], -1.0));
+                add(Constraint::new(
+                    Expression::new(terms, 0.0),
+                    RelationalOperator::GreaterOrEqual,
+                    STRONG,
+                ))?;
+            }
+            for LayoutConstraint {
+                edge,
+                relation,
+                target,
+                target_edge,
+                multiplier,
+                constant,
+                strength,
+            } in &item.layout.constraints
+            {
+                let mut terms = variables.terms(*edge, 1.0);
+                let target = match target {
+                    LayoutConstraintTarget::None => None,
+                    LayoutConstraintTarget::Parent => Some(&parent),
+                    LayoutConstraintTarget::Sibling(key) => match keys.get(key.as_str()) {
+                        Some(variables) => Some(variables),
+                        None => {
+                            return Err(ConstraintLayoutError::UnknownSibling {
+                                id,
+                                key: key.to_owned(),
+                            })
+                        }
+                    },
+                };
+                if let Some(target) = target {
+                    terms.extend(target.terms(*target_edge, -solver_value(*multiplier)));
+                }
+                let operator = match relation {
+                    LayoutRelation::Equal => RelationalOperator::Equal,
+                    LayoutRelation::LessOrEqual => RelationalOperator::LessOrEqual,
+                    LayoutRelation::GreaterOrEqual => RelationalOperator::GreaterOrEqual,
+                };
+                let strength = match strength {
+                    LayoutConstraintStrength::Required => REQUIRED,
+                    LayoutConstraintStrength::Strong => STRONG,
+                    LayoutConstraintStrength::Medium => MEDIUM,
+                    LayoutConstraintStrength::Weak => WEAK,
+                };
+                add(Constraint::new(
+                    Expression::new(terms, -solver_value(*constant)),
+                    operator,
+                    strength,
+                ))?;
+            }
+        }
+        Ok(variables.iter().map(|v| v.rect(&solver)).collect())
+    }
+}
+
+impl LayoutEngine<ConstraintLayoutError> for ConstraintLayoutEngine {
+    fn layout(
+        &mut self,
+        mapping: &CoordsMapping,
+        tree: &WidgetUnit,
+    ) -> Result<Layout, ConstraintLayoutError> {
+        let ui_space = mapping.virtual_area();
+        if let Some(root) = Self::layout_node(ui_space.size(), tree)? {
+            Ok(DefaultLayoutEngine::unpack_root(ui_space, root))
+        } else {
+            Ok(Layout {
+                ui_space,
+                ..Default::default()
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        props::Props,
+        widget::{
+            component::containers::{
+                content_box::content_box,
+                size_box::{size_box, SizeBoxProps},
+            },
+            unit::{content::ContentBoxItemLayout, size::SizeBoxSizeValue},
+        },
+    };
+
+    #[test]
+    fn test_constraint_layout() {
+        let size_props = SizeBoxProps {
+            width: SizeBoxSizeValue::Fill,
+            height: SizeBoxSizeValue::Fill,
+            ..Default::default()
+        };
+        let a_props = Props::new(size_props.clone()).with(ContentBoxItemLayout {
+            constraints: vec![LayoutConstraint::relate(
+                LayoutEdge::Left,
+                LayoutRelation::Equal,
+                LayoutConstraintTarget::Parent,
+                LayoutEdge::Left,
+            )
+            .constant(10.0)],
+            ..Default::default()
+        });
+        let b_props = Props::new(size_props).with(ContentBoxItemLayout {
+            constraints: vec![
+                // equal widths.
+                LayoutConstraint::relate(
+                    LayoutEdge::Width,
+                    LayoutRelation::Equal,
+                    LayoutConstraintTarget::Sibling("a".to_owned()),
+                    LayoutEdge::Width,
+                ),
+                // fixed gap.
+                LayoutConstraint::relate(
+                    LayoutEdge::Left,
+                    LayoutRelation::Equal,
+                    LayoutConstraintTarget::Sibling("a".to_owned()),
+                    LayoutEdge::Right,
+                )
+                .constant(20.0),
+                LayoutConstraint::relate(
+                    LayoutEdge::Right,
+                    LayoutRelation::Equal,
+                    LayoutConstraintTarget::Parent,
+                    LayoutEdge::Right,
+                )
+                .constant(-10.0),
+            ],
+            ..Default::default()
+        });
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"root"} content_box [
+                (#{"a"} size_box: {a_props})
+                (#{"b"} size_box: {b_props})
+            ])
+        });
+        application.process();
+        let mapping = CoordsMapping::new(Rect {
+            left: 0.0,
+            right: 200.0,
+            top: 0.0,
+            bottom: 100.0,
+        });
+        application
+            .layout(&mapping, &mut ConstraintLayoutEngine)
+            .unwrap();
+        let rect = |path| application.layout_data().find(path).unwrap().ui_space;
+        assert_eq!(
+            rect("root/a"),
+            Rect {
+                left: 10.0,
+                right: 90.0,
+                top: 0.0,
+                bottom: 100.0,
+            }
+        );
+        assert_eq!(
+            rect("root/b"),
+            Rect {
+                left: 110.0,
+                right: 190.0,
+                top: 0.0,
+                bottom: 100.0,
+            }
+        );
+    }
+}
@@ -1,9 +1,13 @@
 use crate::{
-    layout::{CoordsMapping, Layout, LayoutEngine, LayoutItem, LayoutNode},
+    layout::{
+        chained_layout_engine::ChainedLayoutEngine,
+        text_fit::{TextFitPass, TextMeasurer},
+        CoordsMapping, Layout, LayoutEngine, LayoutItem, LayoutNode,
+    },
     widget::{
         unit::{
             area::AreaBox,
-            content::ContentBox,
+            content::{ContentBox, ContentBoxItemLayout},
             flex::FlexBox,
             grid::GridBox,
             image::{ImageBox, ImageBoxSizeValue},
@@ -11,41 +15,112 @@ use crate::{
             text::{TextBox, TextBoxSizeValue},
             WidgetUnit,
         },
-        utils::{lerp, Rect, Vec2},
+        utils::{lerp, IntRect, Rect, Vec2},
         WidgetId,
     },
-    Scalar,
+    Integer, Scalar,
 };
 use std::collections::HashMap;
 
+/// Custom layout of a single widget: gets area available to the widget (in its parent local
+/// space) and returns area chosen for it
+pub type LayoutOverride = Box<dyn Fn(Rect) -> Rect + Send + Sync>;
+
+pub type LayoutOverrides = HashMap<WidgetId, LayoutOverride>;
+
 #[derive(Debug, Default, Copy, Clone)]
 pub struct DefaultLayoutEngine;
 
 impl DefaultLayoutEngine {
+    pub fn with_overrides(overrides: LayoutOverrides) -> OverridingLayoutEngine {
+        OverridingLayoutEngine { overrides }
+    }
+
+    /// Layout engine that fits text of text boxes into their areas with given `measurer`, see
+    /// [`TextFitPass`].
+    pub fn with_text_measurer<M>(measurer: M) -> ChainedLayoutEngine<TextFitPass<M>, Self>
+    where
+        M: TextMeasurer,
+    {
+        ChainedLayoutEngine::new(TextFitPass::new(measurer), Self)
+    }
+
+    /// Size given tree would take when laid out in `size_available`, computed the same way as
+    /// with the full layout pass, but without producing [`Layout`].
+    ///
+    /// Units that are not laid out (none or portal boxes) measure as zero size. Text boxes are
+    /// sized by their width and height values only, since no text is measured here.
+    pub fn measure(&self, unit: &WidgetUnit, size_available: Vec2) -> Vec2 {
+        Self::layout_node(size_available, unit)
+            .map(|node| node.local_space.size())
+            .unwrap_or_default()
+    }
+
     pub fn layout_node(size_available: Vec2, unit: &WidgetUnit) -> Option<LayoutNode> {
+        Self::layout_node_with(&LayoutOverrides::new(), size_available, unit)
+    }
+
+    /// Same as [`layout_node`][Self::layout_node], but widgets in `overrides` get the area
+    /// returned by their override, and their children get laid out within it.
+    pub fn layout_node_with(
+        overrides: &LayoutOverrides,
+        size_available: Vec2,
+        unit: &WidgetUnit,
+    ) -> Option<LayoutNode> {
+        let f = match unit.as_data().and_then(|data| overrides.get(data.id())) {
+            Some(f) => f,
+            None => return Self::layout_unit_with(overrides, size_available, unit),
+        };
+        let rect = f(Rect {
+            left: 0.0,
+            right: size_available.x,
+            top: 0.0,
+            bottom: size_available.y,
+        });
+        let mut node = Self::layout_unit_with(overrides, rect.size(), unit)?;
+        node.local_space = rect;
+        Some(node)
+    }
+
+    fn layout_unit_with(
+        overrides: &LayoutOverrides,
+        size_available: Vec2,
+        unit: &WidgetUnit,
+    ) -> Option<LayoutNode> {
         match unit {
             WidgetUnit::None | WidgetUnit::PortalBox(_) => None,
-            WidgetUnit::AreaBox(b) => Self::layout_area_box(size_available, b),
-            WidgetUnit::ContentBox(b) => Self::layout_content_box(size_available, b),
-            WidgetUnit::FlexBox(b) => Self::layout_flex_box(size_available, b),
-            WidgetUnit::GridBox(b) => Self::layout_grid_box(size_available, b),
-            WidgetUnit::SizeBox(b) => Self::layout_size_box(size_available, b),
+            WidgetUnit::AreaBox(b) => Self::layout_area_box_with(overrides, size_available, b),
+            WidgetUnit::ContentBox(b) => {
+                Self::layout_content_box_with(overrides, size_available, b)
+            }
+            WidgetUnit::FlexBox(b) => Self::layout_flex_box_with(overrides, size_available, b),
+            WidgetUnit::GridBox(b) => Self::layout_grid_box_with(overrides, size_available, b),
+            WidgetUnit::SizeBox(b) => Self::layout_size_box_with(overrides, size_available, b),
             WidgetUnit::ImageBox(b) => Self::layout_image_box(size_available, b),
             WidgetUnit::TextBox(b) => Self::layout_text_box(size_available, b),
         }
     }
 
     pub fn layout_area_box(size_available: Vec2, unit: &AreaBox) -> Option<LayoutNode> {
+        Self::layout_area_box_with(&LayoutOverrides::new(), size_available, unit)
+    }
+
+    fn layout_area_box_with(
+        overrides: &LayoutOverrides,
+        size_available: Vec2,
+        unit: &AreaBox,
+    ) -> Option<LayoutNode> {
         if !unit.id.is_valid() {
             return None;
         }
-        let (children, w, h) = if let Some(child) = Self::layout_node(size_available, &unit.slot) {
-            let w = child.local_space.width();
-            let h = child.local_space.height();
-            (vec![child], w, h)
-        } else {
-            (vec![], 0.0, 0.0)
-        };
+        let (children, w, h) =
+            if let Some(child) = Self::layout_node_with(overrides, size_available, &unit.slot) {
+                let w = child.local_space.width();
+                let h = child.local_space.height();
+                (vec![child], w, h)
+            } else {
+                (vec![], 0.0, 0.0)
+            };
         let local_space = Rect {
             left: 0.0,
             right: w,
@@ -60,66 +135,97 @@ impl DefaultLayoutEngine {
     }
 
     pub fn layout_content_box(size_available: Vec2, unit: &ContentBox) -> Option<LayoutNode> {
+        Self::layout_content_box_with(&LayoutOverrides::new(), size_available, unit)
+    }
+
+    fn layout_content_box_with(
+        overrides: &LayoutOverrides,
+        size_available: Vec2,
+        unit: &ContentBox,
+    ) -> Option<LayoutNode> {
         if !unit.id.is_valid() {
             return None;
         }
-        let children = unit
+        let outer_size = size_available;
+        let size_available = Self::padded_size(size_available, unit.padding);
+        let mut children = unit
             .items
             .iter()
             .filter_map(|item| {
-                let left = lerp(0.0, size_available.x, item.layout.anchors.left);
-                let left = left + item.layout.margin.left + item.layout.offset.x;
-                let right = lerp(0.0, size_available.x, item.layout.anchors.right);
-                let right = right - item.layout.margin.right + item.layout.offset.x;
-                let top = lerp(0.0, size_available.y, item.layout.anchors.top);
-                let top = top + item.layout.margin.top + item.layout.offset.y;
-                let bottom = lerp(0.0, size_available.y, item.layout.anchors.bottom);
-                let bottom = bottom - item.layout.margin.bottom + item.layout.offset.y;
-                let width = (right - left).max(0.0);
-                let height = (bottom - top).max(0.0);
-                let size = Vec2 {
-                    x: width,
-                    y: height,
-                };
-                if let Some(mut child) = Self::layout_node(size, &item.slot) {
-                    let diff = child.local_space.width() - width;
-                    let ox = lerp(0.0, diff, item.layout.align.x);
-                    child.local_space.left += left - ox;
-                    child.local_space.right += left - ox;
-                    let diff = child.local_space.height() - height;
-                    let oy = lerp(0.0, diff, item.layout.align.y);
-                    child.local_space.top += top - oy;
-                    child.local_space.bottom += top - oy;
-                    Some(child)
-                } else {
-                    None
-                }
+                let rect = Self::content_box_item_rect(size_available, &item.layout);
+                let mut child = Self::layout_node_with(overrides, rect.size(), &item.slot)?;
+                Self::place_content_box_item(&mut child, rect, item.layout.align);
+                Some(child)
             })
             .collect::<Vec<_>>();
+        Self::offset_children(&mut children, unit.padding);
         Some(LayoutNode {
             id: unit.id.to_owned(),
             local_space: Rect {
                 left: 0.0,
-                right: size_available.x,
+                right: outer_size.x,
                 top: 0.0,
-                bottom: size_available.y,
+                bottom: outer_size.y,
             },
             children,
         })
     }
 
     pub fn layout_flex_box(size_available: Vec2, unit: &FlexBox) -> Option<LayoutNode> {
+        Self::layout_flex_box_with(&LayoutOverrides::new(), size_available, unit)
+    }
+
+    fn layout_flex_box_with(
+        overrides: &LayoutOverrides,
+        size_available: Vec2,
+        unit: &FlexBox,
+    ) -> Option<LayoutNode> {
         if !unit.id.is_valid() {
             return None;
         }
-        if unit.wrap {
-            Some(Self::layout_flex_box_wrapping(size_available, unit))
+        let size = Self::padded_size(size_available, unit.padding);
+        let mut result = if unit.wrap {
+            Self::layout_flex_box_wrapping_with(overrides, size, unit)
+        } else {
+            Self::layout_flex_box_no_wrap_with(overrides, size, unit)
+        };
+        Self::offset_children(&mut result.children, unit.padding);
+        result.local_space.right += unit.padding.left + unit.padding.right;
+        result.local_space.bottom += unit.padding.top + unit.padding.bottom;
+        Some(result)
+    }
+
+    /// Separation of flex box items, with percentage resolved against `size_available` along box
+    /// main axis.
+    fn flex_box_separation(size_available: Vec2, unit: &FlexBox) -> Scalar {
+        if unit.direction.is_horizontal() {
+            unit.separation.resolve(size_available.x)
+        } else {
+            unit.separation.resolve(size_available.y)
+        }
+    }
+
+    /// Separation of wrapping flex box lines, with percentage resolved against `size_available`
+    /// along box cross axis.
+    fn flex_box_cross_separation(size_available: Vec2, unit: &FlexBox) -> Scalar {
+        if unit.direction.is_horizontal() {
+            unit.separation.resolve(size_available.y)
         } else {
-            Some(Self::layout_flex_box_no_wrap(size_available, unit))
+            unit.separation.resolve(size_available.x)
         }
     }
 
     pub fn layout_flex_box_wrapping(size_available: Vec2, unit: &FlexBox) -> LayoutNode {
+        Self::layout_flex_box_wrapping_with(&LayoutOverrides::new(), size_available, unit)
+    }
+
+    fn layout_flex_box_wrapping_with(
+        overrides: &LayoutOverrides,
+        size_available: Vec2,
+        unit: &FlexBox,
+    ) -> LayoutNode {
+        let separation = Self::flex_box_separation(size_available, unit);
+        let cross_separation = Self::flex_box_cross_separation(size_available, unit);
         let main_available = if unit.direction.is_horizontal() {
             size_available.x
         } else {
@@ -163,7 +269,7 @@ impl DefaultLayoutEngine {
                         item.layout.margin.left + item.layout.margin.right
                     };
                 if !line.is_empty() && main + local_main > main_available {
-                    main += line.len().saturating_sub(1) as Scalar * unit.separation;
+                    main += line.len().saturating_sub(1) as Scalar * separation;
                     lines.push((main, cross, grow, std::mem::take(&mut line)));
                     main = 0.0;
                     cross = 0.0;
@@ -174,7 +280,7 @@ impl DefaultLayoutEngine {
                 grow += item.layout.grow;
                 line.push((item, local_main, local_cross));
             }
-            main += line.len().saturating_sub(1) as Scalar * unit.separation;
+            main += line.len().saturating_sub(1) as Scalar * separation;
             lines.push((main, cross, grow, line));
             (lines, count)
         };
@@ -184,7 +290,9 @@ impl DefaultLayoutEngine {
         for (main, cross_available, grow, items) in lines {
             let diff = main_available - main;
             let mut new_main = 0.0;
-            let mut new_cross: Scalar = 0.0;
+            // each line is as thick as its own tallest (or widest) laid out item, margins included.
+            let mut line_cross = cross_available;
+            let mut line = Vec::with_capacity(items.len());
             for (item, local_main, local_cross) in items {
                 let child_main = if main < main_available {
                     local_main
@@ -203,14 +311,17 @@ impl DefaultLayoutEngine {
                         item.layout.margin.top + item.layout.margin.bottom
                     })
                 .max(0.0);
-                let child_cross = (local_cross
-                    - if unit.direction.is_horizontal() {
-                        item.layout.margin.top + item.layout.margin.bottom
-                    } else {
-                        item.layout.margin.left + item.layout.margin.right
-                    })
-                .max(0.0);
-                let child_cross = lerp(child_cross, cross_available, item.layout.fill);
+                let cross_margin = if unit.direction.is_horizontal() {
+                    item.layout.margin.top + item.layout.margin.bottom
+                } else {
+                    item.layout.margin.left + item.layout.margin.right
+                };
+                let child_cross = (local_cross - cross_margin).max(0.0);
+                let child_cross = lerp(
+                    child_cross,
+                    (cross_available - cross_margin).max(0.0),
+                    item.layout.fill,
+                );
                 let rect = if unit.direction.is_horizontal() {
                     Vec2 {
                         x: child_main,
@@ -222,7 +333,7 @@ impl DefaultLayoutEngine {
                         y: child_main,
                     }
                 };
-                if let Some(mut child) = Self::layout_node(rect, &item.slot) {
+                if let Some(mut child) = Self::layout_node_with(overrides, rect, &item.slot) {
                     if unit.direction.is_horizontal() {
                         if unit.direction.is_order_ascending() {
                             child.local_space.left += new_main + item.layout.margin.left;
@@ -236,14 +347,7 @@ impl DefaultLayoutEngine {
                                 size_available.x - left - new_main - item.layout.margin.right;
                         }
                         new_main += rect.x + item.layout.margin.left + item.layout.margin.right;
-                        let diff = lerp(
-                            0.0,
-                            cross_available - child.local_space.height(),
-                            item.layout.align,
-                        );
-                        child.local_space.top += cross_max + item.layout.margin.top + diff;
-                        child.local_space.bottom += cross_max + item.layout.margin.top + diff;
-                        new_cross = new_cross.max(rect.y);
+                        line_cross = line_cross.max(child.local_space.height() + cross_margin);
                     } else {
                         if unit.direction.is_order_ascending() {
                             child.local_space.top += new_main + item.layout.margin.top;
@@ -257,24 +361,37 @@ impl DefaultLayoutEngine {
                                 size_available.y - top - new_main - item.layout.margin.bottom;
                         }
                         new_main += rect.y + item.layout.margin.top + item.layout.margin.bottom;
-                        let diff = lerp(
-                            0.0,
-                            cross_available - child.local_space.width(),
-                            item.layout.align,
-                        );
-                        child.local_space.left += cross_max + item.layout.margin.left + diff;
-                        child.local_space.right += cross_max + item.layout.margin.left + diff;
-                        new_cross = new_cross.max(rect.x);
+                        line_cross = line_cross.max(child.local_space.width() + cross_margin);
                     }
-                    new_main += unit.separation;
-                    children.push(child);
+                    new_main += separation;
+                    line.push((item, cross_margin, child));
+                }
+            }
+            for (item, cross_margin, mut child) in line {
+                if unit.direction.is_horizontal() {
+                    let diff = lerp(
+                        0.0,
+                        line_cross - cross_margin - child.local_space.height(),
+                        item.layout.align,
+                    );
+                    child.local_space.top += cross_max + item.layout.margin.top + diff;
+                    child.local_space.bottom += cross_max + item.layout.margin.top + diff;
+                } else {
+                    let diff = lerp(
+                        0.0,
+                        line_cross - cross_margin - child.local_space.width(),
+                        item.layout.align,
+                    );
+                    child.local_space.left += cross_max + item.layout.margin.left + diff;
+                    child.local_space.right += cross_max + item.layout.margin.left + diff;
                 }
+                children.push(child);
             }
-            new_main = (new_main - unit.separation).max(0.0);
+            new_main = (new_main - separation).max(0.0);
             main_max = main_max.max(new_main);
-            cross_max += new_cross + unit.separation;
+            cross_max += line_cross + cross_separation;
         }
-        cross_max = (cross_max - unit.separation).max(0.0);
+        cross_max = (cross_max - cross_separation).max(0.0);
         let local_space = if unit.direction.is_horizontal() {
             Rect {
                 left: 0.0,
@@ -298,6 +415,15 @@ impl DefaultLayoutEngine {
     }
 
     pub fn layout_flex_box_no_wrap(size_available: Vec2, unit: &FlexBox) -> LayoutNode {
+        Self::layout_flex_box_no_wrap_with(&LayoutOverrides::new(), size_available, unit)
+    }
+
+    fn layout_flex_box_no_wrap_with(
+        overrides: &LayoutOverrides,
+        size_available: Vec2,
+        unit: &FlexBox,
+    ) -> LayoutNode {
+        let separation = Self::flex_box_separation(size_available, unit);
         let (main_available, cross_available) = if unit.direction.is_horizontal() {
             (size_available.x, size_available.y)
         } else {
@@ -345,7 +471,7 @@ impl DefaultLayoutEngine {
             shrink += item.layout.shrink;
             axis_sizes.push((local_main, local_cross));
         }
-        main += items.len().saturating_sub(1) as Scalar * unit.separation;
+        main += items.len().saturating_sub(1) as Scalar * separation;
         let diff = main_available - main;
         let mut new_main = 0.0;
         let mut new_cross: Scalar = 0.0;
@@ -395,7 +521,7 @@ impl DefaultLayoutEngine {
                         y: child_main,
                     }
                 };
-                if let Some(mut child) = Self::layout_node(rect, &item.slot) {
+                if let Some(mut child) = Self::layout_node_with(overrides, rect, &item.slot) {
                     if unit.direction.is_horizontal() {
                         if unit.direction.is_order_ascending() {
                             child.local_space.left += new_main + item.layout.margin.left;
@@ -439,14 +565,14 @@ impl DefaultLayoutEngine {
                         child.local_space.right += item.layout.margin.left + diff;
                         new_cross = new_cross.max(rect.x);
                     }
-                    new_main += unit.separation;
+                    new_main += separation;
                     Some(child)
                 } else {
                     None
                 }
             })
             .collect::<Vec<_>>();
-        new_main = (new_main - unit.separation).max(0.0);
+        new_main = (new_main - separation).max(0.0);
         let local_space = if unit.direction.is_horizontal() {
             Rect {
                 left: 0.0,
@@ -470,27 +596,48 @@ impl DefaultLayoutEngine {
     }
 
     pub fn layout_grid_box(size_available: Vec2, unit: &GridBox) -> Option<LayoutNode> {
+        Self::layout_grid_box_with(&LayoutOverrides::new(), size_available, unit)
+    }
+
+    fn layout_grid_box_with(
+        overrides: &LayoutOverrides,
+        size_available: Vec2,
+        unit: &GridBox,
+    ) -> Option<LayoutNode> {
         if !unit.id.is_valid() {
             return None;
         }
+        let outer_size = size_available;
+        let size_available = Self::padded_size(size_available, unit.padding);
+        let col_gap = unit.col_gap.max(0.0);
+        let row_gap = unit.row_gap.max(0.0);
         let cell_width = if unit.cols > 0 {
-            size_available.x / unit.cols as Scalar
+            (size_available.x - col_gap * (unit.cols - 1) as Scalar).max(0.0) / unit.cols as Scalar
         } else {
             0.0
         };
         let cell_height = if unit.rows > 0 {
-            size_available.y / unit.rows as Scalar
+            (size_available.y - row_gap * (unit.rows - 1) as Scalar).max(0.0) / unit.rows as Scalar
         } else {
             0.0
         };
-        let children = unit
+        // start of the track at given index, gaps are only put between tracks.
+        let col = |index: Integer| index as Scalar * (cell_width + col_gap);
+        let row = |index: Integer| index as Scalar * (cell_height + row_gap);
+        let mut children = unit
             .items
             .iter()
             .filter_map(|item| {
-                let left = item.layout.space_occupancy.left as Scalar * cell_width;
-                let right = item.layout.space_occupancy.right as Scalar * cell_width;
-                let top = item.layout.space_occupancy.top as Scalar * cell_height;
-                let bottom = item.layout.space_occupancy.bottom as Scalar * cell_height;
+                let IntRect {
+                    left,
+                    right,
+                    top,
+                    bottom,
+                } = item.layout.space_occupancy;
+                let right = col(right).max(col(left) + col_gap) - col_gap;
+                let left = col(left);
+                let bottom = row(bottom).max(row(top) + row_gap) - row_gap;
+                let top = row(top);
                 let width =
                     (right - left - item.layout.margin.left - item.layout.margin.right).max(0.0);
                 let height =
@@ -499,7 +646,7 @@ impl DefaultLayoutEngine {
                     x: width,
                     y: height,
                 };
-                if let Some(mut child) = Self::layout_node(size, &item.slot) {
+                if let Some(mut child) = Self::layout_node_with(overrides, size, &item.slot) {
                     let diff = size.x - child.local_space.width();
                     let ox = lerp(0.0, diff, item.layout.horizontal_align);
                     let diff = size.y - child.local_space.height();
@@ -514,19 +661,77 @@ impl DefaultLayoutEngine {
                 }
             })
             .collect::<Vec<_>>();
+        Self::offset_children(&mut children, unit.padding);
         Some(LayoutNode {
             id: unit.id.to_owned(),
             local_space: Rect {
                 left: 0.0,
-                right: size_available.x,
+                right: outer_size.x,
                 top: 0.0,
-                bottom: size_available.y,
+                bottom: outer_size.y,
             },
             children,
         })
     }
 
+    /// Area of content box item anchored in container content area of `size_available`.
+    pub(crate) fn content_box_item_rect(
+        size_available: Vec2,
+        layout: &ContentBoxItemLayout,
+    ) -> Rect {
+        let left = lerp(0.0, size_available.x, layout.anchors.left);
+        let left = left + layout.margin.left + layout.offset.x;
+        let right = lerp(0.0, size_available.x, layout.anchors.right);
+        let right = right - layout.margin.right + layout.offset.x;
+        let top = lerp(0.0, size_available.y, layout.anchors.top);
+        let top = top + layout.margin.top + layout.offset.y;
+        let bottom = lerp(0.0, size_available.y, layout.anchors.bottom);
+        let bottom = bottom - layout.margin.bottom + layout.offset.y;
+        Rect {
+            left,
+            right: left + (right - left).max(0.0),
+            top,
+            bottom: top + (bottom - top).max(0.0),
+        }
+    }
+
+    /// Moves laid out item into its area, aligning it there if its size differs.
+    pub(crate) fn place_content_box_item(child: &mut LayoutNode, rect: Rect, align: Vec2) {
+        let diff = child.local_space.width() - rect.width();
+        let ox = lerp(0.0, diff, align.x);
+        child.local_space.left += rect.left - ox;
+        child.local_space.right += rect.left - ox;
+        let diff = child.local_space.height() - rect.height();
+        let oy = lerp(0.0, diff, align.y);
+        child.local_space.top += rect.top - oy;
+        child.local_space.bottom += rect.top - oy;
+    }
+
+    pub(crate) fn padded_size(size: Vec2, padding: Rect) -> Vec2 {
+        Vec2 {
+            x: (size.x - padding.left - padding.right).max(0.0),
+            y: (size.y - padding.top - padding.bottom).max(0.0),
+        }
+    }
+
+    pub(crate) fn offset_children(children: &mut [LayoutNode], padding: Rect) {
+        for child in children {
+            child.local_space.left += padding.left;
+            child.local_space.right += padding.left;
+            child.local_space.top += padding.top;
+            child.local_space.bottom += padding.top;
+        }
+    }
+
     pub fn layout_size_box(size_available: Vec2, unit: &SizeBox) -> Option<LayoutNode> {
+        Self::layout_size_box_with(&LayoutOverrides::new(), size_available, unit)
+    }
+
+    fn layout_size_box_with(
+        overrides: &LayoutOverrides,
+        size_available: Vec2,
+        unit: &SizeBox,
+    ) -> Option<LayoutNode> {
         if !unit.id.is_valid() {
             return None;
         }
@@ -542,7 +747,8 @@ impl DefaultLayoutEngine {
                 SizeBoxSizeValue::Exact(v) => v,
             },
         };
-        let children = if let Some(mut child) = Self::layout_node(size, &unit.slot) {
+        let children = if let Some(mut child) = Self::layout_node_with(overrides, size, &unit.slot)
+        {
             child.local_space.left += unit.margin.left;
             child.local_space.right += unit.margin.left;
             child.local_space.top += unit.margin.top;
@@ -658,6 +864,7 @@ impl DefaultLayoutEngine {
     }
 
     fn calc_horizontal_flex_box_min_width(size_available: Vec2, unit: &FlexBox) -> Scalar {
+        let separation = Self::flex_box_separation(size_available, unit);
         if unit.wrap {
             let mut result: Scalar = 0.0;
             let mut line = 0.0;
@@ -669,7 +876,7 @@ impl DefaultLayoutEngine {
                 if first || line + size <= size_available.x {
                     line += size;
                     if !first {
-                        line += unit.separation;
+                        line += separation;
                     }
                     first = false;
                 } else {
@@ -686,11 +893,12 @@ impl DefaultLayoutEngine {
                     + item.layout.margin.left
                     + item.layout.margin.right;
             }
-            result + (unit.items.len().saturating_sub(1) as Scalar) * unit.separation
+            result + (unit.items.len().saturating_sub(1) as Scalar) * separation
         }
     }
 
     fn calc_vertical_flex_box_min_width(size_available: Vec2, unit: &FlexBox) -> Scalar {
+        let separation = Self::flex_box_separation(size_available, unit);
         if unit.wrap {
             let mut result = 0.0;
             let mut line_length = 0.0;
@@ -707,7 +915,7 @@ impl DefaultLayoutEngine {
                 if first || line_length + height <= size_available.y {
                     line_length += height;
                     if !first {
-                        line_length += unit.separation;
+                        line_length += separation;
                     }
                     line = line.max(width);
                     first = false;
@@ -721,7 +929,9 @@ impl DefaultLayoutEngine {
             }
             result += line;
             lines += 1;
-            result + (lines.saturating_sub(1) as Scalar) * unit.separation
+            result
+                + (lines.saturating_sub(1) as Scalar)
+                    * Self::flex_box_cross_separation(size_available, unit)
         } else {
             unit.items.iter().fold(0.0, |a, item| {
                 (Self::calc_unit_min_width(size_available, &item.slot)
@@ -798,6 +1008,7 @@ impl DefaultLayoutEngine {
     }
 
     fn calc_horizontal_flex_box_min_height(size_available: Vec2, unit: &FlexBox) -> Scalar {
+        let separation = Self::flex_box_separation(size_available, unit);
         if unit.wrap {
             let mut result = 0.0;
             let mut line_length = 0.0;
@@ -814,7 +1025,7 @@ impl DefaultLayoutEngine {
                 if first || line_length + width <= size_available.x {
                     line_length += width;
                     if !first {
-                        line_length += unit.separation;
+                        line_length += separation;
                     }
                     line = line.max(height);
                     first = false;
@@ -828,7 +1039,9 @@ impl DefaultLayoutEngine {
             }
             result += line;
             lines += 1;
-            result + (lines.saturating_sub(1) as Scalar) * unit.separation
+            result
+                + (lines.saturating_sub(1) as Scalar)
+                    * Self::flex_box_cross_separation(size_available, unit)
         } else {
             unit.items.iter().fold(0.0, |a, item| {
                 (Self::calc_unit_min_height(size_available, &item.slot)
@@ -840,6 +1053,7 @@ impl DefaultLayoutEngine {
     }
 
     fn calc_vertical_flex_box_min_height(size_available: Vec2, unit: &FlexBox) -> Scalar {
+        let separation = Self::flex_box_separation(size_available, unit);
         if unit.wrap {
             let mut result: Scalar = 0.0;
             let mut line = 0.0;
@@ -851,7 +1065,7 @@ impl DefaultLayoutEngine {
                 if first || line + size <= size_available.y {
                     line += size;
                     if !first {
-                        line += unit.separation;
+                        line += separation;
                     }
                     first = false;
                 } else {
@@ -868,7 +1082,7 @@ impl DefaultLayoutEngine {
                     + item.layout.margin.top
                     + item.layout.margin.bottom;
             }
-            result + (unit.items.len().saturating_sub(1) as Scalar) * unit.separation
+            result + (unit.items.len().saturating_sub(1) as Scalar) * separation
         }
     }
 
@@ -888,11 +1102,21 @@ impl DefaultLayoutEngine {
         result
     }
 
+    /// Flattens laid out tree into layout items placed in `ui_space`.
+    pub(crate) fn unpack_root(ui_space: Rect, root: LayoutNode) -> Layout {
+        let count = root.count();
+        let mut items = HashMap::with_capacity(count);
+        let mut ordered = Vec::with_capacity(count);
+        Self::unpack_node(None, ui_space, root, &mut items, &mut ordered);
+        Layout::new(ui_space, items, ordered)
+    }
+
     fn unpack_node(
         parent: Option<&WidgetId>,
         ui_space: Rect,
         node: LayoutNode,
         items: &mut HashMap<WidgetId, LayoutItem>,
+        ordered: &mut Vec<WidgetId>,
     ) {
         let LayoutNode {
             id,
@@ -905,8 +1129,9 @@ impl DefaultLayoutEngine {
             top: local_space.top + ui_space.top,
             bottom: local_space.bottom + ui_space.top,
         };
+        ordered.push(id.to_owned());
         for node in children {
-            Self::unpack_node(Some(&id), ui_space, node, items);
+            Self::unpack_node(Some(&id), ui_space, node, items, ordered);
         }
         items.insert(
             id,
@@ -923,13 +1148,57 @@ impl LayoutEngine<()> for DefaultLayoutEngine {
     fn layout(&mut self, mapping: &CoordsMapping, tree: &WidgetUnit) -> Result<Layout, ()> {
         let ui_space = mapping.virtual_area();
         if let Some(root) = Self::layout_node(ui_space.size(), tree) {
-            let mut items = HashMap::with_capacity(root.count());
-            Self::unpack_node(None, ui_space, root, &mut items);
-            Ok(Layout { ui_space, items })
+            Ok(Self::unpack_root(ui_space, root))
+        } else {
+            Ok(Layout {
+                ui_space,
+                ..Default::default()
+            })
+        }
+    }
+}
+
+/// [`DefaultLayoutEngine`] that lets chosen widgets decide their own area
+///
+/// Overrides are used only for laying out widgets, minimal sizes of flex and grid box items are
+/// still calculated from their content.
+#[derive(Default)]
+pub struct OverridingLayoutEngine {
+    pub overrides: LayoutOverrides,
+}
+
+impl OverridingLayoutEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with<F>(mut self, id: WidgetId, f: F) -> Self
+    where
+        F: Fn(Rect) -> Rect + Send + Sync + 'static,
+    {
+        self.overrides.insert(id, Box::new(f));
+        self
+    }
+}
+
+impl std::fmt::Debug for OverridingLayoutEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OverridingLayoutEngine")
+            .field("overrides", &self.overrides.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl LayoutEngine<()> for OverridingLayoutEngine {
+    fn layout(&mut self, mapping: &CoordsMapping, tree: &WidgetUnit) -> Result<Layout, ()> {
+        let ui_space = mapping.virtual_area();
+        let root = DefaultLayoutEngine::layout_node_with(&self.overrides, ui_space.size(), tree);
+        if let Some(root) = root {
+            Ok(DefaultLayoutEngine::unpack_root(ui_space, root))
         } else {
             Ok(Layout {
                 ui_space,
-                items: Default::default(),
+                ..Default::default()
             })
         }
     }
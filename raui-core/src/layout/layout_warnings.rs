@@ -0,0 +1,315 @@
+//! Warnings about degenerate layouts
+
+use crate::{
+    layout::{chained_layout_engine::LayoutPass, CoordsMapping, Layout},
+    widget::{
+        unit::WidgetUnit,
+        utils::{Rect, Vec2},
+        WidgetId,
+    },
+    LogKind, Logger, Scalar,
+};
+use std::collections::HashSet;
+
+/// Kind of [`LayoutWarning`], used to suppress whole groups of warnings.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LayoutWarningKind {
+    /// Container got NaN or negative size.
+    InvalidSize,
+    /// Container with children got zero width or height, so its children collapse.
+    ZeroSize,
+    /// Flex box items have weights, but they sum up to zero (or less), so space does not get
+    /// distributed between them.
+    ZeroTotalWeight,
+}
+
+/// Degenerate layout case found by [`LayoutWarningsPass`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutWarning {
+    InvalidSize {
+        id: WidgetId,
+        size: Vec2,
+    },
+    ZeroSize {
+        id: WidgetId,
+        size: Vec2,
+    },
+    ZeroTotalWeight {
+        id: WidgetId,
+        grow: Scalar,
+        shrink: Scalar,
+    },
+}
+
+impl LayoutWarning {
+    pub fn kind(&self) -> LayoutWarningKind {
+        match self {
+            Self::InvalidSize { .. } => LayoutWarningKind::InvalidSize,
+            Self::ZeroSize { .. } => LayoutWarningKind::ZeroSize,
+            Self::ZeroTotalWeight { .. } => LayoutWarningKind::ZeroTotalWeight,
+        }
+    }
+
+    pub fn id(&self) -> &WidgetId {
+        match self {
+            Self::InvalidSize { id, .. }
+            | Self::ZeroSize { id, .. }
+            | Self::ZeroTotalWeight { id, .. } => id,
+        }
+    }
+}
+
+impl std::fmt::Display for LayoutWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidSize { id, size } => write!(
+                f,
+                "Container `{}` has invalid size: {} x {}",
+                id.path(),
+                size.x,
+                size.y
+            ),
+            Self::ZeroSize { id, size } => write!(
+                f,
+                "Container `{}` has zero size ({} x {}), its children collapse",
+                id.path(),
+                size.x,
+                size.y
+            ),
+            Self::ZeroTotalWeight { id, grow, shrink } => write!(
+                f,
+                "Flex box `{}` items have zero total weight (grow: {}, shrink: {})",
+                id.path(),
+                grow,
+                shrink
+            ),
+        }
+    }
+}
+
+/// Layout pass that reports degenerate layouts to `logger` as warnings, instead of letting
+/// children silently collapse.
+///
+/// Checks containers that have children for NaN, negative or zero sizes, and flex boxes for
+/// weights that do not sum up to positive value. Kinds of warnings can be suppressed with
+/// [`suppress`][Self::suppress].
+///
+/// # Example
+/// ```
+/// # use raui_core::prelude::*;
+/// let mut engine = ChainedLayoutEngine::new(
+///     LayoutWarningsPass::new(PrintLogger).suppress(LayoutWarningKind::ZeroSize),
+///     DefaultLayoutEngine,
+/// );
+/// # let mut application = Application::new();
+/// # application.apply(widget! { (content_box [ (image_box) ]) });
+/// # application.process();
+/// # let mapping = CoordsMapping::new(Rect { left: 0.0, right: 100.0, top: 0.0, bottom: 100.0 });
+/// application.layout(&mapping, &mut engine).unwrap();
+/// assert!(engine.pass.warnings().is_empty());
+/// ```
+pub struct LayoutWarningsPass<L>
+where
+    L: Logger,
+{
+    pub logger: L,
+    suppressed: HashSet<LayoutWarningKind>,
+    warnings: Vec<LayoutWarning>,
+}
+
+impl<L> LayoutWarningsPass<L>
+where
+    L: Logger,
+{
+    pub fn new(logger: L) -> Self {
+        Self {
+            logger,
+            suppressed: Default::default(),
+            warnings: Default::default(),
+        }
+    }
+
+    pub fn suppress(mut self, kind: LayoutWarningKind) -> Self {
+        self.set_suppressed(kind, true);
+        self
+    }
+
+    pub fn set_suppressed(&mut self, kind: LayoutWarningKind, value: bool) {
+        if value {
+            self.suppressed.insert(kind);
+        } else {
+            self.suppressed.remove(&kind);
+        }
+    }
+
+    pub fn is_suppressed(&self, kind: LayoutWarningKind) -> bool {
+        self.suppressed.contains(&kind)
+    }
+
+    /// Warnings (not suppressed ones) found during last layout.
+    pub fn warnings(&self) -> &[LayoutWarning] {
+        &self.warnings
+    }
+
+    fn check_unit(&mut self, unit: &WidgetUnit, layout: &Layout) {
+        if let Some(data) = unit.as_data() {
+            let has_children = match unit {
+                WidgetUnit::AreaBox(b) => b.slot.is_some(),
+                WidgetUnit::SizeBox(b) => b.slot.is_some(),
+                _ => unit.is_container() && !unit.is_empty_container(),
+            };
+            if has_children {
+                if let Some(item) = layout.items.get(data.id()) {
+                    self.check_size(data.id(), item.local_space);
+                }
+            }
+            if let WidgetUnit::FlexBox(b) = unit {
+                let weighted = b
+                    .items
+                    .iter()
+                    .any(|item| item.layout.grow != 0.0 || item.layout.shrink != 0.0);
+                let grow = b.items.iter().map(|item| item.layout.grow).sum::<Scalar>();
+                let shrink = b
+                    .items
+                    .iter()
+                    .map(|item| item.layout.shrink)
+                    .sum::<Scalar>();
+                // NaN sums fail the positive check as well.
+                if weighted && !(grow > 0.0 || shrink > 0.0) {
+                    self.report(LayoutWarning::ZeroTotalWeight {
+                        id: data.id().to_owned(),
+                        grow,
+                        shrink,
+                    });
+                }
+            }
+            for child in data.get_children() {
+                self.check_unit(child, layout);
+            }
+        }
+    }
+
+    fn check_size(&mut self, id: &WidgetId, rect: Rect) {
+        let size = rect.size();
+        if size.x.is_nan() || size.y.is_nan() || size.x < 0.0 || size.y < 0.0 {
+            self.report(LayoutWarning::InvalidSize {
+                id: id.to_owned(),
+                size,
+            });
+        } else if size.x == 0.0 || size.y == 0.0 {
+            self.report(LayoutWarning::ZeroSize {
+                id: id.to_owned(),
+                size,
+            });
+        }
+    }
+
+    fn report(&mut self, warning: LayoutWarning) {
+        if !self.is_suppressed(warning.kind()) {
+            self.logger.log(LogKind::Warning, &warning.to_string());
+            self.warnings.push(warning);
+        }
+    }
+}
+
+impl<L, E> LayoutPass<E> for LayoutWarningsPass<L>
+where
+    L: Logger,
+{
+    fn post_layout(
+        &mut self,
+        _mapping: &CoordsMapping,
+        tree: &WidgetUnit,
+        layout: &mut Layout,
+    ) -> Result<(), E> {
+        self.warnings.clear();
+        self.check_unit(tree, layout);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        layout::{chained_layout_engine::ChainedLayoutEngine, default_layout_engine::*},
+        widget::{
+            component::{
+                containers::{
+                    flex_box::flex_box,
+                    size_box::{size_box, SizeBoxProps},
+                },
+                image_box::image_box,
+            },
+            unit::{flex::FlexBoxItemLayout, size::SizeBoxSizeValue},
+        },
+    };
+    use std::sync::{Arc, Mutex};
+
+    struct TestLogger(Arc<Mutex<Vec<(LogKind, String)>>>);
+
+    impl Logger for TestLogger {
+        fn log(&mut self, kind: LogKind, message: &str) {
+            self.0.lock().unwrap().push((kind, message.to_owned()));
+        }
+    }
+
+    fn run(pass: LayoutWarningsPass<TestLogger>) -> Vec<(LayoutWarningKind, String)> {
+        let size_props = SizeBoxProps {
+            width: SizeBoxSizeValue::Exact(0.0),
+            ..Default::default()
+        };
+        let grow = |grow| FlexBoxItemLayout {
+            grow,
+            shrink: 0.0,
+            ..Default::default()
+        };
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"panel"} size_box: {size_props} {
+                content = (#{"list"} flex_box [
+                    (#{"a"} image_box: {grow(1.0)})
+                    (#{"b"} image_box: {grow(-1.0)})
+                ])
+            })
+        });
+        application.process();
+        let mapping = CoordsMapping::new(Rect {
+            left: 0.0,
+            right: 100.0,
+            top: 0.0,
+            bottom: 100.0,
+        });
+        let mut engine = ChainedLayoutEngine::new(pass, DefaultLayoutEngine);
+        application.layout(&mapping, &mut engine).unwrap();
+        engine
+            .pass
+            .warnings()
+            .iter()
+            .map(|warning| (warning.kind(), warning.id().key().to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn test_layout_warnings() {
+        let logs = Arc::new(Mutex::new(vec![]));
+        let warnings = run(LayoutWarningsPass::new(TestLogger(logs.clone())));
+        assert!(warnings.contains(&(LayoutWarningKind::ZeroSize, "panel".to_owned())));
+        assert!(warnings.contains(&(LayoutWarningKind::ZeroTotalWeight, "list".to_owned())));
+        let logs = logs.lock().unwrap();
+        assert_eq!(logs.len(), warnings.len());
+        assert!(logs.iter().all(|(kind, _)| *kind == LogKind::Warning));
+        assert!(logs.iter().any(|(_, message)| message.contains("panel")));
+
+        let logs = Arc::new(Mutex::new(vec![]));
+        let warnings =
+            run(LayoutWarningsPass::new(TestLogger(logs.clone()))
+                .suppress(LayoutWarningKind::ZeroSize));
+        assert_eq!(
+            warnings,
+            vec![(LayoutWarningKind::ZeroTotalWeight, "list".to_owned())]
+        );
+        assert_eq!(logs.lock().unwrap().len(), 1);
+    }
+}
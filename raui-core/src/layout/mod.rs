@@ -1,8 +1,14 @@
 //! Layout engine
 
+pub mod chained_layout_engine;
+#[cfg(feature = "constraints")]
+pub mod constraint_layout_engine;
 pub mod default_layout_engine;
+pub mod layout_warnings;
+pub mod text_fit;
 
 use crate::{
+    layout::text_fit::TextBoxFit,
     widget::{
         unit::WidgetUnit,
         utils::{Rect, Vec2},
@@ -39,6 +45,16 @@ impl<'a> std::fmt::Debug for LayoutSortedItems<'a> {
 pub struct Layout {
     pub ui_space: Rect,
     pub items: HashMap<WidgetId, LayoutItem>,
+    /// Ids of all `items` in pre-order of the rendered tree (see [`ordered`][Self::ordered]).
+    ///
+    /// Layout engines that leave it empty get it derived from item paths by
+    /// [`Application::layout`][crate::application::Application::layout], where siblings are
+    /// ordered by their keys rather than by drawing order.
+    #[serde(default)]
+    pub ordered: Vec<WidgetId>,
+    /// Text fitted into text boxes by [`TextFitPass`][text_fit::TextFitPass]
+    #[serde(default)]
+    pub text_fits: HashMap<WidgetId, TextBoxFit>,
 }
 
 impl std::fmt::Debug for Layout {
@@ -51,6 +67,34 @@ impl std::fmt::Debug for Layout {
 }
 
 impl Layout {
+    pub fn new(
+        ui_space: Rect,
+        items: HashMap<WidgetId, LayoutItem>,
+        ordered: Vec<WidgetId>,
+    ) -> Self {
+        Self {
+            ui_space,
+            items,
+            ordered,
+            text_fits: Default::default(),
+        }
+    }
+
+    /// Ids of layout items in pre-order of the rendered tree (parents before their children,
+    /// siblings in order of drawing). Iterate it in reverse for top-most-first hit testing.
+    pub fn ordered(&self) -> &[WidgetId] {
+        &self.ordered
+    }
+
+    /// Fills [`ordered`][Self::ordered] ids from item paths if they are missing.
+    pub fn ensure_ordered(&mut self) {
+        if self.ordered.is_empty() && !self.items.is_empty() {
+            self.ordered = self.items.keys().cloned().collect();
+            // comparing parts keeps parents before their children.
+            self.ordered.sort_by(|a, b| a.parts().cmp(b.parts()));
+        }
+    }
+
     pub fn find(&self, mut path: &str) -> Option<&LayoutItem> {
         loop {
             if let Some(item) =
@@ -87,6 +131,12 @@ impl Layout {
                 .iter()
                 .map(|(k, v)| (k.to_owned(), v.virtual_to_real(mapping)))
                 .collect::<HashMap<_, _>>(),
+            ordered: self.ordered.clone(),
+            text_fits: self
+                .text_fits
+                .iter()
+                .map(|(k, v)| (k.to_owned(), v.virtual_to_real(mapping)))
+                .collect::<HashMap<_, _>>(),
         }
     }
 
@@ -98,9 +148,75 @@ impl Layout {
                 .iter()
                 .map(|(k, v)| (k.to_owned(), v.real_to_virtual(mapping)))
                 .collect::<HashMap<_, _>>(),
+            ordered: self.ordered.clone(),
+            text_fits: self
+                .text_fits
+                .iter()
+                .map(|(k, v)| (k.to_owned(), v.real_to_virtual(mapping)))
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+
+    pub fn virtual_to_physical(&self, mapping: &CoordsMapping) -> Self {
+        Self {
+            ui_space: mapping.virtual_to_physical_rect(self.ui_space, false),
+            items: self
+                .items
+                .iter()
+                .map(|(k, v)| (k.to_owned(), v.virtual_to_physical(mapping)))
+                .collect::<HashMap<_, _>>(),
+            ordered: self.ordered.clone(),
+            text_fits: self
+                .text_fits
+                .iter()
+                .map(|(k, v)| (k.to_owned(), v.virtual_to_physical(mapping)))
+                .collect::<HashMap<_, _>>(),
         }
     }
 
+    pub fn physical_to_virtual(&self, mapping: &CoordsMapping) -> Self {
+        Self {
+            ui_space: mapping.physical_to_virtual_rect(self.ui_space, false),
+            items: self
+                .items
+                .iter()
+                .map(|(k, v)| (k.to_owned(), v.physical_to_virtual(mapping)))
+                .collect::<HashMap<_, _>>(),
+            ordered: self.ordered.clone(),
+            text_fits: self
+                .text_fits
+                .iter()
+                .map(|(k, v)| (k.to_owned(), v.physical_to_virtual(mapping)))
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+
+    /// Text fit of the text box that is either `id` widget or the first one (by path) laid out
+    /// within it, together with that text box id.
+    pub fn find_text_fit(&self, id: &WidgetId) -> Option<(&WidgetId, &TextBoxFit)> {
+        self.text_fits
+            .iter()
+            .filter(|(other, _)| *other == id || id.is_ancestor_of(other))
+            .min_by(|(a, _), (b, _)| a.path().cmp(b.path()))
+    }
+
+    /// Index of caret closest to `x` position in ui space, in text of the text box found with
+    /// [`find_text_fit`][Self::find_text_fit]. Requires text carets computed by
+    /// [`TextFitPass`][text_fit::TextFitPass].
+    pub fn text_caret_at(&self, id: &WidgetId, x: Scalar) -> Option<usize> {
+        let (id, fit) = self.find_text_fit(id)?;
+        let item = self.items.get(id)?;
+        fit.caret_index(x - item.ui_space.left)
+    }
+
+    /// Position in ui space of caret at `index`, in text of the text box found with
+    /// [`find_text_fit`][Self::find_text_fit].
+    pub fn text_caret_position(&self, id: &WidgetId, index: usize) -> Option<Scalar> {
+        let (id, fit) = self.find_text_fit(id)?;
+        let item = self.items.get(id)?;
+        Some(item.ui_space.left + fit.caret_x(index)?)
+    }
+
     pub fn rect_relative_to(&self, id: &WidgetId, to: &WidgetId) -> Option<Rect> {
         let a = self.items.get(id)?;
         let b = self.items.get(to)?;
@@ -151,13 +267,29 @@ impl LayoutItem {
             parent: self.parent.to_owned(),
         }
     }
+
+    pub fn virtual_to_physical(&self, mapping: &CoordsMapping) -> Self {
+        Self {
+            local_space: mapping.virtual_to_physical_rect(self.local_space, true),
+            ui_space: mapping.virtual_to_physical_rect(self.ui_space, false),
+            parent: self.parent.to_owned(),
+        }
+    }
+
+    pub fn physical_to_virtual(&self, mapping: &CoordsMapping) -> Self {
+        Self {
+            local_space: mapping.physical_to_virtual_rect(self.local_space, true),
+            ui_space: mapping.physical_to_virtual_rect(self.ui_space, false),
+            parent: self.parent.to_owned(),
+        }
+    }
 }
 
 impl LayoutEngine<()> for () {
     fn layout(&mut self, mapping: &CoordsMapping, _: &WidgetUnit) -> Result<Layout, ()> {
         Ok(Layout {
             ui_space: mapping.virtual_area(),
-            items: Default::default(),
+            ..Default::default()
         })
     }
 }
@@ -189,6 +321,9 @@ pub struct CoordsMapping {
     real_area: Rect,
     #[serde(default)]
     virtual_area: Rect,
+    /// Ratio of physical (framebuffer) pixels to real (logical) units, e.g. 2 on HiDPI screens.
+    #[serde(default = "CoordsMapping::default_scale_factor")]
+    scale_factor: Scalar,
 }
 
 impl Default for CoordsMapping {
@@ -209,6 +344,7 @@ impl CoordsMapping {
                 top: 0.0,
                 bottom: real_area.height(),
             },
+            scale_factor: 1.0,
         }
     }
 
@@ -224,6 +360,7 @@ impl CoordsMapping {
                     top: 0.0,
                     bottom: real_area.height(),
                 },
+                scale_factor: 1.0,
             },
             CoordsMappingScaling::Stretch(size) => {
                 let vw = size.x;
@@ -250,6 +387,7 @@ impl CoordsMapping {
                         top: 0.0,
                         bottom: vh,
                     },
+                    scale_factor: 1.0,
                 }
             }
             CoordsMappingScaling::FitHorizontal(vw) => {
@@ -267,6 +405,7 @@ impl CoordsMapping {
                         top: 0.0,
                         bottom: vh,
                     },
+                    scale_factor: 1.0,
                 }
             }
             CoordsMappingScaling::FitVertical(vh) => {
@@ -284,6 +423,7 @@ impl CoordsMapping {
                         top: 0.0,
                         bottom: vh,
                     },
+                    scale_factor: 1.0,
                 }
             }
             CoordsMappingScaling::FitMinimum(size) => {
@@ -330,26 +470,133 @@ impl CoordsMapping {
                         top: 0.0,
                         bottom: vh,
                     },
+                    scale_factor: 1.0,
                 }
             }
         }
     }
 
+    /// Maps `virtual_area` into `real_area` (logical units), while `scale_factor` tells how many
+    /// physical pixels are there per single logical unit (HiDPI framebuffers).
+    pub fn new_scaled(real_area: Rect, virtual_area: Rect, scale_factor: Scalar) -> Self {
+        let scale = Vec2 {
+            x: real_area.width() / virtual_area.width(),
+            y: real_area.height() / virtual_area.height(),
+        };
+        Self {
+            scale,
+            offset: Vec2 {
+                x: -virtual_area.left * scale.x,
+                y: -virtual_area.top * scale.y,
+            },
+            real_area,
+            virtual_area,
+            scale_factor,
+        }
+    }
+
+    pub fn with_scale_factor(mut self, scale_factor: Scalar) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    fn default_scale_factor() -> Scalar {
+        1.0
+    }
+
     #[inline]
     pub fn scale(&self) -> Vec2 {
         self.scale
     }
 
+    /// Uniform scale of virtual units into real ones, used for scaling fonts. It is the smaller
+    /// one of scale axes, so scaled text never overflows its stretched box.
+    #[inline]
+    pub fn font_scale(&self) -> Scalar {
+        self.scale.x.min(self.scale.y)
+    }
+
     #[inline]
     pub fn offset(&self) -> Vec2 {
         self.offset
     }
 
+    #[inline]
+    pub fn scale_factor(&self) -> Scalar {
+        self.scale_factor
+    }
+
+    #[inline]
+    pub fn real_area(&self) -> Rect {
+        self.real_area
+    }
+
     #[inline]
     pub fn virtual_area(&self) -> Rect {
         self.virtual_area
     }
 
+    #[inline]
+    pub fn physical_area(&self) -> Rect {
+        self.real_to_physical_rect(self.real_area)
+    }
+
+    #[inline]
+    pub fn real_to_physical_vec2(&self, coord: Vec2) -> Vec2 {
+        Vec2 {
+            x: coord.x * self.scale_factor,
+            y: coord.y * self.scale_factor,
+        }
+    }
+
+    #[inline]
+    pub fn physical_to_real_vec2(&self, coord: Vec2) -> Vec2 {
+        Vec2 {
+            x: coord.x / self.scale_factor,
+            y: coord.y / self.scale_factor,
+        }
+    }
+
+    #[inline]
+    pub fn real_to_physical_rect(&self, area: Rect) -> Rect {
+        Rect {
+            left: area.left * self.scale_factor,
+            right: area.right * self.scale_factor,
+            top: area.top * self.scale_factor,
+            bottom: area.bottom * self.scale_factor,
+        }
+    }
+
+    #[inline]
+    pub fn physical_to_real_rect(&self, area: Rect) -> Rect {
+        Rect {
+            left: area.left / self.scale_factor,
+            right: area.right / self.scale_factor,
+            top: area.top / self.scale_factor,
+            bottom: area.bottom / self.scale_factor,
+        }
+    }
+
+    #[inline]
+    pub fn virtual_to_physical_vec2(&self, coord: Vec2, local_space: bool) -> Vec2 {
+        self.real_to_physical_vec2(self.virtual_to_real_vec2(coord, local_space))
+    }
+
+    #[inline]
+    pub fn physical_to_virtual_vec2(&self, coord: Vec2, local_space: bool) -> Vec2 {
+        self.real_to_virtual_vec2(self.physical_to_real_vec2(coord), local_space)
+    }
+
+    #[inline]
+    pub fn virtual_to_physical_rect(&self, area: Rect, local_space: bool) -> Rect {
+        self.real_to_physical_rect(self.virtual_to_real_rect(area, local_space))
+    }
+
+    #[inline]
+    pub fn physical_to_virtual_rect(&self, area: Rect, local_space: bool) -> Rect {
+        self.real_to_virtual_rect(self.physical_to_real_rect(area), local_space)
+    }
+
     #[inline]
     pub fn virtual_to_real_vec2(&self, coord: Vec2, local_space: bool) -> Vec2 {
         if local_space {
@@ -418,3 +665,538 @@ impl CoordsMapping {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::{
+        unit::{
+            content::{ContentBox, ContentBoxItem, ContentBoxItemLayout},
+            flex::{FlexBox, FlexBoxDirection, FlexBoxItem, FlexBoxItemLayout},
+            grid::{GridBox, GridBoxItem, GridBoxItemLayout},
+            size::{SizeBox, SizeBoxSizeValue},
+            text::{TextBox, TextBoxSizeValue},
+        },
+        utils::{IntRect, Spacing},
+    };
+    use default_layout_engine::{DefaultLayoutEngine, OverridingLayoutEngine};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_coords_mapping_scaled() {
+        let mapping =
+            CoordsMapping::new_scaled(Rect::from((800.0, 600.0)), Rect::from((400.0, 300.0)), 2.0);
+        assert_eq!(mapping.scale(), Vec2::from(2.0));
+        assert_eq!(mapping.scale_factor(), 2.0);
+        assert_eq!(mapping.physical_area(), Rect::from((1600.0, 1200.0)));
+
+        let point = Vec2::from((100.0, 50.0));
+        let physical = mapping.virtual_to_physical_vec2(point, false);
+        assert_eq!(physical, Vec2::from((400.0, 200.0)));
+        assert_eq!(mapping.physical_to_virtual_vec2(physical, false), point);
+        let real = mapping.virtual_to_real_vec2(point, false);
+        assert_eq!(real, Vec2::from((200.0, 100.0)));
+        assert_eq!(mapping.real_to_virtual_vec2(real, false), point);
+    }
+
+    #[test]
+    fn test_layout_physical_sizes() {
+        let mapping =
+            CoordsMapping::new_scaled(Rect::from((800.0, 600.0)), Rect::from((400.0, 300.0)), 2.0);
+        let id = WidgetId::from_str("size_box:/root").unwrap();
+        let tree = WidgetUnit::SizeBox(SizeBox {
+            id: id.to_owned(),
+            width: SizeBoxSizeValue::Exact(100.0),
+            height: SizeBoxSizeValue::Exact(50.0),
+            ..Default::default()
+        });
+        let layout = DefaultLayoutEngine.layout(&mapping, &tree).unwrap();
+        assert_eq!(layout.ui_space, Rect::from((400.0, 300.0)));
+        let layout = layout.virtual_to_physical(&mapping);
+        assert_eq!(layout.ui_space, Rect::from((1600.0, 1200.0)));
+        let item = layout.items.get(&id).unwrap();
+        assert_eq!(item.ui_space.size(), Vec2::from((400.0, 200.0)));
+        assert_eq!(item.local_space.size(), Vec2::from((400.0, 200.0)));
+    }
+
+    #[test]
+    fn test_content_box_item_top_center() {
+        let mapping = CoordsMapping::new(Rect::from((200.0, 100.0)));
+        let id = WidgetId::from_str("size_box:/root/item").unwrap();
+        let tree = WidgetUnit::ContentBox(ContentBox {
+            id: WidgetId::from_str("content_box:/root").unwrap(),
+            items: vec![ContentBoxItem {
+                slot: WidgetUnit::SizeBox(SizeBox {
+                    id: id.to_owned(),
+                    width: SizeBoxSizeValue::Exact(40.0),
+                    height: SizeBoxSizeValue::Exact(20.0),
+                    ..Default::default()
+                }),
+                layout: ContentBoxItemLayout {
+                    anchors: Rect {
+                        left: 0.5,
+                        right: 0.5,
+                        top: 0.0,
+                        bottom: 0.0,
+                    },
+                    align: Vec2 { x: 0.5, y: 0.0 },
+                    ..Default::default()
+                },
+            }],
+            ..Default::default()
+        });
+        let layout = DefaultLayoutEngine.layout(&mapping, &tree).unwrap();
+        let item = layout.items.get(&id).unwrap();
+        assert_eq!(
+            item.ui_space,
+            Rect {
+                left: 80.0,
+                right: 120.0,
+                top: 0.0,
+                bottom: 20.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_container_padding() {
+        let mapping = CoordsMapping::new(Rect::from((200.0, 100.0)));
+        let padding = Rect {
+            left: 10.0,
+            right: 20.0,
+            top: 5.0,
+            bottom: 15.0,
+        };
+        let size_box = |id: &WidgetId, width| {
+            WidgetUnit::SizeBox(SizeBox {
+                id: id.to_owned(),
+                width,
+                height: SizeBoxSizeValue::Fill,
+                ..Default::default()
+            })
+        };
+
+        let id = WidgetId::from_str("size_box:/root/item").unwrap();
+        let tree = WidgetUnit::ContentBox(ContentBox {
+            id: WidgetId::from_str("content_box:/root").unwrap(),
+            items: vec![ContentBoxItem {
+                slot: size_box(&id, SizeBoxSizeValue::Fill),
+                ..Default::default()
+            }],
+            padding,
+            ..Default::default()
+        });
+        let layout = DefaultLayoutEngine.layout(&mapping, &tree).unwrap();
+        assert_eq!(
+            layout.items.get(&id).unwrap().ui_space,
+            Rect {
+                left: 10.0,
+                right: 180.0,
+                top: 5.0,
+                bottom: 85.0,
+            }
+        );
+
+        let tree = WidgetUnit::FlexBox(FlexBox {
+            id: WidgetId::from_str("flex_box:/root").unwrap(),
+            items: vec![FlexBoxItem {
+                slot: size_box(&id, SizeBoxSizeValue::Exact(50.0)),
+                ..Default::default()
+            }],
+            padding,
+            ..Default::default()
+        });
+        let layout = DefaultLayoutEngine.layout(&mapping, &tree).unwrap();
+        assert_eq!(
+            layout.items.get(&id).unwrap().ui_space,
+            Rect {
+                left: 10.0,
+                right: 60.0,
+                top: 5.0,
+                bottom: 85.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_grid_box_gaps() {
+        let mapping = CoordsMapping::new(Rect::from((220.0, 110.0)));
+        let id = |path: &str| WidgetId::from_str(path).unwrap();
+        let item = |path: &str, left, right, top, bottom| GridBoxItem {
+            slot: WidgetUnit::SizeBox(SizeBox {
+                id: id(path),
+                width: SizeBoxSizeValue::Fill,
+                height: SizeBoxSizeValue::Fill,
+                ..Default::default()
+            }),
+            layout: GridBoxItemLayout {
+                space_occupancy: IntRect {
+                    left,
+                    right,
+                    top,
+                    bottom,
+                },
+                ..Default::default()
+            },
+        };
+        let tree = WidgetUnit::GridBox(GridBox {
+            id: id("grid_box:/root"),
+            items: vec![
+                item("size_box:/root/a", 0, 1, 0, 1),
+                item("size_box:/root/b", 1, 3, 0, 1),
+                item("size_box:/root/c", 2, 3, 1, 2),
+            ],
+            cols: 3,
+            rows: 2,
+            col_gap: 20.0,
+            row_gap: 10.0,
+            ..Default::default()
+        });
+        let layout = DefaultLayoutEngine.layout(&mapping, &tree).unwrap();
+        let rect = |path: &str| layout.items.get(&id(path)).unwrap().ui_space;
+
+        // cells are 60 x 50 once gaps are taken out of 220 x 110.
+        assert_eq!(
+            rect("size_box:/root/a"),
+            Rect {
+                left: 0.0,
+                right: 60.0,
+                top: 0.0,
+                bottom: 50.0,
+            }
+        );
+        // spanned cells include the gap between them, but not the one after.
+        assert_eq!(
+            rect("size_box:/root/b"),
+            Rect {
+                left: 80.0,
+                right: 220.0,
+                top: 0.0,
+                bottom: 50.0,
+            }
+        );
+        assert_eq!(
+            rect("size_box:/root/c"),
+            Rect {
+                left: 160.0,
+                right: 220.0,
+                top: 60.0,
+                bottom: 110.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_measure() {
+        let available = Vec2 { x: 200.0, y: 100.0 };
+        let text = WidgetUnit::TextBox(TextBox {
+            id: WidgetId::from_str("text_box:/root/label").unwrap(),
+            text: "hello".to_owned(),
+            width: TextBoxSizeValue::Exact(40.0),
+            height: TextBoxSizeValue::Exact(16.0),
+            ..Default::default()
+        });
+        assert_eq!(
+            DefaultLayoutEngine.measure(&text, available),
+            Vec2 { x: 40.0, y: 16.0 }
+        );
+
+        let item = |key: &str, width, height| FlexBoxItem {
+            slot: WidgetUnit::SizeBox(SizeBox {
+                id: WidgetId::from_str(&format!("size_box:/root/list/{}", key)).unwrap(),
+                width: SizeBoxSizeValue::Exact(width),
+                height: SizeBoxSizeValue::Exact(height),
+                ..Default::default()
+            }),
+            layout: FlexBoxItemLayout {
+                fill: 0.0,
+                grow: 0.0,
+                shrink: 0.0,
+                ..Default::default()
+            },
+        };
+        let list = WidgetUnit::FlexBox(FlexBox {
+            id: WidgetId::from_str("flex_box:/root/list").unwrap(),
+            items: vec![item("a", 30.0, 20.0), item("b", 50.0, 10.0)],
+            direction: FlexBoxDirection::VerticalTopToBottom,
+            separation: 5.0.into(),
+            ..Default::default()
+        });
+        assert_eq!(
+            DefaultLayoutEngine.measure(&list, available),
+            Vec2 { x: 50.0, y: 35.0 }
+        );
+        assert_eq!(
+            DefaultLayoutEngine.measure(&WidgetUnit::None, available),
+            Vec2::default()
+        );
+    }
+
+    #[test]
+    fn test_percentage_separation() {
+        let item = |key: &str| FlexBoxItem {
+            slot: WidgetUnit::SizeBox(SizeBox {
+                id: WidgetId::from_str(&format!("size_box:/root/list/{}", key)).unwrap(),
+                width: SizeBoxSizeValue::Exact(20.0),
+                height: SizeBoxSizeValue::Exact(10.0),
+                ..Default::default()
+            }),
+            layout: FlexBoxItemLayout {
+                fill: 0.0,
+                grow: 0.0,
+                shrink: 0.0,
+                ..Default::default()
+            },
+        };
+        let list = FlexBox {
+            id: WidgetId::from_str("flex_box:/root/list").unwrap(),
+            items: vec![item("a"), item("b")],
+            direction: FlexBoxDirection::HorizontalLeftToRight,
+            separation: Spacing::percentage(10.0),
+            ..Default::default()
+        };
+        let offset = |width| {
+            let node =
+                DefaultLayoutEngine::layout_flex_box(Vec2 { x: width, y: 50.0 }, &list).unwrap();
+            node.children[1].local_space.left
+        };
+        assert_eq!(offset(100.0), 30.0);
+        assert_eq!(offset(200.0), 40.0);
+
+        let list = FlexBox {
+            separation: 10.0.into(),
+            ..list
+        };
+        let node = DefaultLayoutEngine::layout_flex_box(Vec2 { x: 200.0, y: 50.0 }, &list).unwrap();
+        assert_eq!(node.children[1].local_space.left, 30.0);
+
+        // lines of wrapping box are separated by percentage of cross axis size.
+        let list = FlexBox {
+            separation: Spacing::percentage(10.0),
+            wrap: true,
+            ..list
+        };
+        let node = DefaultLayoutEngine::layout_flex_box(Vec2 { x: 30.0, y: 50.0 }, &list).unwrap();
+        assert_eq!(node.children[1].local_space.top, 15.0);
+        assert_eq!(node.local_space.height(), 25.0);
+
+        assert_eq!(Spacing::pixels(5.0).to_string(), "5");
+        assert_eq!(Spacing::percentage(2.5).to_string(), "2.5%");
+
+        assert_eq!(
+            serde_yaml::from_str::<Spacing>("10").unwrap(),
+            Spacing::pixels(10.0)
+        );
+        assert_eq!(
+            serde_yaml::from_str::<Spacing>("{ value: 5, unit: Percentage }").unwrap(),
+            Spacing::percentage(5.0)
+        );
+    }
+
+    #[test]
+    fn test_layout_ordered() {
+        let mapping = CoordsMapping::new(Rect::from((200.0, 100.0)));
+        let id = |path: &str| WidgetId::from_str(path).unwrap();
+        let size_box = |path: &str| {
+            WidgetUnit::SizeBox(SizeBox {
+                id: id(path),
+                width: SizeBoxSizeValue::Fill,
+                height: SizeBoxSizeValue::Fill,
+                ..Default::default()
+            })
+        };
+        let tree = WidgetUnit::ContentBox(ContentBox {
+            id: id("content_box:/root"),
+            items: vec![
+                ContentBoxItem {
+                    slot: WidgetUnit::FlexBox(FlexBox {
+                        id: id("flex_box:/root/list"),
+                        items: vec![
+                            FlexBoxItem {
+                                slot: size_box("size_box:/root/list/a"),
+                                ..Default::default()
+                            },
+                            FlexBoxItem {
+                                slot: size_box("size_box:/root/list/b"),
+                                ..Default::default()
+                            },
+                        ],
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                ContentBoxItem {
+                    slot: size_box("size_box:/root/c"),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        });
+        let layout = DefaultLayoutEngine.layout(&mapping, &tree).unwrap();
+        assert_eq!(
+            layout
+                .ordered()
+                .iter()
+                .map(|id| id.path())
+                .collect::<Vec<_>>(),
+            vec!["root", "root/list", "root/list/a", "root/list/b", "root/c"]
+        );
+        assert_eq!(layout.ordered().len(), layout.items.len());
+        let mapping =
+            CoordsMapping::new_scaled(Rect::from((400.0, 200.0)), Rect::from((200.0, 100.0)), 1.0);
+        assert_eq!(layout.virtual_to_real(&mapping).ordered(), layout.ordered());
+
+        let mut layout = Layout {
+            items: ["root", "root/list-x", "root/list/a", "root/list"]
+                .iter()
+                .map(|path| {
+                    (
+                        WidgetId::from_str(&format!("a:/{}", path)).unwrap(),
+                        Default::default(),
+                    )
+                })
+                .collect(),
+            ..Default::default()
+        };
+        layout.ensure_ordered();
+        assert_eq!(
+            layout
+                .ordered()
+                .iter()
+                .map(|id| id.path())
+                .collect::<Vec<_>>(),
+            vec!["root", "root/list", "root/list/a", "root/list-x"]
+        );
+    }
+
+    #[test]
+    fn test_layout_overrides() {
+        let mapping = CoordsMapping::new(Rect::from((200.0, 100.0)));
+        let id = |path: &str| WidgetId::from_str(path).unwrap();
+        let panel = id("content_box:/root/panel");
+        let child = id("size_box:/root/panel/child");
+        let tree = WidgetUnit::ContentBox(ContentBox {
+            id: id("content_box:/root"),
+            items: vec![ContentBoxItem {
+                slot: WidgetUnit::ContentBox(ContentBox {
+                    id: panel.to_owned(),
+                    items: vec![ContentBoxItem {
+                        slot: WidgetUnit::SizeBox(SizeBox {
+                            id: child.to_owned(),
+                            width: SizeBoxSizeValue::Fill,
+                            height: SizeBoxSizeValue::Fill,
+                            ..Default::default()
+                        }),
+                        layout: ContentBoxItemLayout {
+                            anchors: Rect {
+                                left: 0.5,
+                                right: 1.0,
+                                top: 0.0,
+                                bottom: 1.0,
+                            },
+                            ..Default::default()
+                        },
+                    }],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        let mut engine = OverridingLayoutEngine::new().with(panel.to_owned(), |available| {
+            assert_eq!(available, Rect::from((200.0, 100.0)));
+            Rect {
+                left: 10.0,
+                right: 60.0,
+                top: 20.0,
+                bottom: 40.0,
+            }
+        });
+        let layout = engine.layout(&mapping, &tree).unwrap();
+        assert_eq!(
+            layout.items.get(&panel).unwrap().ui_space,
+            Rect {
+                left: 10.0,
+                right: 60.0,
+                top: 20.0,
+                bottom: 40.0,
+            }
+        );
+        assert_eq!(
+            layout.items.get(&child).unwrap().ui_space,
+            Rect {
+                left: 35.0,
+                right: 60.0,
+                top: 20.0,
+                bottom: 40.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_flex_box_wrap_line_cross_sizes() {
+        let mapping = CoordsMapping::new(Rect::from((100.0, 200.0)));
+        let id = |path: &str| WidgetId::from_str(path).unwrap();
+        let item = |path: &str, height: Scalar, margin: Scalar| FlexBoxItem {
+            slot: WidgetUnit::SizeBox(SizeBox {
+                id: id(path),
+                width: SizeBoxSizeValue::Exact(40.0),
+                height: SizeBoxSizeValue::Exact(height),
+                ..Default::default()
+            }),
+            layout: FlexBoxItemLayout {
+                fill: 0.0,
+                grow: 0.0,
+                margin: Rect {
+                    left: 0.0,
+                    right: 0.0,
+                    top: margin,
+                    bottom: margin,
+                },
+                ..Default::default()
+            },
+        };
+        let tree = WidgetUnit::FlexBox(FlexBox {
+            id: id("flex_box:/root"),
+            items: vec![
+                item("size_box:/root/a", 10.0, 0.0),
+                item("size_box:/root/b", 30.0, 5.0),
+                item("size_box:/root/c", 20.0, 0.0),
+            ],
+            separation: 2.0.into(),
+            wrap: true,
+            ..Default::default()
+        });
+        let layout = DefaultLayoutEngine.layout(&mapping, &tree).unwrap();
+        let rect = |path: &str| layout.items.get(&id(path)).unwrap().ui_space;
+        // first line is as tall as its tallest item with margins (40), second one only 20.
+        assert_eq!(rect("size_box:/root/a"), Rect::from((40.0, 10.0)));
+        assert_eq!(
+            rect("size_box:/root/b"),
+            Rect {
+                left: 42.0,
+                right: 82.0,
+                top: 5.0,
+                bottom: 35.0,
+            }
+        );
+        assert_eq!(
+            rect("size_box:/root/c"),
+            Rect {
+                left: 0.0,
+                right: 40.0,
+                top: 42.0,
+                bottom: 62.0,
+            }
+        );
+        assert_eq!(
+            rect("flex_box:/root"),
+            Rect {
+                left: 0.0,
+                right: 82.0,
+                top: 0.0,
+                bottom: 62.0,
+            }
+        );
+    }
+}
@@ -0,0 +1,746 @@
+//! Fitting text into text boxes
+
+use crate::{
+    layout::{chained_layout_engine::LayoutPass, CoordsMapping, Layout},
+    widget::{
+        unit::{
+            text::{
+                TextBox, TextBoxAutoSize, TextBoxHorizontalAlign, TextBoxLineHeight,
+                TextBoxOverflow, TextBoxWrap,
+            },
+            WidgetUnit,
+        },
+        utils::Vec2,
+    },
+    Scalar,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    hash::{Hash, Hasher},
+};
+
+/// Number of binary search steps done when looking for the font size that fits.
+const FIT_ITERATIONS: usize = 16;
+
+/// Ellipsis that ends text cut to fit its box.
+pub const TEXT_ELLIPSIS: &str = "\u{2026}";
+
+/// Measures text the same way host renders it, so layout can tell whether text fits its box
+pub trait TextMeasurer {
+    /// Size of `text` rendered with font of `unit` at `font_size`, spaced according to `unit`
+    /// line height and letter spacing.
+    fn measure_text(&self, unit: &TextBox, text: &str, font_size: Scalar) -> Vec2;
+}
+
+impl<F> TextMeasurer for F
+where
+    F: Fn(&TextBox, &str, Scalar) -> Vec2,
+{
+    fn measure_text(&self, unit: &TextBox, text: &str, font_size: Scalar) -> Vec2 {
+        self(unit, text, font_size)
+    }
+}
+
+/// Measures text as if all characters had the same advance, regardless of font name
+///
+/// Useful as rough approximation of monospace fonts, or for tests.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FixedAdvanceTextMeasurer {
+    /// Character advance as fraction of font size.
+    pub advance: Scalar,
+}
+
+impl Default for FixedAdvanceTextMeasurer {
+    fn default() -> Self {
+        Self { advance: 0.5 }
+    }
+}
+
+impl TextMeasurer for FixedAdvanceTextMeasurer {
+    fn measure_text(&self, unit: &TextBox, text: &str, font_size: Scalar) -> Vec2 {
+        let mut lines = 0;
+        let mut width: Scalar = 0.0;
+        for line in text.split('\n') {
+            let count = line.chars().count();
+            let spacing = count.saturating_sub(1) as Scalar * unit.letter_spacing;
+            width = width.max(count as Scalar * font_size * self.advance + spacing);
+            lines += 1;
+        }
+        Vec2 {
+            x: width,
+            y: lines as Scalar * unit.line_height.resolve(font_size),
+        }
+    }
+}
+
+/// Breaks `text` into lines that fit within `width` according to wrap policy of `unit`
+///
+/// Explicit line breaks are always kept. Space at which line gets broken is dropped.
+pub fn wrap_text<M>(
+    unit: &TextBox,
+    text: &str,
+    font_size: Scalar,
+    width: Scalar,
+    measurer: &M,
+) -> Vec<String>
+where
+    M: TextMeasurer + ?Sized,
+{
+    let fits = |line: &str| measurer.measure_text(unit, line, font_size).x <= width;
+    // appends characters one by one, starting new line whenever next one does not fit.
+    let push_chars = |lines: &mut Vec<String>, line: &mut String, chars: &str| {
+        for c in chars.chars() {
+            line.push(c);
+            if line.chars().count() > 1 && !fits(line) {
+                line.pop();
+                lines.push(std::mem::take(line));
+                line.push(c);
+            }
+        }
+    };
+    let mut lines = vec![];
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        match unit.wrap {
+            TextBoxWrap::Anywhere => push_chars(&mut lines, &mut line, paragraph),
+            TextBoxWrap::Word | TextBoxWrap::BreakWord => {
+                for (index, word) in paragraph.split(' ').enumerate() {
+                    if index > 0 {
+                        let candidate = format!("{} {}", line, word);
+                        if fits(&candidate) {
+                            line = candidate;
+                            continue;
+                        }
+                        lines.push(std::mem::take(&mut line));
+                    }
+                    if unit.wrap == TextBoxWrap::BreakWord && !fits(word) {
+                        push_chars(&mut lines, &mut line, word);
+                    } else {
+                        line.push_str(word);
+                    }
+                }
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Font size and text chosen for text box by [`TextFitPass`], which renderers should use instead
+/// of ones stored in [`TextBox`]
+///
+/// Font size is given in the same units as text box font size, according to its
+/// [`font_size_mode`][TextBox::font_size_mode].
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextBoxFit {
+    #[serde(default)]
+    pub font_size: Scalar,
+    #[serde(default)]
+    pub text: String,
+    /// Lines of `text` wrapped to fit text box width, according to text box wrap policy.
+    #[serde(default)]
+    pub lines: Vec<String>,
+    /// Horizontal positions of caret placed before each character of `text` (and after the last
+    /// one), relative to text box area. Computed only when [`TextFitPass::carets`] is enabled.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub carets: Vec<Scalar>,
+}
+
+impl TextBoxFit {
+    /// Converts carets to real space. Font size stays in text box units, since renderers scale
+    /// it according to text box [`font_size_mode`][TextBox::font_size_mode].
+    pub fn virtual_to_real(&self, mapping: &CoordsMapping) -> Self {
+        self.map_carets(|x| mapping.virtual_to_real_vec2(Vec2 { x, y: 0.0 }, true).x)
+    }
+
+    pub fn real_to_virtual(&self, mapping: &CoordsMapping) -> Self {
+        self.map_carets(|x| mapping.real_to_virtual_vec2(Vec2 { x, y: 0.0 }, true).x)
+    }
+
+    pub fn virtual_to_physical(&self, mapping: &CoordsMapping) -> Self {
+        self.map_carets(|x| mapping.virtual_to_physical_vec2(Vec2 { x, y: 0.0 }, true).x)
+    }
+
+    pub fn physical_to_virtual(&self, mapping: &CoordsMapping) -> Self {
+        self.map_carets(|x| mapping.physical_to_virtual_vec2(Vec2 { x, y: 0.0 }, true).x)
+    }
+
+    fn map_carets<F>(&self, f: F) -> Self
+    where
+        F: FnMut(Scalar) -> Scalar,
+    {
+        Self {
+            carets: self.carets.iter().copied().map(f).collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Finds the biggest font size (between auto size minimum and text box font size) that lets
+    /// text, wrapped according to text box wrap policy, fit within `size`. When text does not fit
+    /// even at minimal size, it gets cut with [`TEXT_ELLIPSIS`] if text box overflow is set to
+    /// [`TextBoxOverflow::Ellipsis`], otherwise it is kept whole and clipped.
+    pub fn new<M>(unit: &TextBox, size: Vec2, measurer: &M) -> Self
+    where
+        M: TextMeasurer + ?Sized,
+    {
+        let (font_size, text) = Self::fit(unit, size, measurer);
+        let lines = wrap_text(unit, &text, font_size, size.x, measurer);
+        Self {
+            font_size,
+            text,
+            lines,
+            carets: vec![],
+        }
+    }
+
+    fn fit<M>(unit: &TextBox, size: Vec2, measurer: &M) -> (Scalar, String)
+    where
+        M: TextMeasurer + ?Sized,
+    {
+        let fits = |text: &str, font_size: Scalar| {
+            let lines = wrap_text(unit, text, font_size, size.x, measurer);
+            let measured = measurer.measure_text(unit, &lines.join("\n"), font_size);
+            measured.x <= size.x && measured.y <= size.y
+        };
+        let max_size = unit.font.size;
+        let min_size = match unit.auto_size {
+            Some(auto_size) => auto_size.min_size.min(max_size),
+            None => max_size,
+        };
+        if fits(&unit.text, max_size) {
+            return (max_size, unit.text.to_owned());
+        }
+        if fits(&unit.text, min_size) {
+            let mut from = min_size;
+            let mut to = max_size;
+            for _ in 0..FIT_ITERATIONS {
+                let font_size = (from + to) * 0.5;
+                if fits(&unit.text, font_size) {
+                    from = font_size;
+                } else {
+                    to = font_size;
+                }
+            }
+            return (from, unit.text.to_owned());
+        }
+        let text = match unit.overflow {
+            TextBoxOverflow::Clip => unit.text.to_owned(),
+            TextBoxOverflow::Ellipsis => {
+                let chars = unit.text.chars().collect::<Vec<_>>();
+                let cut = |count: usize| {
+                    let mut text = chars[..count].iter().collect::<String>();
+                    text.push_str(TEXT_ELLIPSIS);
+                    text
+                };
+                // longest prefix that fits together with the ellipsis.
+                let mut from = 0;
+                let mut to = chars.len();
+                while from < to {
+                    let count = (from + to).div_ceil(2);
+                    if fits(&cut(count), min_size) {
+                        from = count;
+                    } else {
+                        to = count - 1;
+                    }
+                }
+                cut(from)
+            }
+        };
+        (min_size, text)
+    }
+
+    /// Computes [`carets`][Self::carets] of fitted text laid out in box of `size`, taking text
+    /// box horizontal align into account. Caret positions are measured within their own lines.
+    pub fn measure_carets<M>(&mut self, unit: &TextBox, size: Vec2, measurer: &M)
+    where
+        M: TextMeasurer + ?Sized,
+    {
+        let factor = match unit.horizontal_align {
+            TextBoxHorizontalAlign::Left => 0.0,
+            TextBoxHorizontalAlign::Center => 0.5,
+            TextBoxHorizontalAlign::Right => 1.0,
+        };
+        let mut carets = Vec::with_capacity(self.text.chars().count() + 1);
+        for line in self.text.split('\n') {
+            let width = measurer.measure_text(unit, line, self.font_size).x;
+            let offset = (size.x - width) * factor;
+            for (index, _) in line.char_indices() {
+                carets.push(
+                    offset
+                        + measurer
+                            .measure_text(unit, &line[..index], self.font_size)
+                            .x,
+                );
+            }
+            // caret after the last character of the line.
+            carets.push(offset + width);
+        }
+        self.carets = carets;
+    }
+
+    /// Index of caret (in characters) closest to `x` position relative to text box area, or
+    /// [`None`] if carets were not computed.
+    ///
+    /// Meant for single line text, since only horizontal position is considered.
+    pub fn caret_index(&self, x: Scalar) -> Option<usize> {
+        self.carets
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| (*a - x).abs().partial_cmp(&(*b - x).abs()).unwrap())
+            .map(|(index, _)| index)
+    }
+
+    /// Horizontal position relative to text box area of caret at `index` (in characters).
+    pub fn caret_x(&self, index: usize) -> Option<Scalar> {
+        self.carets.get(index).copied()
+    }
+}
+
+/// Everything that text fit depends on.
+#[derive(Debug, Clone, PartialEq)]
+struct TextFitKey {
+    text: String,
+    font: String,
+    font_size: Scalar,
+    size: Vec2,
+    line_height: TextBoxLineHeight,
+    letter_spacing: Scalar,
+    wrap: TextBoxWrap,
+    auto_size: Option<TextBoxAutoSize>,
+    overflow: TextBoxOverflow,
+    horizontal_align: TextBoxHorizontalAlign,
+    carets: bool,
+}
+
+impl TextFitKey {
+    fn new(unit: &TextBox, size: Vec2, carets: bool) -> Self {
+        Self {
+            text: unit.text.to_owned(),
+            font: unit.font.name.to_owned(),
+            font_size: unit.font.size,
+            size,
+            line_height: unit.line_height,
+            letter_spacing: unit.letter_spacing,
+            wrap: unit.wrap,
+            auto_size: unit.auto_size,
+            overflow: unit.overflow,
+            horizontal_align: unit.horizontal_align,
+            carets,
+        }
+    }
+}
+
+impl Eq for TextFitKey {}
+
+impl Hash for TextFitKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.text.hash(state);
+        self.font.hash(state);
+        self.font_size.to_bits().hash(state);
+        self.size.x.to_bits().hash(state);
+        self.size.y.to_bits().hash(state);
+    }
+}
+
+/// Bounded cache of text fits that lets [`TextFitPass`] skip measuring text of text boxes whose
+/// text, font and area did not change since previous layouts
+///
+/// When full, least recently used fit gets evicted. Capacity of zero disables caching.
+#[derive(Debug, Clone)]
+pub struct TextFitCache {
+    capacity: usize,
+    /// With time of last use.
+    fits: HashMap<TextFitKey, (TextBoxFit, usize)>,
+    time: usize,
+}
+
+impl Default for TextFitCache {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl TextFitCache {
+    pub const DEFAULT_CAPACITY: usize = 256;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            fits: HashMap::with_capacity(capacity),
+            time: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.fits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fits.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.fits.clear();
+    }
+
+    fn get(&mut self, key: &TextFitKey) -> Option<TextBoxFit> {
+        self.time += 1;
+        let (fit, time) = self.fits.get_mut(key)?;
+        *time = self.time;
+        Some(fit.to_owned())
+    }
+
+    fn insert(&mut self, key: TextFitKey, fit: TextBoxFit) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.fits.len() >= self.capacity {
+            let oldest = self
+                .fits
+                .iter()
+                .min_by_key(|(_, (_, time))| *time)
+                .map(|(key, _)| key.to_owned());
+            if let Some(oldest) = oldest {
+                self.fits.remove(&oldest);
+            }
+        }
+        self.fits.insert(key, (fit, self.time));
+    }
+}
+
+/// Layout pass that fits text of auto sized (or ellipsized) text boxes into their laid out areas
+/// and stores results in [`Layout::text_fits`]
+///
+/// # Example
+/// ```
+/// # use raui_core::prelude::*;
+/// let mut engine = DefaultLayoutEngine::with_text_measurer(FixedAdvanceTextMeasurer::default());
+/// # let mut application = Application::new();
+/// # application.apply(widget! { (text_box) });
+/// # application.process();
+/// # let mapping = CoordsMapping::new(Rect { left: 0.0, right: 100.0, top: 0.0, bottom: 100.0 });
+/// application.layout(&mapping, &mut engine).unwrap();
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct TextFitPass<M> {
+    pub measurer: M,
+    /// Whether to compute [`TextBoxFit::carets`] for all text boxes, used for placing caret with
+    /// pointer in text inputs.
+    pub carets: bool,
+    /// Fits reused across layouts, so measurer gets called only for text boxes that changed.
+    pub cache: TextFitCache,
+}
+
+impl<M> TextFitPass<M>
+where
+    M: TextMeasurer,
+{
+    pub fn new(measurer: M) -> Self {
+        Self {
+            measurer,
+            carets: false,
+            cache: Default::default(),
+        }
+    }
+
+    pub fn carets(mut self, value: bool) -> Self {
+        self.carets = value;
+        self
+    }
+
+    pub fn cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache = TextFitCache::new(capacity);
+        self
+    }
+
+    fn fit_unit(&mut self, mapping: &CoordsMapping, unit: &WidgetUnit, layout: &mut Layout) {
+        if let WidgetUnit::TextBox(unit) = unit {
+            if self.carets
+                || unit.auto_size.is_some()
+                || unit.overflow == TextBoxOverflow::Ellipsis
+                || unit.wrap != TextBoxWrap::Word
+            {
+                if let Some(item) = layout.items.get(&unit.id) {
+                    // text gets measured in units of its font size, which differ from layout
+                    // units when font size is not scaled together with the UI.
+                    let units = unit.font_size_mode.scale(mapping) / mapping.font_scale();
+                    let size = item.local_space.size();
+                    let size = Vec2 {
+                        x: size.x / units,
+                        y: size.y / units,
+                    };
+                    let key = TextFitKey::new(unit, size, self.carets);
+                    let mut fit = match self.cache.get(&key) {
+                        Some(fit) => fit,
+                        None => {
+                            let mut fit = TextBoxFit::new(unit, size, &self.measurer);
+                            if self.carets {
+                                fit.measure_carets(unit, size, &self.measurer);
+                            }
+                            self.cache.insert(key, fit.to_owned());
+                            fit
+                        }
+                    };
+                    for caret in &mut fit.carets {
+                        *caret *= units;
+                    }
+                    layout.text_fits.insert(unit.id.to_owned(), fit);
+                }
+            }
+        } else if let Some(data) = unit.as_data() {
+            for child in data.get_children() {
+                self.fit_unit(mapping, child, layout);
+            }
+        }
+    }
+}
+
+impl<M, E> LayoutPass<E> for TextFitPass<M>
+where
+    M: TextMeasurer,
+{
+    fn post_layout(
+        &mut self,
+        mapping: &CoordsMapping,
+        tree: &WidgetUnit,
+        layout: &mut Layout,
+    ) -> Result<(), E> {
+        self.fit_unit(mapping, tree, layout);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        layout::{
+            chained_layout_engine::ChainedLayoutEngine, default_layout_engine::DefaultLayoutEngine,
+            LayoutEngine,
+        },
+        widget::{
+            unit::text::{
+                TextBoxAutoSize, TextBoxFont, TextBoxFontSizeMode, TextBoxLineHeight,
+                TextBoxSizeValue,
+            },
+            utils::Rect,
+            WidgetId,
+        },
+    };
+
+    fn text_box(min_size: Scalar, overflow: TextBoxOverflow) -> TextBox {
+        TextBox {
+            id: WidgetId::new("text_box", &["badge".to_owned()]),
+            text: "HelloWorld!".to_owned(),
+            width: TextBoxSizeValue::Exact(40.0),
+            height: TextBoxSizeValue::Exact(20.0),
+            font: TextBoxFont {
+                name: "font".to_owned(),
+                size: 20.0,
+            },
+            auto_size: Some(TextBoxAutoSize { min_size }),
+            overflow,
+            ..Default::default()
+        }
+    }
+
+    fn fit(unit: TextBox) -> TextBoxFit {
+        let mapping = CoordsMapping::new(Rect::from((100.0, 100.0)));
+        let mut engine =
+            DefaultLayoutEngine::with_text_measurer(FixedAdvanceTextMeasurer::default());
+        let layout = engine.layout(&mapping, &WidgetUnit::TextBox(unit)).unwrap();
+        layout.text_fits.values().next().unwrap().to_owned()
+    }
+
+    #[test]
+    fn test_text_fit() {
+        let unit = text_box(4.0, TextBoxOverflow::Clip);
+        let result = fit(unit.clone());
+        assert_eq!(result.text, "HelloWorld!");
+        let size =
+            FixedAdvanceTextMeasurer::default().measure_text(&unit, &result.text, result.font_size);
+        assert!(size.x <= 40.0 && size.y <= 20.0);
+        assert!(result.font_size > 7.0);
+
+        let result = fit(text_box(10.0, TextBoxOverflow::Clip));
+        assert_eq!(result.font_size, 10.0);
+        assert_eq!(result.text, "HelloWorld!");
+
+        let result = fit(text_box(10.0, TextBoxOverflow::Ellipsis));
+        assert_eq!(result.font_size, 10.0);
+        assert_eq!(result.text, format!("HelloWo{}", TEXT_ELLIPSIS));
+    }
+
+    #[test]
+    fn test_text_spacing() {
+        let measurer = FixedAdvanceTextMeasurer::default();
+        let mut unit = text_box(4.0, TextBoxOverflow::Clip);
+        let text = "Hello\nWorld";
+        let size = measurer.measure_text(&unit, text, 10.0);
+        assert_eq!(size, Vec2 { x: 25.0, y: 20.0 });
+
+        unit.line_height = TextBoxLineHeight::Relative(1.5);
+        let size = measurer.measure_text(&unit, text, 10.0);
+        assert_eq!(size, Vec2 { x: 25.0, y: 30.0 });
+
+        unit.line_height = TextBoxLineHeight::Exact(24.0);
+        unit.letter_spacing = 2.0;
+        let size = measurer.measure_text(&unit, text, 10.0);
+        assert_eq!(size, Vec2 { x: 33.0, y: 48.0 });
+
+        // taller lines no longer fit, so font gets smaller.
+        unit.line_height = TextBoxLineHeight::Relative(4.0);
+        unit.letter_spacing = 0.0;
+        let result = fit(unit);
+        assert!(result.font_size > 4.9 && result.font_size <= 5.0);
+    }
+
+    #[test]
+    fn test_text_wrap() {
+        let measurer = FixedAdvanceTextMeasurer::default();
+        let mut unit = TextBox {
+            text: "go https://example.com".to_owned(),
+            width: TextBoxSizeValue::Exact(40.0),
+            height: TextBoxSizeValue::Exact(100.0),
+            font: TextBoxFont {
+                name: "font".to_owned(),
+                size: 10.0,
+            },
+            ..Default::default()
+        };
+        let lines = wrap_text(&unit, &unit.text, 10.0, 40.0, &measurer);
+        assert_eq!(lines, vec!["go", "https://example.com"]);
+
+        unit.wrap = TextBoxWrap::BreakWord;
+        let lines = wrap_text(&unit, &unit.text, 10.0, 40.0, &measurer);
+        assert_eq!(lines, vec!["go", "https://", "example.", "com"]);
+
+        unit.wrap = TextBoxWrap::Anywhere;
+        let lines = wrap_text(&unit, &unit.text, 10.0, 40.0, &measurer);
+        assert_eq!(lines, vec!["go https", "://examp", "le.com"]);
+
+        // layout wraps text of boxes with wrap policy other than default one.
+        unit.id = WidgetId::new("text_box", &["url".to_owned()]);
+        unit.wrap = TextBoxWrap::BreakWord;
+        assert_eq!(fit(unit).lines.len(), 4);
+    }
+
+    #[test]
+    fn test_text_carets() {
+        let unit = TextBox {
+            id: WidgetId::new("text_box", &["input".to_owned(), "text".to_owned()]),
+            text: "Hello".to_owned(),
+            width: TextBoxSizeValue::Exact(100.0),
+            height: TextBoxSizeValue::Exact(20.0),
+            horizontal_align: TextBoxHorizontalAlign::Center,
+            font: TextBoxFont {
+                name: "font".to_owned(),
+                size: 10.0,
+            },
+            ..Default::default()
+        };
+        let mapping = CoordsMapping::new(Rect::from((100.0, 100.0)));
+        let mut engine = ChainedLayoutEngine::new(
+            TextFitPass::new(FixedAdvanceTextMeasurer::default()).carets(true),
+            DefaultLayoutEngine,
+        );
+        let layout = engine
+            .layout(&mapping, &WidgetUnit::TextBox(unit.clone()))
+            .unwrap();
+        let fit = layout.text_fits.get(&unit.id).unwrap();
+        assert_eq!(fit.carets, vec![37.5, 42.5, 47.5, 52.5, 57.5, 62.5]);
+        assert_eq!(fit.caret_index(0.0), Some(0));
+        assert_eq!(fit.caret_index(49.0), Some(2));
+        assert_eq!(fit.caret_index(51.0), Some(3));
+        assert_eq!(fit.caret_index(100.0), Some(5));
+
+        // carets can be found from widget that contains the text box.
+        let owner = WidgetId::new("input_field", &["input".to_owned()]);
+        assert_eq!(layout.text_caret_at(&owner, 44.0), Some(1));
+        assert_eq!(layout.text_caret_position(&owner, 4), Some(57.5));
+        let other = WidgetId::new("input_field", &["other".to_owned()]);
+        assert_eq!(layout.text_caret_at(&other, 44.0), None);
+
+        // carets are scaled along with layout items, font size is kept.
+        let mapping =
+            CoordsMapping::new_scaled(Rect::from((200.0, 100.0)), Rect::from((100.0, 100.0)), 2.0);
+        let real = layout.virtual_to_real(&mapping);
+        let fit = real.text_fits.get(&unit.id).unwrap();
+        assert_eq!(fit.carets, vec![75.0, 85.0, 95.0, 105.0, 115.0, 125.0]);
+        assert_eq!(fit.font_size, 10.0);
+        assert_eq!(real.text_caret_position(&owner, 4), Some(115.0));
+        let physical = layout.virtual_to_physical(&mapping);
+        assert_eq!(physical.text_caret_position(&owner, 4), Some(230.0));
+        assert_eq!(
+            physical.physical_to_virtual(&mapping).text_fits[&unit.id].carets,
+            layout.text_fits[&unit.id].carets
+        );
+    }
+
+    #[test]
+    fn test_text_font_size_mode() {
+        let mapping =
+            CoordsMapping::new_scaled(Rect::from((200.0, 200.0)), Rect::from((100.0, 100.0)), 1.0);
+        let mut engine =
+            DefaultLayoutEngine::with_text_measurer(FixedAdvanceTextMeasurer::default());
+        let mut fit = |unit: &TextBox| {
+            let layout = engine
+                .layout(&mapping, &WidgetUnit::TextBox(unit.clone()))
+                .unwrap();
+            layout.text_fits.values().next().unwrap().font_size
+        };
+
+        let mut unit = text_box(4.0, TextBoxOverflow::Clip);
+        unit.font_size_mode = TextBoxFontSizeMode::Scaled;
+        assert_eq!(unit.real_font_size(&mapping), 40.0);
+        // text scales together with its box, so it fits the same as without scaling.
+        let scaled = fit(&unit);
+        assert!(scaled > 7.0 && scaled < 8.0);
+
+        unit.font_size_mode = TextBoxFontSizeMode::Absolute;
+        assert_eq!(unit.real_font_size(&mapping), 20.0);
+        // box is twice as big in real units, so text in absolute units fits at bigger size.
+        assert!(fit(&unit) > 14.0);
+    }
+
+    #[test]
+    fn test_text_fit_cache() {
+        let calls = std::cell::Cell::new(0);
+        let measurer = |unit: &TextBox, text: &str, font_size: Scalar| {
+            calls.set(calls.get() + 1);
+            FixedAdvanceTextMeasurer::default().measure_text(unit, text, font_size)
+        };
+        let mapping = CoordsMapping::new(Rect::from((100.0, 100.0)));
+        let mut engine = ChainedLayoutEngine::new(
+            TextFitPass::new(&measurer).cache_capacity(1),
+            DefaultLayoutEngine,
+        );
+        let mut layout = |unit: &TextBox| {
+            let before = calls.get();
+            engine
+                .layout(&mapping, &WidgetUnit::TextBox(unit.clone()))
+                .unwrap();
+            calls.get() - before
+        };
+
+        let mut unit = text_box(4.0, TextBoxOverflow::Clip);
+        assert!(layout(&unit) > 0);
+        assert_eq!(layout(&unit), 0);
+        assert_eq!(layout(&unit), 0);
+
+        unit.text = "Hello!".to_owned();
+        assert!(layout(&unit) > 0);
+        assert_eq!(layout(&unit), 0);
+
+        // previous text got evicted from cache with capacity of one.
+        unit.text = "HelloWorld!".to_owned();
+        assert!(layout(&unit) > 0);
+        assert_eq!(layout(&unit), 0);
+    }
+}
@@ -4,6 +4,7 @@
 //! the [`widget!`] macro. Those have more documentation on how to use widgets, components, etc. in
 //! your app.
 
+pub mod accessibility;
 pub mod application;
 #[macro_use]
 pub mod messenger;
@@ -18,6 +19,8 @@ pub mod data_binding;
 pub mod interactive;
 pub mod layout;
 pub mod signals;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
 /// A floating point, scalar value define as a type alias to allow switching between 32 and 64 bit
 /// floats
@@ -113,14 +116,22 @@ impl Logger for PrintLogger {
 
 #[doc(hidden)]
 pub mod prelude {
+    #[cfg(feature = "constraints")]
+    pub use crate::layout::constraint_layout_engine::*;
+    #[cfg(feature = "testing")]
+    pub use crate::testing::*;
     pub use crate::{
+        accessibility::*,
         animator::*,
         application::*,
         data_binding::*,
         implement_message_data, implement_props_data,
         interactive::default_interactions_engine::*,
         interactive::*,
+        layout::chained_layout_engine::*,
         layout::default_layout_engine::*,
+        layout::layout_warnings::*,
+        layout::text_fit::*,
         layout::*,
         make_widget,
         messenger::*,
@@ -136,10 +147,12 @@ pub mod prelude {
             component::{
                 containers::{
                     anchor_box::*, content_box::*, context_box::*, flex_box::*, grid_box::*,
-                    hidden_box::*, horizontal_box::*, portal_box::*, scroll_box::*, size_box::*,
-                    switch_box::*, tabs_box::*, tooltip_box::*, variant_box::*, vertical_box::*,
-                    wrap_box::*,
+                    hidden_box::*, horizontal_box::*, modal_box::*, navigator_box::*,
+                    portal_box::*, scroll_box::*, size_box::*, suspense_box::*, switch_box::*,
+                    tabs_box::*, tooltip_box::*, variant_box::*, vertical_box::*, wrap_box::*,
                 },
+                error_boundary::*,
+                fragment::*,
                 image_box::*,
                 interactive::*,
                 interactive::{button::*, input_field::*, navigation::*, scroll_view::*},
@@ -1,6 +1,6 @@
 //! Widget messaging
 
-use crate::widget::WidgetId;
+use crate::widget::{WidgetId, WidgetRef};
 use std::{any::Any, sync::mpsc::Sender};
 
 pub trait MessageData: std::fmt::Debug + Send + Sync {
@@ -17,6 +17,23 @@ impl Clone for Box<dyn MessageData> {
 pub type Message = Box<dyn MessageData>;
 pub type Messages = Vec<Message>;
 
+/// Envelope of message to deliver after given number of processes, unpacked by application.
+#[derive(Debug, Clone)]
+pub(crate) struct DelayedMessage {
+    pub frames: usize,
+    pub message: Message,
+}
+
+impl MessageData for DelayedMessage {
+    fn clone_message(&self) -> Box<dyn MessageData> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 #[derive(Clone)]
 pub struct MessageSender(Sender<(WidgetId, Message)>);
 
@@ -36,6 +53,19 @@ impl MessageSender {
         self.0.send((id, message)).is_ok()
     }
 
+    /// Sends message that gets delivered `frames` processes later than it would be otherwise.
+    pub fn write_delayed<T>(&self, id: WidgetId, message: T, frames: usize) -> bool
+    where
+        T: 'static + MessageData,
+    {
+        let message = Box::new(message);
+        if frames == 0 {
+            self.write_raw(id, message)
+        } else {
+            self.write(id, DelayedMessage { frames, message })
+        }
+    }
+
     pub fn write_raw_all<I>(&self, messages: I)
     where
         I: IntoIterator<Item = (WidgetId, Message)>,
@@ -48,6 +78,7 @@ impl MessageSender {
 
 pub struct Messenger<'a> {
     sender: MessageSender,
+    /// Messages received by widget, in the order they were sent.
     pub messages: &'a [Message],
 }
 
@@ -67,6 +98,26 @@ impl<'a> Messenger<'a> {
         self.sender.write_raw(id, message)
     }
 
+    /// Sends message that gets delivered `frames` processes later than it would be otherwise,
+    /// dropped if target widget gets unmounted before that.
+    pub fn write_delayed<T>(&self, id: WidgetId, message: T, frames: usize) -> bool
+    where
+        T: 'static + MessageData,
+    {
+        self.sender.write_delayed(id, message, frames)
+    }
+
+    /// Sends message to widget that given reference resolves to, fails if it is not resolved yet.
+    pub fn write_ref<T>(&self, idref: &WidgetRef, message: T) -> bool
+    where
+        T: 'static + MessageData,
+    {
+        match idref.read() {
+            Some(id) => self.sender.write(id, message),
+            None => false,
+        }
+    }
+
     pub fn write_raw_all<I>(&self, messages: I)
     where
         I: IntoIterator<Item = (WidgetId, Message)>,
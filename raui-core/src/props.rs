@@ -1,6 +1,6 @@
 //! Widget property types
 
-use crate::{Prefab, PrefabError, PrefabValue};
+use crate::{widget::utils::Lerp, Prefab, PrefabError, PrefabValue, Scalar};
 use serde::{Deserialize, Serialize};
 use std::{
     any::{type_name, Any, TypeId},
@@ -11,11 +11,17 @@ type PropsSerializeFactory =
     Box<dyn Fn(&dyn PropsData) -> Result<PrefabValue, PrefabError> + Send + Sync>;
 type PropsDeserializeFactory =
     Box<dyn Fn(PrefabValue, &mut Props) -> Result<(), PrefabError> + Send + Sync>;
+type PropsDefaultFactory = Box<dyn Fn() -> Result<PrefabValue, PrefabError> + Send + Sync>;
+
+/// Registers props type under given name in [`PropsRegistry`], made with
+/// [`PropsRegistry::registration`] for batch registrations.
+pub type PropsRegistration = fn(&mut PropsRegistry, &str);
 
 #[derive(Default)]
 pub struct PropsRegistry {
     type_mapping: HashMap<TypeId, String>,
     factories: HashMap<String, (PropsSerializeFactory, PropsDeserializeFactory)>,
+    defaults: HashMap<String, PropsDefaultFactory>,
 }
 
 impl PropsRegistry {
@@ -37,11 +43,75 @@ impl PropsRegistry {
             Ok(())
         });
         self.factories.insert(name.to_owned(), (s, d));
+        self.defaults.remove(name);
         self.type_mapping.insert(TypeId::of::<T>(), name.to_owned());
     }
 
+    /// Same as [`register_factory`][Self::register_factory] but also makes
+    /// [`default_prefab`][Self::default_prefab] available for this type.
+    pub fn register_factory_with_default<T>(&mut self, name: &str)
+    where
+        T: 'static + Prefab + PropsData + Default,
+    {
+        self.register_factory::<T>(name);
+        self.defaults
+            .insert(name.to_owned(), Box::new(|| T::default().to_prefab()));
+    }
+
+    /// [`register_factory`][Self::register_factory] of type `T` as a value, so types can be
+    /// registered in batches.
+    pub fn registration<T>() -> PropsRegistration
+    where
+        T: 'static + Prefab + PropsData,
+    {
+        Self::register_factory::<T>
+    }
+
+    /// Same as [`registration`][Self::registration] but registers type with
+    /// [`register_factory_with_default`][Self::register_factory_with_default].
+    pub fn registration_with_default<T>() -> PropsRegistration
+    where
+        T: 'static + Prefab + PropsData + Default,
+    {
+        Self::register_factory_with_default::<T>
+    }
+
     pub fn unregister_factory(&mut self, name: &str) {
         self.factories.remove(name);
+        self.defaults.remove(name);
+    }
+
+    /// Names of all registered props types, in no particular order.
+    pub fn registered_names(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(|name| name.as_str())
+    }
+
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+
+    /// Name under which props type of `type_id` is registered.
+    pub fn type_name(&self, type_id: TypeId) -> Option<&str> {
+        self.type_mapping
+            .get(&type_id)
+            .map(|name| name.as_str())
+            .filter(|name| self.factories.contains_key(*name))
+    }
+
+    /// Registered names of all data types stored in `props`, in no particular order. Types that
+    /// are not registered are skipped.
+    pub fn props_type_names<'a>(&'a self, props: &'a Props) -> impl Iterator<Item = &'a str> {
+        props
+            .type_ids()
+            .filter_map(move |type_id| self.type_name(type_id))
+    }
+
+    /// Serialized default value of props type registered under `name`.
+    ///
+    /// Returns [`None`] if the type was not registered with
+    /// [`register_factory_with_default`][Self::register_factory_with_default].
+    pub fn default_prefab(&self, name: &str) -> Option<Result<PrefabValue, PrefabError>> {
+        self.defaults.get(name).map(|factory| factory())
     }
 
     pub fn serialize(&self, props: &Props) -> Result<PrefabValue, PrefabError> {
@@ -148,6 +218,11 @@ impl Props {
         Self(result)
     }
 
+    /// Types of all data stored in these props, in no particular order.
+    pub fn type_ids(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.0.keys().copied()
+    }
+
     pub fn has<T>(&self) -> bool
     where
         T: 'static + PropsData,
@@ -240,6 +315,15 @@ impl Props {
         self.read_cloned().unwrap_or_else(|_| f())
     }
 
+    /// Interpolates between data of type `T` stored in these props (at `factor` 0) and in `to`
+    /// props (at `factor` 1), so typed props can be tweened directly.
+    pub fn read_lerp<T>(&self, to: &Self, factor: Scalar) -> Result<T, PropsError>
+    where
+        T: 'static + PropsData + Lerp,
+    {
+        Ok(self.read::<T>()?.lerp(to.read::<T>()?, factor))
+    }
+
     pub fn write<T>(&mut self, data: T)
     where
         T: 'static + PropsData,
@@ -381,3 +465,90 @@ implement_props_data!(isize);
 implement_props_data!(usize);
 implement_props_data!(bool);
 implement_props_data!(String);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::{
+        component::{image_box::ImageBoxProps, text_box::TextBoxProps, WidgetAlpha},
+        utils::{Color, Vec2},
+    };
+
+    #[test]
+    fn test_props_read_lerp() {
+        let from = Props::new(Color {
+            r: 1.0,
+            g: 0.0,
+            b: 0.0,
+            a: 1.0,
+        });
+        let to = Props::new(Color {
+            r: 0.0,
+            g: 0.0,
+            b: 1.0,
+            a: 1.0,
+        });
+        assert_eq!(
+            from.read_lerp::<Color>(&to, 0.5).unwrap(),
+            Color {
+                r: 0.5,
+                g: 0.0,
+                b: 0.5,
+                a: 1.0,
+            }
+        );
+        assert!(from.read_lerp::<Vec2>(&to, 0.5).is_err());
+    }
+
+    #[test]
+    fn test_props_registry_introspection() {
+        let mut registry = PropsRegistry::default();
+        registry.register_factory_with_default::<TextBoxProps>("TextBoxProps");
+        registry.register_factory_with_default::<ImageBoxProps>("ImageBoxProps");
+        registry.register_factory::<WidgetAlpha>("WidgetAlpha");
+
+        let mut names = registry.registered_names().collect::<Vec<_>>();
+        names.sort_unstable();
+        assert_eq!(names, vec!["ImageBoxProps", "TextBoxProps", "WidgetAlpha"]);
+        assert!(registry.is_registered("WidgetAlpha"));
+        assert!(!registry.is_registered("SizeBoxProps"));
+
+        let prefab = registry.default_prefab("TextBoxProps").unwrap().unwrap();
+        let props = TextBoxProps::from_prefab(prefab).unwrap();
+        assert_eq!(props.text, TextBoxProps::default().text);
+        assert!(registry.default_prefab("WidgetAlpha").is_none());
+        assert!(registry.default_prefab("SizeBoxProps").is_none());
+
+        registry.unregister_factory("TextBoxProps");
+        assert!(registry.default_prefab("TextBoxProps").is_none());
+        assert_eq!(registry.registered_names().count(), 2);
+    }
+
+    #[test]
+    fn test_props_type_names() {
+        let mut registry = PropsRegistry::default();
+        registry.register_factory_with_default::<TextBoxProps>("TextBoxProps");
+        registry.register_factory_with_default::<ImageBoxProps>("ImageBoxProps");
+        registry.register_factory::<WidgetAlpha>("WidgetAlpha");
+
+        let props = Props::new(WidgetAlpha(0.5)).with(TextBoxProps::default());
+        let mut type_ids = props.type_ids().collect::<Vec<_>>();
+        type_ids.sort_unstable();
+        let mut expected = vec![TypeId::of::<WidgetAlpha>(), TypeId::of::<TextBoxProps>()];
+        expected.sort_unstable();
+        assert_eq!(type_ids, expected);
+        let mut names = registry.props_type_names(&props).collect::<Vec<_>>();
+        names.sort_unstable();
+        assert_eq!(names, vec!["TextBoxProps", "WidgetAlpha"]);
+        assert_eq!(
+            registry.type_name(TypeId::of::<ImageBoxProps>()),
+            Some("ImageBoxProps")
+        );
+
+        registry.unregister_factory("TextBoxProps");
+        assert_eq!(
+            registry.props_type_names(&props).collect::<Vec<_>>(),
+            vec!["WidgetAlpha"]
+        );
+    }
+}
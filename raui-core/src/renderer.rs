@@ -2,8 +2,12 @@
 
 use crate::{
     layout::{CoordsMapping, Layout},
-    widget::unit::WidgetUnit,
+    widget::{
+        unit::{image::ImageBoxMaterial, WidgetUnit},
+        utils::Rect,
+    },
 };
+use std::fmt::Write;
 
 pub trait Renderer<T, E> {
     fn render(
@@ -27,3 +31,127 @@ impl Renderer<WidgetUnit, ()> for RawRenderer {
         Ok(tree.clone())
     }
 }
+
+/// Renders tree into stable, human readable text meant for snapshot tests.
+///
+/// Every unit takes single line with its kind, id, laid out rectangle in real space and its key
+/// properties, indented by its depth in the tree. Children are listed in the tree order, so the
+/// same tree and layout always produce the same text. Volatile data (transforms, colors, image
+/// details) is left out.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SnapshotRenderer;
+
+impl SnapshotRenderer {
+    fn render_unit(
+        result: &mut String,
+        unit: &WidgetUnit,
+        depth: usize,
+        mapping: &CoordsMapping,
+        layout: &Layout,
+    ) {
+        let data = match unit.as_data() {
+            Some(data) => data,
+            None => return,
+        };
+        let kind = match unit {
+            WidgetUnit::None => return,
+            WidgetUnit::AreaBox(_) => "AreaBox",
+            WidgetUnit::PortalBox(_) => "PortalBox",
+            WidgetUnit::ContentBox(_) => "ContentBox",
+            WidgetUnit::FlexBox(_) => "FlexBox",
+            WidgetUnit::GridBox(_) => "GridBox",
+            WidgetUnit::SizeBox(_) => "SizeBox",
+            WidgetUnit::ImageBox(_) => "ImageBox",
+            WidgetUnit::TextBox(_) => "TextBox",
+        };
+        let _ = write!(
+            result,
+            "{:indent$}{} {}",
+            "",
+            kind,
+            data.id().as_ref(),
+            indent = depth * 2
+        );
+        match layout.items.get(data.id()) {
+            Some(item) => {
+                let Rect {
+                    left,
+                    right,
+                    top,
+                    bottom,
+                } = mapping.virtual_to_real_rect(item.ui_space, false);
+                let _ = write!(
+                    result,
+                    " [{:.2} {:.2} {:.2} {:.2}]",
+                    left, top, right, bottom
+                );
+            }
+            None => result.push_str(" [-]"),
+        }
+        let _ = match unit {
+            WidgetUnit::AreaBox(unit) => match &unit.renderer_effect {
+                Some(effect) => write!(result, " effect={:?}", effect.id),
+                None => Ok(()),
+            },
+            WidgetUnit::PortalBox(unit) => {
+                write!(
+                    result,
+                    " owner={} layer={}",
+                    unit.owner.as_ref(),
+                    unit.layer
+                )
+            }
+            WidgetUnit::ContentBox(unit) => {
+                let clipping = match (unit.clips_x(), unit.clips_y()) {
+                    (true, true) => "true",
+                    (true, false) => "x",
+                    (false, true) => "y",
+                    (false, false) => "false",
+                };
+                write!(result, " clipping={}", clipping)
+            }
+            WidgetUnit::FlexBox(unit) => write!(
+                result,
+                " direction={:?} separation={} wrap={}",
+                unit.direction, unit.separation, unit.wrap
+            ),
+            WidgetUnit::GridBox(unit) => write!(result, " cols={} rows={}", unit.cols, unit.rows),
+            WidgetUnit::SizeBox(unit) => {
+                write!(result, " width={:?} height={:?}", unit.width, unit.height)
+            }
+            WidgetUnit::ImageBox(unit) => {
+                let _ = write!(result, " width={:?} height={:?}", unit.width, unit.height);
+                match &unit.material {
+                    ImageBoxMaterial::Color(_) => write!(result, " material=color"),
+                    ImageBoxMaterial::Image(image) => write!(result, " image={:?}", image.id),
+                    ImageBoxMaterial::Procedural(procedural) => {
+                        write!(result, " procedural={:?}", procedural.id)
+                    }
+                }
+            }
+            WidgetUnit::TextBox(unit) => write!(
+                result,
+                " text={:?} font={:?} size={}",
+                unit.text, unit.font.name, unit.font.size
+            ),
+            WidgetUnit::None => Ok(()),
+        };
+        result.push('\n');
+        for child in data.get_children() {
+            Self::render_unit(result, child, depth + 1, mapping, layout);
+        }
+    }
+}
+
+impl Renderer<String, ()> for SnapshotRenderer {
+    fn render(
+        &mut self,
+        tree: &WidgetUnit,
+        mapping: &CoordsMapping,
+        layout: &Layout,
+    ) -> Result<String, ()> {
+        let mut result = String::new();
+        Self::render_unit(&mut result, tree, 0, mapping, layout);
+        Ok(result)
+    }
+}
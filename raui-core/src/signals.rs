@@ -7,15 +7,37 @@
 //! Signals may be sent using the [`SignalSender`] in the widget [change context][change_context] or
 //! [unmount context][unmount_context].
 //!
+//! Widgets which would otherwise flood the host with signals (e.g. search-as-you-type text input)
+//! can limit them with [`SignalDebounce`] and [`SignalThrottle`].
+//!
+//! Widgets that need an answer from the host send [`SignalRequest`] with
+//! [`SignalSender::request_signal`], and host replies to it with
+//! [`Application::respond_signal`][crate::application::Application::respond_signal], which sends
+//! [`SignalResponse`] message with the same correlation id back to the requesting widget.
+//!
+//! Widgets that need host to know where they are on screen (e.g. to position popup next to them)
+//! send [`LayoutSignal`] with [`SignalSender::write_with_rect`], which gets reported after layout
+//! with the sender layout rectangle attached.
+//!
 //! [change_context]: crate::widget::context::WidgetMountOrChangeContext
 //!
 //! [unmount_context]: crate::widget::context::WidgetUnmountContext
 
 use crate::{
+    animator::{AnimatedValue, Animation, AnimationMessage},
     messenger::{Message, MessageData},
-    widget::WidgetId,
+    widget::{context::WidgetMountOrChangeContext, utils::Rect, WidgetId},
+    Scalar,
+};
+use std::{
+    any::Any,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::Sender,
+    },
 };
-use std::sync::mpsc::Sender;
+
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
 
 /// A signal is a [message][crate::messenger] sent by a widget that can be read by the
 /// [`Application`][crate::application::Application]
@@ -55,6 +77,34 @@ impl SignalSender {
         self.sender.send((self.id.clone(), message)).is_ok()
     }
 
+    /// Send a [`SignalRequest`] with given payload
+    ///
+    /// Returns correlation id of the request, to match it with [`SignalResponse`] the host sends
+    /// back. Keep it in widget state, since the response arrives in one of later processes.
+    pub fn request_signal<T>(&self, payload: T) -> u64
+    where
+        T: 'static + MessageData,
+    {
+        let id = NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed);
+        self.write(SignalRequest {
+            id,
+            payload: Box::new(payload),
+        });
+        id
+    }
+
+    /// Send a message that gets reported as [`LayoutSignal`] after the next
+    /// [`layout`][crate::application::Application::layout], with layout rectangle of the sending
+    /// widget attached
+    ///
+    /// Returns `false` if the message could not successfully be sent
+    pub fn write_with_rect<T>(&self, payload: T) -> bool
+    where
+        T: 'static + MessageData,
+    {
+        self.write(PendingLayoutSignal(Box::new(payload)))
+    }
+
     /// Sends a set of raw [`Message`]s from an iterator
     pub fn write_raw_all<I>(&self, messages: I)
     where
@@ -65,3 +115,417 @@ impl SignalSender {
         }
     }
 }
+
+/// Signal that expects host to respond with [`SignalResponse`] of the same `id`
+#[derive(Debug, Clone)]
+pub struct SignalRequest {
+    /// Correlation id, unique within the process
+    pub id: u64,
+    pub payload: Message,
+}
+
+impl SignalRequest {
+    pub fn payload<T>(&self) -> Option<&T>
+    where
+        T: 'static,
+    {
+        self.payload.as_any().downcast_ref::<T>()
+    }
+}
+
+impl MessageData for SignalRequest {
+    fn clone_message(&self) -> Box<dyn MessageData> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Signal sent with [`SignalSender::write_with_rect`] that waits for layout
+#[derive(Debug, Clone)]
+pub(crate) struct PendingLayoutSignal(pub Message);
+
+impl MessageData for PendingLayoutSignal {
+    fn clone_message(&self) -> Box<dyn MessageData> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Signal sent with [`SignalSender::write_with_rect`], reported after layout
+#[derive(Debug, Clone)]
+pub struct LayoutSignal {
+    /// Layout rectangle of the sender in UI space, or [`None`] if the sender got no layout.
+    pub rect: Option<Rect>,
+    pub payload: Message,
+}
+
+impl LayoutSignal {
+    pub fn payload<T>(&self) -> Option<&T>
+    where
+        T: 'static,
+    {
+        self.payload.as_any().downcast_ref::<T>()
+    }
+}
+
+impl MessageData for LayoutSignal {
+    fn clone_message(&self) -> Box<dyn MessageData> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Message with host response to [`SignalRequest`] of the same `id`
+#[derive(Debug, Clone)]
+pub struct SignalResponse {
+    /// Correlation id of the request
+    pub id: u64,
+    pub payload: Message,
+}
+
+impl SignalResponse {
+    pub fn payload<T>(&self) -> Option<&T>
+    where
+        T: 'static,
+    {
+        self.payload.as_any().downcast_ref::<T>()
+    }
+
+    /// Finds payload of response to request with given correlation id among widget messages.
+    pub fn find<T>(messages: &[Message], id: u64) -> Option<&T>
+    where
+        T: 'static,
+    {
+        messages.iter().find_map(|msg| {
+            msg.as_any()
+                .downcast_ref::<Self>()
+                .filter(|response| response.id == id)
+                .and_then(|response| response.payload())
+        })
+    }
+}
+
+impl MessageData for SignalResponse {
+    fn clone_message(&self) -> Box<dyn MessageData> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+fn animation_message_received(context: &WidgetMountOrChangeContext, anim_id: &str) -> bool {
+    context.messenger.messages.iter().any(|msg| {
+        msg.as_any()
+            .downcast_ref::<AnimationMessage>()
+            .map(|msg| msg.0 == anim_id)
+            .unwrap_or_default()
+    })
+}
+
+fn delayed_message(anim_id: &str, delay: Scalar) -> Animation {
+    Animation::Sequence(vec![
+        Animation::Value(AnimatedValue {
+            name: anim_id.to_owned(),
+            duration: delay,
+        }),
+        Animation::Message(anim_id.to_owned()),
+    ])
+}
+
+/// Sends signal only after its source stopped changing for `delay` seconds
+///
+/// Time is measured with widget animator (using `anim_id` as both animation and value name), so it
+/// persists across process calls and follows [`Application::animations_delta_time`].
+///
+/// # Example
+///
+/// ```
+/// # use raui_core::prelude::*;
+/// fn use_search(context: &mut WidgetContext) {
+///     context.life_cycle.change(|context| {
+///         let mut text = context.state.read_cloned_or_default::<String>();
+///         let mut changed = false;
+///         for msg in context.messenger.messages {
+///             if let Some(msg) = msg.as_any().downcast_ref::<String>() {
+///                 text = msg.to_owned();
+///                 changed = true;
+///             }
+///         }
+///         if changed {
+///             let _ = context.state.write(text.to_owned());
+///         }
+///         // State writes are applied after this hook, so message is made from `text`.
+///         SignalDebounce::new("search", 0.5).write_with(&context, changed, || text);
+///     });
+/// }
+/// ```
+///
+/// [`Application::animations_delta_time`]: crate::application::Application::animations_delta_time
+#[derive(Debug, Clone)]
+pub struct SignalDebounce {
+    pub anim_id: String,
+    pub delay: Scalar,
+}
+
+impl SignalDebounce {
+    pub fn new(anim_id: impl ToString, delay: Scalar) -> Self {
+        Self {
+            anim_id: anim_id.to_string(),
+            delay,
+        }
+    }
+
+    /// Restarts quiet period when `changed`, otherwise sends message made by `f` if quiet period
+    /// has just passed.
+    ///
+    /// Returns `true` if the message was sent.
+    pub fn write_with<T, F>(
+        &self,
+        context: &WidgetMountOrChangeContext,
+        changed: bool,
+        f: F,
+    ) -> bool
+    where
+        T: 'static + MessageData,
+        F: FnOnce() -> T,
+    {
+        if changed {
+            let animation = delayed_message(&self.anim_id, self.delay);
+            let _ = context.animator.change(&self.anim_id, Some(animation));
+            false
+        } else if animation_message_received(context, &self.anim_id) {
+            context.signals.write(f())
+        } else {
+            false
+        }
+    }
+}
+
+/// Sends signal at most once every `interval` seconds
+///
+/// First change sends signal immediately, changes made during following `interval` are collapsed
+/// into single signal sent when it ends. Time is measured the same way as with [`SignalDebounce`].
+#[derive(Debug, Clone)]
+pub struct SignalThrottle {
+    pub anim_id: String,
+    pub interval: Scalar,
+}
+
+impl SignalThrottle {
+    pub fn new(anim_id: impl ToString, interval: Scalar) -> Self {
+        Self {
+            anim_id: anim_id.to_string(),
+            interval,
+        }
+    }
+
+    /// Sends message made by `f` when `changed` outside of interval or when interval with pending
+    /// changes has just passed, otherwise marks change as pending.
+    ///
+    /// Returns `true` if the message was sent.
+    pub fn write_with<T, F>(
+        &self,
+        context: &WidgetMountOrChangeContext,
+        changed: bool,
+        f: F,
+    ) -> bool
+    where
+        T: 'static + MessageData,
+        F: FnOnce() -> T,
+    {
+        let remaining = context
+            .animator
+            .value_progress(&self.anim_id, &self.anim_id)
+            .map(|progress| progress.duration - progress.time)
+            .unwrap_or_default();
+        if animation_message_received(context, &self.anim_id) || (changed && remaining <= 0.0) {
+            let animation = Animation::Value(AnimatedValue {
+                name: self.anim_id.to_owned(),
+                duration: self.interval,
+            });
+            let _ = context.animator.change(&self.anim_id, Some(animation));
+            context.signals.write(f())
+        } else {
+            if changed {
+                let animation = delayed_message(&self.anim_id, remaining);
+                let _ = context.animator.change(&self.anim_id, Some(animation));
+            }
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        widget::{component::image_box::image_box, context::WidgetContext, node::WidgetNode},
+        PropsData,
+    };
+    use serde::{Deserialize, Serialize};
+
+    fn limited(context: WidgetContext, throttle: bool) -> WidgetNode {
+        context.life_cycle.change(move |context| {
+            let mut text = context.state.read_cloned_or_default::<String>();
+            let mut changed = false;
+            for msg in context.messenger.messages {
+                if let Some(msg) = msg.as_any().downcast_ref::<String>() {
+                    text = msg.to_owned();
+                    changed = true;
+                }
+            }
+            if changed {
+                let _ = context.state.write(text.to_owned());
+            }
+            let text = || text;
+            if throttle {
+                SignalThrottle::new("limit", 0.5).write_with(&context, changed, text);
+            } else {
+                SignalDebounce::new("limit", 0.5).write_with(&context, changed, text);
+            }
+        });
+        image_box(context)
+    }
+
+    fn debounced(context: WidgetContext) -> WidgetNode {
+        limited(context, false)
+    }
+
+    fn throttled(context: WidgetContext) -> WidgetNode {
+        limited(context, true)
+    }
+
+    fn signals(application: &mut Application) -> Vec<String> {
+        application
+            .consume_signals()
+            .into_iter()
+            .filter_map(|(_, msg)| msg.as_any().downcast_ref::<String>().cloned())
+            .collect()
+    }
+
+    #[derive(PropsData, Debug, Default, Clone, Serialize, Deserialize)]
+    #[props_data(crate::props::PropsData)]
+    #[prefab(crate::Prefab)]
+    struct AskingState {
+        request: u64,
+        answer: u32,
+    }
+
+    fn asking(context: WidgetContext) -> WidgetNode {
+        context.life_cycle.mount(|context| {
+            let request = context.signals.request_signal("question".to_owned());
+            let _ = context.state.write_with(AskingState { request, answer: 0 });
+        });
+        context.life_cycle.change(|context| {
+            let AskingState { request, .. } = context.state.read_cloned_or_default();
+            if let Some(answer) = SignalResponse::find::<u32>(context.messenger.messages, request) {
+                let _ = context.state.write_with(AskingState {
+                    request,
+                    answer: *answer,
+                });
+            }
+        });
+        image_box(context)
+    }
+
+    #[test]
+    fn test_signal_request() {
+        let mut application = Application::new();
+        application.apply(widget! { (#{"app"} asking) });
+        application.process();
+        let requests = application.consume_signals();
+        assert_eq!(requests.len(), 1);
+        let request = requests[0]
+            .1
+            .as_any()
+            .downcast_ref::<SignalRequest>()
+            .unwrap();
+        assert_eq!(request.payload::<String>().unwrap(), "question");
+        let id = requests[0].0.to_owned();
+        let answer = |application: &Application| {
+            application
+                .state_read(&id)
+                .unwrap()
+                .read_cloned_or_default::<AskingState>()
+                .answer
+        };
+
+        // response to other request is not taken.
+        let stale = SignalResponse {
+            id: request.id + 1,
+            payload: Box::new(7u32),
+        };
+        application.send_message(&id, stale);
+        application.process();
+        application.process();
+        assert_eq!(answer(&application), 0);
+
+        assert!(application.respond_signal(&requests[0], 42u32));
+        assert!(!application.respond_signal(&(id.to_owned(), Box::new(())), 42u32));
+        application.process();
+        application.process();
+        assert_eq!(answer(&application), 42);
+    }
+
+    #[test]
+    fn test_signal_debounce() {
+        let mut application = Application::new();
+        application.apply(widget! { (#{"app"} debounced) });
+        application.process();
+        let id = application
+            .rendered_tree()
+            .as_data()
+            .unwrap()
+            .id()
+            .to_owned();
+        application.animations_delta_time = 0.1;
+        for text in ["s", "se", "sea", "sear", "searc", "search"] {
+            application.send_message(&id, text.to_owned());
+            application.process();
+            assert!(signals(&mut application).is_empty());
+        }
+        let mut result = vec![];
+        for _ in 0..10 {
+            application.process();
+            result.extend(signals(&mut application));
+        }
+        assert_eq!(result, vec!["search".to_owned()]);
+    }
+
+    #[test]
+    fn test_signal_throttle() {
+        let mut application = Application::new();
+        application.apply(widget! { (#{"app"} throttled) });
+        application.process();
+        let id = application
+            .rendered_tree()
+            .as_data()
+            .unwrap()
+            .id()
+            .to_owned();
+        application.animations_delta_time = 0.1;
+        let mut result = vec![];
+        for text in ["s", "se", "sea", "sear", "searc", "search"] {
+            application.send_message(&id, text.to_owned());
+            application.process();
+            result.extend(signals(&mut application));
+        }
+        for _ in 0..10 {
+            application.process();
+            result.extend(signals(&mut application));
+        }
+        assert_eq!(result, vec!["s".to_owned(), "search".to_owned()]);
+    }
+}
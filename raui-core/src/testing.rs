@@ -0,0 +1,284 @@
+//! Headless application harness for component tests
+//!
+//! [`TestHarness`] bundles an [`Application`] with [`DefaultLayoutEngine`],
+//! [`DefaultInteractionsEngine`] and a fixed [`CoordsMapping`], so tests can drive widgets with
+//! frames, clicks and ticks instead of wiring all of these manually. It is available with the
+//! `testing` feature.
+//!
+//! Every frame processes application, lays it out and then performs interactions, so messages sent
+//! by interactions get handled in the next frame. Signals sent in every frame are collected until
+//! taken, since reacting to an interaction usually takes few frames of messages passing.
+//!
+//! Core has no text measuring, text boxes get laid out by their props alone, same as with
+//! [`DefaultLayoutEngine`] used directly.
+
+use crate::{
+    application::Application,
+    interactive::default_interactions_engine::{
+        DefaultInteractionsEngine, Interaction, PointerButton,
+    },
+    layout::{default_layout_engine::DefaultLayoutEngine, CoordsMapping},
+    messenger::MessageData,
+    renderer::RawRenderer,
+    signals::Signal,
+    widget::{
+        node::WidgetNode,
+        unit::WidgetUnit,
+        utils::{Rect, Vec2},
+        WidgetId,
+    },
+    Scalar,
+};
+
+pub struct TestHarness {
+    pub application: Application,
+    pub interactions: DefaultInteractionsEngine,
+    pub mapping: CoordsMapping,
+    signals: Vec<Signal>,
+}
+
+impl TestHarness {
+    /// Creates harness with given tree applied and processed, laid out in 800 x 600 area.
+    pub fn new(tree: impl Into<WidgetNode>) -> Self {
+        Self::with_area(tree, Rect::from((800.0, 600.0)))
+    }
+
+    pub fn with_area(tree: impl Into<WidgetNode>, area: Rect) -> Self {
+        let mut application = Application::new();
+        application.apply(tree.into());
+        let mut result = Self {
+            application,
+            interactions: DefaultInteractionsEngine::new(),
+            mapping: CoordsMapping::new(area),
+            signals: Default::default(),
+        };
+        result.frame();
+        result
+    }
+
+    /// Runs single frame without interaction.
+    pub fn frame(&mut self) -> &mut Self {
+        self.interact(Interaction::None)
+    }
+
+    /// Runs given number of frames without interaction.
+    pub fn frames(&mut self, count: usize) -> &mut Self {
+        for _ in 0..count {
+            self.frame();
+        }
+        self
+    }
+
+    /// Runs single frame that performs given interaction.
+    pub fn interact(&mut self, interaction: Interaction) -> &mut Self {
+        self.application.forced_process();
+        self.application
+            .layout(&self.mapping, &mut DefaultLayoutEngine)
+            .expect("Default layout engine cannot fail");
+        self.interactions.interact(interaction);
+        self.application
+            .interact(&mut self.interactions)
+            .expect("Default interactions engine cannot fail");
+        self.signals.extend(self.application.consume_signals());
+        self
+    }
+
+    /// Runs single frame with animations advanced by `delta_time`.
+    pub fn tick(&mut self, delta_time: Scalar) -> &mut Self {
+        let prev = self.application.animations_delta_time;
+        self.application.animations_delta_time = delta_time;
+        self.frame();
+        self.application.animations_delta_time = prev;
+        self
+    }
+
+    /// Presses and releases primary pointer at the center of widget, then runs two more frames, so
+    /// button gets released and its notify receiver handles that. Returns false if widget has no
+    /// layout.
+    pub fn click(&mut self, id: &WidgetId) -> bool {
+        let position = match self.layout_of(id) {
+            Some(rect) => Vec2 {
+                x: (rect.left + rect.right) * 0.5,
+                y: (rect.top + rect.bottom) * 0.5,
+            },
+            None => return false,
+        };
+        self.interact(Interaction::PointerMove(position));
+        self.interact(Interaction::PointerDown(PointerButton::Trigger, position));
+        self.interact(Interaction::PointerUp(PointerButton::Trigger, position));
+        self.frames(2);
+        true
+    }
+
+    /// Renders current tree with [`RawRenderer`].
+    pub fn render(&self) -> WidgetUnit {
+        self.application
+            .render(&self.mapping, &mut RawRenderer)
+            .expect("Raw renderer cannot fail")
+    }
+
+    /// Stable text of the current tree and layout, see [`Application::render_snapshot`].
+    pub fn snapshot(&self) -> String {
+        self.application.render_snapshot(&self.mapping)
+    }
+
+    /// Widget area in UI space, as of the latest frame.
+    pub fn layout_of(&self, id: &WidgetId) -> Option<Rect> {
+        self.application
+            .layout_data()
+            .items
+            .get(id)
+            .map(|item| item.ui_space)
+    }
+
+    /// Finds id of laid out widget by its path.
+    pub fn find(&self, path: &str) -> Option<WidgetId> {
+        self.application
+            .layout_data()
+            .items
+            .keys()
+            .find(|id| id.path() == path)
+            .cloned()
+    }
+
+    /// Signals collected since the harness creation or since they were last taken.
+    pub fn signals(&self) -> &[Signal] {
+        &self.signals
+    }
+
+    pub fn take_signals(&mut self) -> Vec<Signal> {
+        std::mem::take(&mut self.signals)
+    }
+
+    /// Collected signals of given type, with their senders.
+    pub fn signals_of<T>(&self) -> Vec<(WidgetId, T)>
+    where
+        T: 'static + MessageData + Clone,
+    {
+        self.signals()
+            .iter()
+            .filter_map(|(id, msg)| {
+                msg.as_any()
+                    .downcast_ref::<T>()
+                    .map(|msg| (id.to_owned(), msg.to_owned()))
+            })
+            .collect()
+    }
+}
+
+/// Performs single `interaction` the way tests of interactive components do: lays application
+/// out on 100x100 area, runs interactions and processes application (forced).
+#[cfg(test)]
+pub(crate) fn run(
+    application: &mut Application,
+    interactions: &mut DefaultInteractionsEngine,
+    interaction: Interaction,
+) -> crate::interactive::default_interactions_engine::DefaultInteractionsEngineResult {
+    let mapping = CoordsMapping::new(Rect {
+        left: 0.0,
+        right: 100.0,
+        top: 0.0,
+        bottom: 100.0,
+    });
+    application
+        .layout(&mapping, &mut DefaultLayoutEngine)
+        .unwrap();
+    interactions.interact(interaction);
+    let result = application.interact(interactions).unwrap();
+    application.forced_process();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pre_hooks,
+        props::Props,
+        widget::{
+            component::{
+                containers::{
+                    content_box::nav_content_box,
+                    size_box::{size_box, SizeBoxProps},
+                },
+                interactive::{
+                    button::{button, ButtonNotifyMessage, ButtonNotifyProps},
+                    navigation::NavItemActive,
+                },
+            },
+            context::WidgetContext,
+            unit::{content::ContentBoxItemLayout, size::SizeBoxSizeValue},
+            WidgetRef,
+        },
+    };
+
+    fn use_counter(context: &mut WidgetContext) {
+        context.life_cycle.change(|context| {
+            for msg in context.messenger.messages {
+                if let Some(msg) = msg.as_any().downcast_ref::<ButtonNotifyMessage>() {
+                    if msg.trigger_stop() {
+                        context.signals.write(msg.sender.key().to_owned());
+                    }
+                }
+            }
+        });
+    }
+
+    #[pre_hooks(use_counter)]
+    fn counter(mut context: WidgetContext) -> WidgetNode {
+        nav_content_box(context)
+    }
+
+    #[test]
+    fn test_harness_click() {
+        let idref = WidgetRef::new();
+        let button_props = Props::new(NavItemActive)
+            .with(ButtonNotifyProps(idref.clone().into()))
+            .with(ContentBoxItemLayout {
+                anchors: Rect {
+                    left: 0.5,
+                    right: 1.0,
+                    top: 0.0,
+                    bottom: 0.5,
+                },
+                ..Default::default()
+            });
+        let size_props = SizeBoxProps {
+            width: SizeBoxSizeValue::Fill,
+            height: SizeBoxSizeValue::Fill,
+            ..Default::default()
+        };
+        let mut harness = TestHarness::with_area(
+            widget! {
+                (#{"app"} | {idref} counter [
+                    (#{"ok"} button: {button_props} {
+                        content = (#{"size"} size_box: {size_props})
+                    })
+                ])
+            },
+            Rect::from((100.0, 100.0)),
+        );
+        let ok = harness.find("app/app/ok").unwrap();
+        assert_eq!(
+            harness.layout_of(&ok),
+            Some(Rect {
+                left: 50.0,
+                right: 100.0,
+                top: 0.0,
+                bottom: 50.0,
+            })
+        );
+        assert!(harness.signals_of::<String>().is_empty());
+
+        assert!(harness.click(&ok));
+        let clicked = harness.signals_of::<String>();
+        assert_eq!(clicked.len(), 1);
+        assert_eq!(clicked[0].0.path(), "app");
+        assert_eq!(clicked[0].1, "ok");
+
+        harness.take_signals();
+        harness.frames(2);
+        assert!(harness.signals().is_empty());
+        assert!(!harness.click(&WidgetId::default()));
+    }
+}
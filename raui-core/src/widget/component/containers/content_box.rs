@@ -10,7 +10,7 @@ use crate::{
         context::WidgetContext,
         node::WidgetNode,
         unit::content::{ContentBoxItemLayout, ContentBoxItemNode, ContentBoxNode},
-        utils::Transform,
+        utils::{Rect, Transform},
     },
     PropsData,
 };
@@ -24,6 +24,15 @@ pub struct ContentBoxProps {
     /// Whether or not to clip the parts of items that overflow outside of the box bounds
     #[serde(default)]
     pub clipping: bool,
+    /// Whether or not to clip only the parts of items that overflow past left and right edges
+    #[serde(default)]
+    pub clip_x: bool,
+    /// Whether or not to clip only the parts of items that overflow past top and bottom edges
+    #[serde(default)]
+    pub clip_y: bool,
+    /// Inset of the box content area, applied to all items on top of their own margins
+    #[serde(default)]
+    pub padding: Rect,
     /// The transform to apply to the box and it's contents
     #[serde(default)]
     pub transform: Transform,
@@ -74,6 +83,9 @@ pub fn content_box(context: WidgetContext) -> WidgetNode {
 
     let ContentBoxProps {
         clipping,
+        clip_x,
+        clip_y,
+        padding,
         transform,
     } = props.read_cloned_or_default();
 
@@ -95,6 +107,9 @@ pub fn content_box(context: WidgetContext) -> WidgetNode {
             props: props.clone(),
             items,
             clipping,
+            clip_x,
+            clip_y,
+            padding,
             transform,
         }
     }}}
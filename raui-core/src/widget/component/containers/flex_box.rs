@@ -1,5 +1,5 @@
 use crate::{
-    pre_hooks, widget,
+    pre_hooks, unpack_named_slots, widget,
     widget::{
         component::interactive::navigation::{
             use_nav_container_active, use_nav_item, use_nav_jump, NavContainerActive,
@@ -8,9 +8,9 @@ use crate::{
         context::WidgetContext,
         node::WidgetNode,
         unit::flex::{FlexBoxDirection, FlexBoxItemLayout, FlexBoxItemNode, FlexBoxNode},
-        utils::Transform,
+        utils::{Rect, Spacing, Transform},
     },
-    PropsData, Scalar,
+    PropsData,
 };
 use serde::{Deserialize, Serialize};
 
@@ -21,9 +21,12 @@ pub struct FlexBoxProps {
     #[serde(default)]
     pub direction: FlexBoxDirection,
     #[serde(default)]
-    pub separation: Scalar,
+    pub separation: Spacing,
     #[serde(default)]
     pub wrap: bool,
+    /// Inset of the box content area, applied to all items on top of their own margins
+    #[serde(default)]
+    pub padding: Rect,
     #[serde(default)]
     pub transform: Transform,
 }
@@ -33,9 +36,11 @@ pub fn nav_flex_box(mut context: WidgetContext) -> WidgetNode {
     let WidgetContext {
         key,
         props,
+        named_slots,
         listed_slots,
         ..
     } = context;
+    unpack_named_slots!(named_slots => separator);
 
     let props = props
         .clone()
@@ -44,26 +49,61 @@ pub fn nav_flex_box(mut context: WidgetContext) -> WidgetNode {
         .without::<NavItemActive>();
 
     widget! {
-        (#{key} flex_box: {props} |[listed_slots]|)
+        (#{key} flex_box: {props} {
+            separator = {separator}
+        } |[listed_slots]|)
+    }
+}
+
+/// Puts clones of `separator` between `items`, never before the first or after the last one.
+///
+/// Separator components get keyed with their index (`"{key}-{index}"`, where `key` is separator
+/// own key or `"separator"`), so they keep their ids when items change.
+pub fn separate_items(items: Vec<WidgetNode>, separator: &WidgetNode) -> Vec<WidgetNode> {
+    let count = items.len();
+    let mut result = Vec::with_capacity(count * 2);
+    for (index, item) in items.into_iter().enumerate() {
+        if index > 0 {
+            let mut separator = separator.clone();
+            if let WidgetNode::Component(component) = &mut separator {
+                let key = component.key.as_deref().unwrap_or("separator");
+                component.key = Some(format!("{}-{}", key, index - 1));
+            }
+            result.push(separator);
+        }
+        result.push(item);
     }
+    result
 }
 
+/// Lays out listed slots along its direction, with clones of optional `separator` named slot put
+/// between them (see [`separate_items`]).
 pub fn flex_box(context: WidgetContext) -> WidgetNode {
     let WidgetContext {
         id,
         props,
+        named_slots,
         listed_slots,
         ..
     } = context;
+    unpack_named_slots!(named_slots => separator);
 
     let FlexBoxProps {
         direction,
         separation,
         wrap,
+        padding,
         transform,
     } = props.read_cloned_or_default();
 
-    let items = listed_slots
+    let mut slots = listed_slots
+        .into_iter()
+        .filter(|slot| slot.props().is_some())
+        .collect::<Vec<_>>();
+    if separator.is_some() {
+        slots = separate_items(slots, &separator);
+    }
+    let items = slots
         .into_iter()
         .filter_map(|slot| {
             if let Some(props) = slot.props() {
@@ -83,7 +123,72 @@ pub fn flex_box(context: WidgetContext) -> WidgetNode {
             direction,
             separation,
             wrap,
+            padding,
             transform,
         }
     }}}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        widget::{
+            component::{containers::vertical_box::vertical_box, image_box::image_box},
+            unit::WidgetUnit,
+        },
+    };
+
+    fn keys(application: &Application) -> Vec<String> {
+        match application.rendered_tree() {
+            WidgetUnit::FlexBox(unit) => unit
+                .items
+                .iter()
+                .filter_map(|item| item.slot.as_data())
+                .map(|data| data.id().key().to_owned())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_separators() {
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"list"} vertical_box {
+                separator = (image_box)
+            } [
+                (#{"a"} image_box)
+                (#{"b"} image_box)
+                (#{"c"} image_box)
+            ])
+        });
+        application.forced_process();
+        assert_eq!(
+            keys(&application),
+            vec!["a", "separator-0", "b", "separator-1", "c"]
+        );
+
+        application.apply(widget! {
+            (#{"list"} vertical_box {
+                separator = (#{"line"} image_box)
+            } [
+                (#{"a"} image_box)
+                (#{"b"} image_box)
+            ])
+        });
+        application.forced_process();
+        assert_eq!(keys(&application), vec!["a", "line-0", "b"]);
+
+        application.apply(widget! {
+            (#{"list"} vertical_box {
+                separator = (image_box)
+            } [
+                (#{"a"} image_box)
+            ])
+        });
+        application.forced_process();
+        assert_eq!(keys(&application), vec!["a"]);
+    }
+}
@@ -8,11 +8,12 @@ use crate::{
         context::WidgetContext,
         node::WidgetNode,
         unit::grid::{GridBoxItemLayout, GridBoxItemNode, GridBoxNode},
-        utils::Transform,
+        utils::{IntRect, Rect, Transform},
     },
-    PropsData,
+    Integer, PropsData, Scalar,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 #[derive(PropsData, Debug, Default, Clone, Serialize, Deserialize)]
 #[props_data(crate::props::PropsData)]
@@ -22,6 +23,20 @@ pub struct GridBoxProps {
     pub cols: usize,
     #[serde(default)]
     pub rows: usize,
+    /// Gap between columns, not applied at the outer edges
+    #[serde(default)]
+    pub col_gap: Scalar,
+    /// Gap between rows, not applied at the outer edges
+    #[serde(default)]
+    pub row_gap: Scalar,
+    /// Inset of the box content area, applied to all items on top of their own margins
+    #[serde(default)]
+    pub padding: Rect,
+    /// Places items without explicit space occupancy into next free cells, left-to-right and
+    /// top-to-bottom, skipping cells occupied by explicitly placed items. Rows are added when
+    /// auto placed items do not fit.
+    #[serde(default)]
+    pub auto_flow: bool,
     #[serde(default)]
     pub transform: Transform,
 }
@@ -46,6 +61,40 @@ pub fn nav_grid_box(mut context: WidgetContext) -> WidgetNode {
     }
 }
 
+/// Assigns cells to items with empty space occupancy, returns number of rows needed to fit them.
+fn auto_place_items(items: &mut [GridBoxItemNode], cols: usize, rows: usize) -> usize {
+    let is_placed = |item: &GridBoxItemNode| {
+        let space = item.layout.space_occupancy;
+        space.width() > 0 && space.height() > 0
+    };
+    let mut occupied = HashSet::new();
+    for item in items.iter().filter(|item| is_placed(item)) {
+        let space = item.layout.space_occupancy;
+        for row in space.top.max(0)..space.bottom {
+            for col in space.left.max(0)..space.right {
+                occupied.insert((col, row));
+            }
+        }
+    }
+    let mut rows = rows;
+    let mut cell = 0;
+    for item in items.iter_mut().filter(|item| !is_placed(item)) {
+        while occupied.contains(&((cell % cols) as Integer, (cell / cols) as Integer)) {
+            cell += 1;
+        }
+        let (col, row) = ((cell % cols) as Integer, (cell / cols) as Integer);
+        item.layout.space_occupancy = IntRect {
+            left: col,
+            right: col + 1,
+            top: row,
+            bottom: row + 1,
+        };
+        rows = rows.max(cell / cols + 1);
+        cell += 1;
+    }
+    rows
+}
+
 pub fn grid_box(context: WidgetContext) -> WidgetNode {
     let WidgetContext {
         id,
@@ -56,11 +105,15 @@ pub fn grid_box(context: WidgetContext) -> WidgetNode {
 
     let GridBoxProps {
         cols,
-        rows,
+        mut rows,
+        col_gap,
+        row_gap,
+        padding,
+        auto_flow,
         transform,
     } = props.read_cloned_or_default();
 
-    let items = listed_slots
+    let mut items = listed_slots
         .into_iter()
         .filter_map(|slot| {
             if let Some(props) = slot.props() {
@@ -71,6 +124,9 @@ pub fn grid_box(context: WidgetContext) -> WidgetNode {
             }
         })
         .collect::<Vec<_>>();
+    if auto_flow && cols > 0 {
+        rows = auto_place_items(&mut items, cols, rows);
+    }
 
     widget! {{{
         GridBoxNode {
@@ -79,7 +135,102 @@ pub fn grid_box(context: WidgetContext) -> WidgetNode {
             items,
             cols,
             rows,
+            col_gap,
+            row_gap,
+            padding,
             transform,
         }
     }}}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        layout::{default_layout_engine::DefaultLayoutEngine, CoordsMapping},
+        props::Props,
+        widget::{component::image_box::image_box, unit::WidgetUnit, WidgetId},
+    };
+
+    #[test]
+    fn test_grid_auto_flow() {
+        let mut application = Application::new();
+        let props = GridBoxProps {
+            cols: 3,
+            rows: 1,
+            auto_flow: true,
+            ..Default::default()
+        };
+        let wide = Props::new(GridBoxItemLayout {
+            space_occupancy: IntRect {
+                left: 1,
+                right: 3,
+                top: 0,
+                bottom: 1,
+            },
+            ..Default::default()
+        });
+        application.apply(widget! {
+            (#{"grid"} grid_box: {props} [
+                (#{"a"} image_box)
+                (#{"wide"} image_box: {wide})
+                (#{"b"} image_box)
+                (#{"c"} image_box)
+            ])
+        });
+        application.forced_process();
+        let cells = match application.rendered_tree() {
+            WidgetUnit::GridBox(unit) => {
+                assert_eq!(unit.rows, 2);
+                unit.items
+                    .iter()
+                    .map(|item| {
+                        let space = item.layout.space_occupancy;
+                        (
+                            item.slot.as_data().unwrap().id().key().to_owned(),
+                            (space.left, space.top, space.right, space.bottom),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            }
+            _ => vec![],
+        };
+        assert_eq!(
+            cells,
+            vec![
+                ("a".to_owned(), (0, 0, 1, 1)),
+                ("wide".to_owned(), (1, 0, 3, 1)),
+                ("b".to_owned(), (0, 1, 1, 2)),
+                ("c".to_owned(), (1, 1, 2, 2)),
+            ]
+        );
+
+        let mapping = CoordsMapping::new(Rect::from((300.0, 200.0)));
+        application
+            .layout(&mapping, &mut DefaultLayoutEngine)
+            .unwrap();
+        let rect = |key: &str| {
+            let id = WidgetId::new("image_box", &["grid".to_owned(), key.to_owned()]);
+            application.layout_data().items.get(&id).unwrap().ui_space
+        };
+        assert_eq!(
+            rect("wide"),
+            Rect {
+                left: 100.0,
+                right: 300.0,
+                top: 0.0,
+                bottom: 100.0,
+            }
+        );
+        assert_eq!(
+            rect("c"),
+            Rect {
+                left: 100.0,
+                right: 200.0,
+                top: 100.0,
+                bottom: 200.0,
+            }
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    pre_hooks, widget,
+    pre_hooks, unpack_named_slots, widget,
     widget::{
         component::{
             containers::flex_box::{flex_box, FlexBoxProps},
@@ -11,9 +11,9 @@ use crate::{
         context::WidgetContext,
         node::WidgetNode,
         unit::flex::FlexBoxDirection,
-        utils::Transform,
+        utils::{Rect, Spacing, Transform},
     },
-    PropsData, Scalar,
+    PropsData,
 };
 use serde::{Deserialize, Serialize};
 
@@ -22,9 +22,12 @@ use serde::{Deserialize, Serialize};
 #[prefab(crate::Prefab)]
 pub struct HorizontalBoxProps {
     #[serde(default)]
-    pub separation: Scalar,
+    pub separation: Spacing,
     #[serde(default)]
     pub reversed: bool,
+    /// Inset of the box content area, applied to all items on top of their own margins
+    #[serde(default)]
+    pub padding: Rect,
     #[serde(default)]
     pub transform: Transform,
 }
@@ -38,9 +41,11 @@ pub fn nav_horizontal_box(mut context: WidgetContext) -> WidgetNode {
     let WidgetContext {
         key,
         props,
+        named_slots,
         listed_slots,
         ..
     } = context;
+    unpack_named_slots!(named_slots => separator);
 
     let props = props
         .clone()
@@ -49,7 +54,9 @@ pub fn nav_horizontal_box(mut context: WidgetContext) -> WidgetNode {
         .without::<NavItemActive>();
 
     widget! {
-        (#{key} horizontal_box: {props} |[listed_slots]|)
+        (#{key} horizontal_box: {props} {
+            separator = {separator}
+        } |[listed_slots]|)
     }
 }
 
@@ -57,13 +64,16 @@ pub fn horizontal_box(context: WidgetContext) -> WidgetNode {
     let WidgetContext {
         key,
         props,
+        named_slots,
         listed_slots,
         ..
     } = context;
+    unpack_named_slots!(named_slots => separator);
 
     let HorizontalBoxProps {
         separation,
         reversed,
+        padding,
         transform,
     } = props.read_cloned_or_default();
 
@@ -75,10 +85,13 @@ pub fn horizontal_box(context: WidgetContext) -> WidgetNode {
         },
         separation,
         wrap: false,
+        padding,
         transform,
     });
 
     widget! {
-        (#{key} flex_box: {props} |[ listed_slots ]|)
+        (#{key} flex_box: {props} {
+            separator = {separator}
+        } |[ listed_slots ]|)
     }
 }
@@ -7,9 +7,12 @@ pub mod flex_box;
 pub mod grid_box;
 pub mod hidden_box;
 pub mod horizontal_box;
+pub mod modal_box;
+pub mod navigator_box;
 pub mod portal_box;
 pub mod scroll_box;
 pub mod size_box;
+pub mod suspense_box;
 pub mod switch_box;
 pub mod tabs_box;
 pub mod tooltip_box;
@@ -0,0 +1,301 @@
+use crate::{
+    pre_hooks,
+    props::Props,
+    state::State,
+    unpack_named_slots, widget,
+    widget::{
+        component::{
+            containers::{
+                content_box::{content_box, nav_content_box},
+                portal_box::portal_box,
+                size_box::{size_box, SizeBoxProps},
+            },
+            interactive::{
+                button::{button, ButtonNotifyMessage, ButtonNotifyProps},
+                navigation::{
+                    use_nav_back_handler, use_nav_container_active, use_nav_focus_trap,
+                    use_nav_jump_step_pages_active, NavItemActive, NavJumpLooped, NavSignal,
+                },
+            },
+        },
+        context::WidgetContext,
+        node::WidgetNode,
+        unit::{area::AreaBoxNode, size::SizeBoxSizeValue},
+        WidgetId,
+    },
+    MessageData, PropsData,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(PropsData, Debug, Default, Copy, Clone, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub struct ModalBoxProps {
+    /// Initial state, later changed with [`ModalBoxSignal`] messages.
+    #[serde(default)]
+    pub open: bool,
+}
+
+#[derive(PropsData, Debug, Default, Copy, Clone, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub struct ModalBoxState {
+    #[serde(default)]
+    pub open: bool,
+}
+
+/// Sent to modal box to open or close it. Modal box also reports `Close` signal whenever it gets
+/// closed, including scrim click and back action.
+#[derive(MessageData, Debug, Copy, Clone, PartialEq, Eq)]
+#[message_data(crate::messenger::MessageData)]
+pub enum ModalBoxSignal {
+    Open,
+    Close,
+}
+
+impl ModalBoxState {
+    fn read(state: &State, props: &Props) -> Self {
+        state.read_cloned_or_else(|| {
+            let ModalBoxProps { open } = props.read_cloned_or_default();
+            Self { open }
+        })
+    }
+}
+
+pub fn use_modal_box(context: &mut WidgetContext) {
+    context.life_cycle.change(|context| {
+        let ModalBoxState { open: prev } = ModalBoxState::read(&context.state, context.props);
+        let mut open = prev;
+        for msg in context.messenger.messages {
+            if let Some(msg) = msg.as_any().downcast_ref() {
+                match msg {
+                    ModalBoxSignal::Open => open = true,
+                    ModalBoxSignal::Close => open = false,
+                }
+            } else if let Some(msg) = msg.as_any().downcast_ref::<ButtonNotifyMessage>() {
+                if msg.trigger_stop() {
+                    open = false;
+                }
+            }
+        }
+        if open != prev {
+            if !open {
+                context.signals.write(ModalBoxSignal::Close);
+            }
+            let _ = context.state.write_with(ModalBoxState { open });
+        }
+    });
+}
+
+fn use_modal_box_content(context: &mut WidgetContext) {
+    context.props.write(NavJumpLooped);
+
+    context.life_cycle.change(|context| {
+        for msg in context.messenger.messages {
+            if let Some(NavSignal::Back) = msg.as_any().downcast_ref() {
+                if let Ok(owner) = context.props.read::<WidgetId>() {
+                    context
+                        .messenger
+                        .write(owner.to_owned(), ModalBoxSignal::Close);
+                }
+            }
+        }
+    });
+}
+
+#[pre_hooks(
+    use_modal_box_content,
+    use_nav_container_active,
+    use_nav_jump_step_pages_active,
+    use_nav_focus_trap,
+    use_nav_back_handler
+)]
+fn modal_box_content(mut context: WidgetContext) -> WidgetNode {
+    content_box(context)
+}
+
+/// Shows `dialog` slot over a full-screen `scrim` slot in portals container while open.
+///
+/// Selection is trapped within the dialog, where `NavSignal::Prev`/`NavSignal::Next` cycle
+/// through its items. Scrim click or back action closes it.
+#[pre_hooks(use_modal_box)]
+pub fn modal_box(mut context: WidgetContext) -> WidgetNode {
+    let WidgetContext {
+        id,
+        idref,
+        key,
+        props,
+        state,
+        named_slots,
+        ..
+    } = context;
+    unpack_named_slots!(named_slots => {content, dialog, scrim});
+
+    let ModalBoxState { open } = ModalBoxState::read(&state, props);
+
+    let modal = if open {
+        // scrim has its own container, so it is not part of dialog items cycling.
+        let scrim_props = Props::new(NavItemActive).with(ButtonNotifyProps(id.to_owned().into()));
+        let size_props = SizeBoxProps {
+            width: SizeBoxSizeValue::Fill,
+            height: SizeBoxSizeValue::Fill,
+            ..Default::default()
+        };
+
+        widget! {
+            (#{"portal"} portal_box {
+                content = (#{"modal"} modal_box_content: {id.to_owned()} [
+                    (#{"scrim"} nav_content_box [
+                        (#{"button"} button: {scrim_props} {
+                            content = (#{"size"} size_box: {size_props} {
+                                content = {scrim}
+                            })
+                        })
+                    ])
+                    {dialog}
+                ])
+            })
+        }
+    } else {
+        widget! {()}
+    };
+
+    let content = widget! {
+        (#{key} | {idref.cloned()} content_box: {props.clone()} [
+            {content}
+            {modal}
+        ])
+    };
+
+    widget! {{{
+        AreaBoxNode {
+            id: id.to_owned(),
+            slot: Box::new(content),
+            renderer_effect: None,
+        }
+    }}}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        interactive::default_interactions_engine::{
+            DefaultInteractionsEngine, Interaction, PointerButton,
+        },
+        testing::run,
+        widget::{
+            component::{
+                containers::portal_box::PortalsContainer, interactive::navigation::NavType,
+            },
+            unit::content::ContentBoxItemLayout,
+            utils::{Rect, Vec2},
+            WidgetRef,
+        },
+    };
+
+    #[test]
+    fn test_modal_box() {
+        let item = |key: &str, left, right| {
+            let size_props = SizeBoxProps {
+                width: SizeBoxSizeValue::Fill,
+                height: SizeBoxSizeValue::Fill,
+                ..Default::default()
+            };
+            let props = Props::new(NavItemActive).with(ContentBoxItemLayout {
+                anchors: Rect {
+                    left,
+                    right,
+                    top: 0.0,
+                    bottom: 0.5,
+                },
+                ..Default::default()
+            });
+            widget! {
+                (#{key} button: {props} {
+                    content = (size_box: {size_props})
+                })
+            }
+        };
+        let selected = |interactions: &DefaultInteractionsEngine| {
+            interactions.selected_item().map(|id| id.key().to_owned())
+        };
+        let mut application = Application::new();
+        let idref = WidgetRef::default();
+        application.apply(widget! {
+            (#{"app"} | {idref.clone()} content_box | {PortalsContainer(idref)} [
+                (#{"screen"} nav_content_box [
+                    {item("outside", 0.0, 0.5)}
+                    (#{"modal"} modal_box: {ModalBoxProps { open: true }} {
+                        dialog = (#{"dialog"} content_box [
+                            {item("a", 0.5, 0.75)}
+                            {item("b", 0.75, 1.0)}
+                        ])
+                    })
+                ])
+            ])
+        });
+        application.forced_process();
+        let outside = application
+            .signals()
+            .iter()
+            .find_map(|(id, msg)| match msg.as_any().downcast_ref() {
+                Some(NavSignal::Register(NavType::Button(_))) if id.key() == "outside" => {
+                    Some(id.to_owned())
+                }
+                _ => None,
+            })
+            .unwrap();
+        let mut interactions = DefaultInteractionsEngine::new();
+        run(&mut application, &mut interactions, Interaction::None);
+        assert_eq!(selected(&interactions).as_deref(), Some("a"));
+
+        // sibling with trap key as prefix of its own key is outside of trap.
+        let trap = interactions.active_focus_trap().unwrap().to_owned();
+        let path = |suffix: &str| {
+            let mut path = trap.parts().map(|part| part.to_owned()).collect::<Vec<_>>();
+            path.last_mut().unwrap().push_str(suffix);
+            path.push("button".to_owned());
+            WidgetId::new("button", &path)
+        };
+        assert!(interactions.is_within_focus_trap(&path("")));
+        assert!(!interactions.is_within_focus_trap(&path("2")));
+
+        for (signal, expected) in [
+            (NavSignal::Next, "b"),
+            (NavSignal::Next, "a"),
+            (NavSignal::Prev, "b"),
+            (NavSignal::Select(outside.into()), "b"),
+        ] {
+            run(
+                &mut application,
+                &mut interactions,
+                Interaction::Navigate(signal),
+            );
+            run(&mut application, &mut interactions, Interaction::None);
+            assert_eq!(selected(&interactions).as_deref(), Some(expected));
+        }
+
+        let mut closed = 0;
+        for interaction in [
+            Interaction::PointerDown(PointerButton::Trigger, Vec2 { x: 25.0, y: 75.0 }),
+            Interaction::PointerUp(PointerButton::Trigger, Vec2 { x: 25.0, y: 75.0 }),
+            Interaction::None,
+            Interaction::None,
+            Interaction::None,
+        ] {
+            run(&mut application, &mut interactions, interaction);
+            closed += application
+                .signals()
+                .iter()
+                .filter(|(_, msg)| {
+                    msg.as_any().downcast_ref::<ModalBoxSignal>() == Some(&ModalBoxSignal::Close)
+                })
+                .count();
+        }
+        assert_eq!(closed, 1);
+        assert!(interactions.active_focus_trap().is_none());
+        assert!(interactions.selected_item().is_none());
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{
+    pre_hooks,
+    props::Props,
+    state::State,
+    unpack_named_slots, widget,
+    widget::{
+        component::{
+            containers::content_box::content_box,
+            interactive::navigation::{use_nav_back_handler, NavSignal},
+        },
+        context::WidgetContext,
+        node::WidgetNode,
+    },
+    MessageData, PropsData, Scalar,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(PropsData, Debug, Default, Clone, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub struct NavigatorBoxProps {
+    /// Route of initial page, later changed with [`NavigatorBoxSignal`] messages.
+    #[serde(default)]
+    pub root: String,
+    /// How long page stays mounted after it gets covered or popped, so it can animate out.
+    #[serde(default)]
+    pub exit_duration: Scalar,
+}
+
+#[derive(PropsData, Debug, Default, Clone, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub struct NavigatorBoxState {
+    /// Routes of pages, with the visible one at the end.
+    #[serde(default)]
+    pub stack: Vec<String>,
+}
+
+impl NavigatorBoxState {
+    fn read(state: &State, props: &Props) -> Self {
+        state.read_cloned_or_else(|| {
+            let NavigatorBoxProps { root, .. } = props.read_cloned_or_default();
+            Self {
+                stack: if root.is_empty() { vec![] } else { vec![root] },
+            }
+        })
+    }
+}
+
+/// Sent to navigator box to change its pages stack. Root page is never popped.
+#[derive(MessageData, Debug, Clone, PartialEq, Eq)]
+#[message_data(crate::messenger::MessageData)]
+pub enum NavigatorBoxSignal {
+    Push(String),
+    Pop,
+    /// Replaces the visible page.
+    Replace(String),
+    PopToRoot,
+}
+
+/// Props of page rendered by navigator box.
+#[derive(PropsData, Debug, Default, Clone, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub struct NavigatorPageProps {
+    #[serde(default)]
+    pub route: String,
+    #[serde(default)]
+    pub exit_duration: Scalar,
+    /// Exit progress factor, above 0 only while page is leaving.
+    #[serde(default)]
+    pub exit: Scalar,
+}
+
+pub fn use_navigator_box(context: &mut WidgetContext) {
+    context.life_cycle.change(|context| {
+        let NavigatorBoxState { stack: prev } =
+            NavigatorBoxState::read(&context.state, context.props);
+        let mut stack = prev.clone();
+        for msg in context.messenger.messages {
+            if let Some(msg) = msg.as_any().downcast_ref() {
+                match msg {
+                    NavigatorBoxSignal::Push(route) => stack.push(route.to_owned()),
+                    NavigatorBoxSignal::Pop => {
+                        if stack.len() > 1 {
+                            stack.pop();
+                        }
+                    }
+                    NavigatorBoxSignal::Replace(route) => {
+                        stack.pop();
+                        stack.push(route.to_owned());
+                    }
+                    NavigatorBoxSignal::PopToRoot => stack.truncate(1),
+                }
+            } else if let Some(NavSignal::Back) = msg.as_any().downcast_ref() {
+                if stack.len() > 1 {
+                    stack.pop();
+                }
+            }
+        }
+        if stack != prev {
+            let _ = context.state.write_with(NavigatorBoxState { stack });
+        }
+    });
+}
+
+/// Keeps page mounted for its exit duration once navigator stops showing it, and passes
+/// [`NavigatorPageProps`] with exit progress to its content.
+pub fn navigator_page(context: WidgetContext) -> WidgetNode {
+    let WidgetContext {
+        props,
+        animator,
+        life_cycle,
+        named_slots,
+        ..
+    } = context;
+    unpack_named_slots!(named_slots => content);
+
+    let mut page_props = props.read_cloned_or_default::<NavigatorPageProps>();
+    if page_props.exit_duration > 0.0 {
+        life_cycle.exit(page_props.exit_duration);
+    }
+    page_props.exit = animator.value_progress_factor_or_zero("exit", "exit");
+    if let Some(props) = content.props_mut() {
+        props.write(page_props);
+    }
+    content
+}
+
+/// Shows the page at the top of its stack, taking pages from named slots by route.
+///
+/// Back action pops the visible page. Pages are keyed with their stack index and route, so the
+/// same route pushed twice makes separate pages. Covered and popped pages stay mounted for
+/// `exit_duration` beneath the visible one, so pages can cross-animate.
+#[pre_hooks(use_nav_back_handler, use_navigator_box)]
+pub fn navigator_box(mut context: WidgetContext) -> WidgetNode {
+    let WidgetContext {
+        key,
+        props,
+        state,
+        mut named_slots,
+        ..
+    } = context;
+
+    let NavigatorBoxState { stack } = NavigatorBoxState::read(&state, props);
+    let NavigatorBoxProps { exit_duration, .. } = props.read_cloned_or_default();
+
+    let page = match stack.last() {
+        Some(route) => {
+            let key = format!("{}-{}", stack.len() - 1, route);
+            let page_props = NavigatorPageProps {
+                route: route.to_owned(),
+                exit_duration,
+                exit: 0.0,
+            };
+            let content = named_slots.remove(route).unwrap_or_default();
+            widget! {
+                (#{key} navigator_page: {page_props} {
+                    content = {content}
+                })
+            }
+        }
+        None => widget! {()},
+    };
+
+    widget! {
+        (#{key} content_box: {props.clone()} [
+            {page}
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        widget::{component::image_box::image_box, unit::WidgetUnit, WidgetId},
+    };
+
+    fn pages(application: &Application) -> Vec<String> {
+        match application.rendered_tree() {
+            WidgetUnit::ContentBox(unit) => unit
+                .items
+                .iter()
+                .filter_map(|item| item.slot.as_data())
+                .map(|data| data.id().path().to_owned())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_navigator_box() {
+        let props = NavigatorBoxProps {
+            root: "home".to_owned(),
+            exit_duration: 1.0,
+        };
+        let mut application = Application::new();
+        application.animations_delta_time = 0.5;
+        application.apply(widget! {
+            (#{"nav"} navigator_box: {props} {
+                home = (#{"home"} image_box)
+                details = (#{"details"} image_box)
+            })
+        });
+        application.process();
+        let id = WidgetId::new("navigator_box", &["nav".to_owned()]);
+        let stack = |application: &Application| {
+            application
+                .state_read(&id)
+                .unwrap()
+                .read_cloned_or_default::<NavigatorBoxState>()
+                .stack
+        };
+        let details = WidgetId::new(
+            "navigator_page",
+            &["nav".to_owned(), "nav".to_owned(), "1-details".to_owned()],
+        );
+        assert_eq!(pages(&application), vec!["nav/nav/0-home/home"]);
+
+        application.send_message(&id, NavigatorBoxSignal::Push("details".to_owned()));
+        application.process();
+        application.process();
+        assert_eq!(stack(&application), vec!["home", "details"]);
+        assert_eq!(
+            pages(&application),
+            vec!["nav/nav/0-home/home", "nav/nav/1-details/details"]
+        );
+        while application.process() {}
+        assert_eq!(pages(&application), vec!["nav/nav/1-details/details"]);
+
+        application.send_message(&id, NavSignal::Back);
+        application.process();
+        application.process();
+        assert_eq!(stack(&application), vec!["home"]);
+        assert!(application.is_exiting(&details));
+        assert_eq!(
+            pages(&application),
+            vec!["nav/nav/1-details/details", "nav/nav/0-home/home"]
+        );
+
+        // root page is never popped.
+        application.send_message(&id, NavigatorBoxSignal::Pop);
+        while application.process() {}
+        assert_eq!(stack(&application), vec!["home"]);
+        assert!(!application.is_exiting(&details));
+        assert_eq!(pages(&application), vec!["nav/nav/0-home/home"]);
+    }
+}
@@ -21,6 +21,16 @@ use serde::{Deserialize, Serialize};
 #[prefab(crate::Prefab)]
 pub struct PortalsContainer(#[serde(default)] pub WidgetRef);
 
+/// Stacking layer of portal among others teleported into the same container: portals get injected
+/// in order of their layers (so higher ones end up on top), then in order they were found in.
+#[derive(PropsData, Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub struct PortalLayer(#[serde(default)] pub i32);
+
+/// Teleports its content into the widget referenced by [`PortalsContainer`] (from props or shared
+/// props). The owner can be anywhere in the tree, before or after the portal - its reference is
+/// resolved once the whole tree got processed.
 pub fn portal_box(context: WidgetContext) -> WidgetNode {
     let WidgetContext {
         id,
@@ -31,8 +41,9 @@ pub fn portal_box(context: WidgetContext) -> WidgetNode {
     } = context;
     unpack_named_slots!(named_slots => content);
 
-    let PortalsContainer(owner) =
-        props.read_cloned_or_else(|| shared_props.read_cloned_or_default());
+    let owner = props
+        .read_cloned::<PortalsContainer>()
+        .or_else(|_| shared_props.read_cloned::<PortalsContainer>());
     let slot = if let Ok(layout) = props.read_cloned::<ContentBoxItemLayout>() {
         PortalBoxSlotNode::ContentItem(ContentBoxItemNode {
             slot: content,
@@ -51,13 +62,17 @@ pub fn portal_box(context: WidgetContext) -> WidgetNode {
     } else {
         PortalBoxSlotNode::Slot(content)
     };
+    let PortalLayer(layer) = props.read_cloned_or_default();
 
-    if let Some(owner) = owner.read() {
+    // owner might not be processed yet, so its reference gets resolved after processing.
+    if let Ok(PortalsContainer(owner)) = owner {
         widget! {{{
             PortalBoxNode {
                 id: id.to_owned(),
                 slot: Box::new(slot),
-                owner,
+                owner: owner.read().unwrap_or_default(),
+                owner_ref: Some(owner),
+                layer,
             }
         }}}
     } else {
@@ -73,3 +88,47 @@ pub fn use_portals_container_relative_layout(context: &mut WidgetContext) {
         relative_to: owner.into(),
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        widget::{
+            component::{containers::content_box::content_box, image_box::image_box},
+            unit::WidgetUnit,
+        },
+    };
+
+    #[test]
+    fn test_portal_layers() {
+        let idref = WidgetRef::default();
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"app"} | {idref.clone()} content_box | {PortalsContainer(idref)} [
+                (#{"modal"} portal_box: {PortalLayer(10)} {
+                    content = (#{"dialog"} image_box)
+                })
+                (#{"tooltip"} portal_box: {PortalLayer(-1)} {
+                    content = (#{"hint"} image_box)
+                })
+                (#{"dropdown"} portal_box {
+                    content = (#{"list"} image_box)
+                })
+            ])
+        });
+        application.process();
+        let keys = match application.rendered_tree() {
+            WidgetUnit::ContentBox(unit) => unit
+                .items
+                .iter()
+                .filter_map(|item| item.slot.as_data())
+                // consumed portals leave empty units behind.
+                .filter(|data| data.id().is_valid())
+                .map(|data| data.id().key().to_owned())
+                .collect::<Vec<_>>(),
+            _ => vec![],
+        };
+        assert_eq!(keys, vec!["hint", "list", "dialog"]);
+    }
+}
@@ -16,7 +16,7 @@ use crate::{
                     use_nav_scroll_view_content, NavButtonTrackingActive, NavItemActive, NavJump,
                     NavScroll, NavSignal,
                 },
-                scroll_view::{use_scroll_view, ScrollViewState},
+                scroll_view::{use_scroll_view, use_scroll_view_notified_state, ScrollViewState},
             },
             use_resize_listener, ResizeListenerSignal,
         },
@@ -73,6 +73,73 @@ pub struct SideScrollbarsState {
     pub vertical: Scalar,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScrollbarAxis {
+    Vertical,
+    Horizontal,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for ScrollbarAxis {
+    fn default() -> Self {
+        Self::Vertical
+    }
+}
+
+impl ScrollbarAxis {
+    fn get(self, v: Vec2) -> Scalar {
+        match self {
+            Self::Vertical => v.y,
+            Self::Horizontal => v.x,
+        }
+    }
+
+    fn set(self, v: &mut Vec2, value: Scalar) {
+        match self {
+            Self::Vertical => v.y = value,
+            Self::Horizontal => v.x = value,
+        }
+    }
+}
+
+/// Props of [`scrollbar`].
+///
+/// Scrolled view is taken from [`ScrollBoxOwner`] and its state from [`ScrollViewState`], both
+/// provided by [`nav_scroll_box`] to its `scrollbars` slot. Scrollbars used elsewhere need them
+/// put in props, or scroll view notifying scrollbar with `ScrollViewNotifyProps`.
+#[derive(PropsData, Debug, Default, Clone, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub struct ScrollbarProps {
+    #[serde(default)]
+    pub axis: ScrollbarAxis,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub back_material: Option<ImageBoxMaterial>,
+    #[serde(default)]
+    pub front_material: ImageBoxMaterial,
+}
+
+#[derive(PropsData, Debug, Default, Copy, Clone, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub struct ScrollbarState {
+    /// Track pointer position and scroll value at the start of thumb drag.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drag: Option<(Scalar, Scalar)>,
+}
+
+/// Thumb start and length as factors of the track, for given scroll value and size factor.
+fn scrollbar_thumb(value: Scalar, size_factor: Scalar) -> (Scalar, Scalar) {
+    let length = if size_factor > 1.0 {
+        1.0 / size_factor
+    } else {
+        1.0
+    };
+    (lerp(0.0, 1.0 - length, value), length)
+}
+
 pub fn use_nav_scroll_box_content(context: &mut WidgetContext) {
     context.life_cycle.change(|context| {
         for msg in context.messenger.messages {
@@ -189,6 +256,147 @@ pub fn nav_scroll_box(mut context: WidgetContext) -> WidgetNode {
     }}}
 }
 
+#[pre_hooks(use_scroll_view_notified_state)]
+pub fn use_scrollbar(context: &mut WidgetContext) {
+    context.life_cycle.mount(|context| {
+        let _ = context.state.write_with(ScrollbarState::default());
+    });
+
+    context.life_cycle.change(|context| {
+        let axis = context
+            .props
+            .map_or_default::<ScrollbarProps, _, _>(|p| p.axis);
+        let view_state = context
+            .props
+            .read_cloned::<ScrollViewState>()
+            .unwrap_or_else(|_| context.state.read_cloned_or_default::<ScrollViewState>());
+        let view = context.props.read_cloned_or_default::<ScrollBoxOwner>().0;
+        let mut data = context.state.read_cloned_or_default::<ScrollbarState>();
+        let mut dirty = false;
+        for msg in context.messenger.messages {
+            if let Some(msg) = msg.as_any().downcast_ref::<ButtonNotifyMessage>() {
+                let pointer = axis.get(msg.state.pointer);
+                let (start, length) =
+                    scrollbar_thumb(axis.get(view_state.value), axis.get(view_state.size_factor));
+                let rest = 1.0 - length;
+                let mut value = None;
+                if msg.trigger_start() {
+                    context.signals.write(NavSignal::Lock);
+                    // pressing track outside of the thumb moves thumb center under the pointer.
+                    let from = if pointer >= start && pointer <= start + length {
+                        axis.get(view_state.value)
+                    } else if rest > 0.0 {
+                        let from = ((pointer - length * 0.5) / rest).clamp(0.0, 1.0);
+                        value = Some(from);
+                        from
+                    } else {
+                        0.0
+                    };
+                    data.drag = Some((pointer, from));
+                    dirty = true;
+                } else if msg.state.trigger {
+                    if let Some((pointer_from, from)) = data.drag {
+                        if rest > 0.0 {
+                            value = Some((from + (pointer - pointer_from) / rest).clamp(0.0, 1.0));
+                        }
+                    }
+                }
+                if msg.trigger_stop() {
+                    context.signals.write(NavSignal::Unlock);
+                    data.drag = None;
+                    dirty = true;
+                }
+                if let Some(value) = value {
+                    let mut pos = view_state.value;
+                    axis.set(&mut pos, value);
+                    context.signals.write(NavSignal::Jump(NavJump::Scroll(
+                        NavScroll::DirectFactor(view.to_owned().into(), pos, false),
+                    )));
+                }
+            }
+        }
+        if dirty {
+            let _ = context.state.write_with(data);
+        }
+    });
+}
+
+/// Scrollbar with draggable thumb, hidden when scrolled content fits its view.
+#[pre_hooks(use_nav_item_active, use_nav_container_active, use_scrollbar)]
+pub fn scrollbar(mut context: WidgetContext) -> WidgetNode {
+    let WidgetContext {
+        id,
+        key,
+        props,
+        state,
+        ..
+    } = context;
+
+    let view_state = props
+        .read_cloned::<ScrollViewState>()
+        .unwrap_or_else(|_| state.read_cloned_or_default::<ScrollViewState>());
+    let ScrollbarProps {
+        axis,
+        back_material,
+        front_material,
+    } = props.read_cloned_or_default();
+
+    let size_factor = axis.get(view_state.size_factor);
+    if size_factor <= 1.0 {
+        return widget! {()};
+    }
+    let (start, length) = scrollbar_thumb(axis.get(view_state.value), size_factor);
+
+    let button_props = Props::new(ButtonNotifyProps(id.to_owned().into()))
+        .with(NavItemActive)
+        .with(NavButtonTrackingActive);
+
+    let back = if let Some(material) = back_material {
+        let props = ImageBoxProps {
+            material,
+            ..Default::default()
+        };
+
+        widget! { (#{"back"} image_box: {props}) }
+    } else {
+        widget! {()}
+    };
+
+    let anchors = match axis {
+        ScrollbarAxis::Vertical => Rect {
+            left: 0.0,
+            right: 1.0,
+            top: start,
+            bottom: start + length,
+        },
+        ScrollbarAxis::Horizontal => Rect {
+            left: start,
+            right: start + length,
+            top: 0.0,
+            bottom: 1.0,
+        },
+    };
+    let front_props = Props::new(ImageBoxProps {
+        material: front_material,
+        ..Default::default()
+    })
+    .with(ContentBoxItemLayout {
+        anchors,
+        ..Default::default()
+    });
+
+    widget! {
+        (#{key} content_box [
+            (#{"track"} button: {button_props} {
+                content = (#{"container"} content_box [
+                    {back}
+                    (#{"front"} image_box: {front_props})
+                ])
+            })
+        ])
+    }
+}
+
 pub fn use_nav_scroll_box_side_scrollbars(context: &mut WidgetContext) {
     context.life_cycle.mount(|context| {
         let _ = context.state.write_with(SideScrollbarsState::default());
@@ -377,3 +585,94 @@ pub fn nav_scroll_box_side_scrollbars(mut context: WidgetContext) -> WidgetNode
         ])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        interactive::default_interactions_engine::{
+            DefaultInteractionsEngine, Interaction, PointerButton,
+        },
+        layout::{default_layout_engine::DefaultLayoutEngine, CoordsMapping},
+        widget::{component::containers::content_box::nav_content_box, WidgetRef},
+    };
+
+    #[test]
+    fn test_scrollbar_drag() {
+        let view_ref = WidgetRef::new();
+        let content_props = SizeBoxProps {
+            width: SizeBoxSizeValue::Fill,
+            height: SizeBoxSizeValue::Exact(400.0),
+            ..Default::default()
+        };
+        let scrollbar_props = Props::new(ScrollbarProps::default()).with(ContentBoxItemLayout {
+            anchors: Rect {
+                left: 1.0,
+                right: 1.0,
+                top: 0.0,
+                bottom: 1.0,
+            },
+            margin: Rect {
+                left: -10.0,
+                right: 0.0,
+                top: 0.0,
+                bottom: 0.0,
+            },
+            ..Default::default()
+        });
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"app"} nav_content_box [
+                (#{"view"} | {view_ref.clone()} nav_scroll_box {
+                    content = (#{"content"} size_box: {content_props})
+                    scrollbars = (#{"scrollbar"} scrollbar: {scrollbar_props})
+                })
+            ])
+        });
+        let mapping = CoordsMapping::new(Rect::from((100.0, 100.0)));
+        let mut interactions = DefaultInteractionsEngine::new();
+        let mut run = |application: &mut Application, interaction| {
+            application.forced_process();
+            application
+                .layout(&mapping, &mut DefaultLayoutEngine)
+                .unwrap();
+            interactions.interact(interaction);
+            application.interact(&mut interactions).unwrap();
+        };
+        let value = |application: &Application| {
+            application
+                .state_read(&view_ref.read().unwrap())
+                .unwrap()
+                .read_cloned_or_default::<ScrollViewState>()
+        };
+        for _ in 0..5 {
+            run(&mut application, Interaction::None);
+        }
+        assert_eq!(value(&application).size_factor.y, 4.0);
+
+        // thumb covers top quarter of the track, leaving three quarters to move along.
+        run(
+            &mut application,
+            Interaction::PointerDown(PointerButton::Trigger, Vec2 { x: 95.0, y: 10.0 }),
+        );
+        run(
+            &mut application,
+            Interaction::PointerMove(Vec2 { x: 95.0, y: 40.0 }),
+        );
+        for _ in 0..5 {
+            run(&mut application, Interaction::None);
+        }
+        assert!((value(&application).value.y - 0.4).abs() < 1.0e-4);
+        assert_eq!(value(&application).value.x, 0.0);
+
+        run(
+            &mut application,
+            Interaction::PointerUp(PointerButton::Trigger, Vec2 { x: 95.0, y: 40.0 }),
+        );
+        for _ in 0..5 {
+            run(&mut application, Interaction::None);
+        }
+        assert!((value(&application).value.y - 0.4).abs() < 1.0e-4);
+    }
+}
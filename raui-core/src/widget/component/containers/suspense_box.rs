@@ -0,0 +1,146 @@
+use crate::{
+    data_binding::DataBinding,
+    widget,
+    widget::{
+        component::containers::switch_box::{switch_box, SwitchBoxProps},
+        context::WidgetContext,
+        node::WidgetNode,
+        utils::Transform,
+    },
+    PropsData,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(PropsData, Debug, Default, Clone, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub struct SuspenseBoxProps {
+    /// Used when there is no `ready_binding`.
+    #[serde(default)]
+    pub ready: bool,
+    /// Readiness flag updated from outside of the UI, takes precedence over `ready`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ready_binding: Option<DataBinding<bool>>,
+    #[serde(default)]
+    pub clipping: bool,
+    #[serde(default)]
+    pub transform: Transform,
+}
+
+impl SuspenseBoxProps {
+    pub fn is_ready(&self) -> bool {
+        match &self.ready_binding {
+            Some(binding) => binding.read_cloned_or_default(),
+            None => self.ready,
+        }
+    }
+}
+
+fn keyed(mut node: WidgetNode, key: &str) -> WidgetNode {
+    if let WidgetNode::Component(component) = &mut node {
+        if component.key.is_none() {
+            component.key = Some(key.to_owned());
+        }
+    }
+    node
+}
+
+/// Shows `fallback` slot until it is ready, then shows `ready` slot.
+///
+/// Slots get distinct keys (unless they already have one), so the same widget type used in both
+/// slots never shares state and `ready` content keeps its state for as long as it stays ready.
+pub fn suspense_box(context: WidgetContext) -> WidgetNode {
+    let WidgetContext {
+        key,
+        props,
+        mut named_slots,
+        ..
+    } = context;
+
+    let props = props.read_cloned_or_default::<SuspenseBoxProps>();
+    let switch_props = SwitchBoxProps {
+        active_index: Some(if props.is_ready() { 0 } else { 1 }),
+        clipping: props.clipping,
+        transform: props.transform,
+        ..Default::default()
+    };
+    let ready = keyed(named_slots.remove("ready").unwrap_or_default(), "ready");
+    let fallback = keyed(
+        named_slots.remove("fallback").unwrap_or_default(),
+        "fallback",
+    );
+
+    widget! {
+        (#{key} switch_box: {switch_props} [
+            {ready}
+            {fallback}
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        widget::{
+            component::text_box::{text_box, TextBoxProps},
+            unit::WidgetUnit,
+        },
+    };
+
+    fn rendered_text(application: &Application) -> Option<(String, String)> {
+        match application.rendered_tree() {
+            WidgetUnit::ContentBox(unit) => match &unit.items.first()?.slot {
+                WidgetUnit::TextBox(unit) => Some((unit.id.key().to_owned(), unit.text.to_owned())),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_suspense_box() {
+        let mut application = Application::new();
+        let mut binding = DataBinding::new_bound(false, application.change_notifier());
+        let props = SuspenseBoxProps {
+            ready_binding: Some(binding.clone()),
+            ..Default::default()
+        };
+        let content = TextBoxProps {
+            text: "content".to_owned(),
+            ..Default::default()
+        };
+        let loading = TextBoxProps {
+            text: "loading".to_owned(),
+            ..Default::default()
+        };
+        application.apply(widget! {
+            (#{"suspense"} suspense_box: {props} {
+                ready = (text_box: {content})
+                fallback = (text_box: {loading})
+            })
+        });
+        application.process();
+        assert_eq!(
+            rendered_text(&application),
+            Some(("fallback".to_owned(), "loading".to_owned()))
+        );
+        assert!(!application.process());
+
+        binding.write(true);
+        assert!(application.process());
+        assert_eq!(
+            rendered_text(&application),
+            Some(("ready".to_owned(), "content".to_owned()))
+        );
+
+        binding.write(false);
+        assert!(application.process());
+        assert_eq!(
+            rendered_text(&application),
+            Some(("fallback".to_owned(), "loading".to_owned()))
+        );
+    }
+}
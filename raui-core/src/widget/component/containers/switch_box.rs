@@ -14,6 +14,41 @@ use crate::{
 };
 use serde::{Deserialize, Serialize};
 
+/// What switch box shows when its active index is past its last item
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SwitchBoxOutOfRange {
+    /// Shows nothing
+    None,
+    /// Shows the last item
+    Clamp,
+    /// Shows item at index modulo items count
+    Wrap,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for SwitchBoxOutOfRange {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl SwitchBoxOutOfRange {
+    /// Index of item to show for `index` among `count` items.
+    pub fn resolve(self, index: usize, count: usize) -> Option<usize> {
+        if index < count {
+            return Some(index);
+        }
+        if count == 0 {
+            return None;
+        }
+        match self {
+            Self::None => None,
+            Self::Clamp => Some(count - 1),
+            Self::Wrap => Some(index % count),
+        }
+    }
+}
+
 #[derive(PropsData, Debug, Default, Clone, Serialize, Deserialize)]
 #[props_data(crate::props::PropsData)]
 #[prefab(crate::Prefab)]
@@ -22,6 +57,8 @@ pub struct SwitchBoxProps {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub active_index: Option<usize>,
     #[serde(default)]
+    pub out_of_range: SwitchBoxOutOfRange,
+    #[serde(default)]
     pub clipping: bool,
     #[serde(default)]
     pub transform: Transform,
@@ -57,22 +94,22 @@ pub fn switch_box(context: WidgetContext) -> WidgetNode {
 
     let SwitchBoxProps {
         active_index,
+        out_of_range,
         clipping,
         transform,
     } = props.read_cloned_or_default();
 
-    let items = if let Some(index) = active_index {
-        if let Some(slot) = listed_slots.into_iter().nth(index) {
+    let count = listed_slots.len();
+    let items = active_index
+        .and_then(|index| out_of_range.resolve(index, count))
+        .and_then(|index| listed_slots.into_iter().nth(index))
+        .map(|slot| {
             vec![ContentBoxItemNode {
                 slot,
                 ..Default::default()
             }]
-        } else {
-            vec![]
-        }
-    } else {
-        vec![]
-    };
+        })
+        .unwrap_or_default();
 
     widget! {{{
         ContentBoxNode {
@@ -81,6 +118,57 @@ pub fn switch_box(context: WidgetContext) -> WidgetNode {
             items,
             clipping,
             transform,
+            ..Default::default()
         }
     }}}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        widget::{component::image_box::image_box, unit::WidgetUnit},
+    };
+
+    fn shown(out_of_range: SwitchBoxOutOfRange, active_index: usize) -> Vec<String> {
+        let props = SwitchBoxProps {
+            active_index: Some(active_index),
+            out_of_range,
+            ..Default::default()
+        };
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"switch"} switch_box: {props} [
+                (#{"a"} image_box)
+                (#{"b"} image_box)
+                (#{"c"} image_box)
+            ])
+        });
+        application.process();
+        match application.rendered_tree() {
+            WidgetUnit::ContentBox(unit) => unit
+                .items
+                .iter()
+                .filter_map(|item| item.slot.as_data())
+                .map(|data| data.id().key().to_owned())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_switch_box_out_of_range() {
+        for mode in [
+            SwitchBoxOutOfRange::None,
+            SwitchBoxOutOfRange::Clamp,
+            SwitchBoxOutOfRange::Wrap,
+        ] {
+            assert_eq!(shown(mode, 1), vec!["b"]);
+        }
+        assert!(shown(SwitchBoxOutOfRange::None, 4).is_empty());
+        assert_eq!(shown(SwitchBoxOutOfRange::Clamp, 4), vec!["c"]);
+        assert_eq!(shown(SwitchBoxOutOfRange::Wrap, 4), vec!["b"]);
+        assert_eq!(SwitchBoxOutOfRange::Wrap.resolve(4, 0), None);
+    }
+}
@@ -81,9 +81,10 @@ impl TabsBoxProps {
                 TabsBoxTabsLocation::Left => FlexBoxDirection::HorizontalLeftToRight,
                 TabsBoxTabsLocation::Right => FlexBoxDirection::HorizontalRightToLeft,
             },
-            separation: self.tabs_and_content_separation,
+            separation: self.tabs_and_content_separation.into(),
             wrap: false,
             transform: self.transform.to_owned(),
+            ..Default::default()
         }
     }
 
@@ -0,0 +1,121 @@
+//! Component that isolates panics of its content
+
+use crate::{
+    unpack_named_slots,
+    widget::{component::WidgetComponent, context::WidgetContext, node::WidgetNode},
+    PropsData,
+};
+use serde::{Deserialize, Serialize};
+
+/// Marks component made by [`error_boundary`], so application catches panics of its content.
+#[derive(PropsData, Debug, Default, Copy, Clone, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub(crate) struct ErrorBoundaryMarker;
+
+pub(crate) fn error_boundary_component(context: WidgetContext) -> WidgetNode {
+    unpack_named_slots!(context.named_slots => content);
+    content
+}
+
+/// Makes component that renders `content`, unless processing of that content panics - then it
+/// renders `fallback` instead.
+///
+/// Panic gets caught when application processes content subtree, content widgets get unmounted
+/// and panic message is reported to application [`Logger`][crate::Logger] as an error. Content is
+/// tried again on every next process, so boundary recovers when cause of the panic goes away.
+/// Only Rust panics are caught, and only with panic strategy set to unwind.
+///
+/// # Example
+///
+/// ```
+/// # use raui_core::prelude::*;
+/// widget! {
+///     (content_box [
+///         {error_boundary(widget! {(image_box)}, widget! {(space_box)}).key("boundary")}
+///     ])
+/// };
+/// ```
+pub fn error_boundary<C, F>(content: C, fallback: F) -> WidgetComponent
+where
+    C: Into<WidgetNode>,
+    F: Into<WidgetNode>,
+{
+    WidgetComponent::new(error_boundary_component, "error_boundary")
+        .with_props(ErrorBoundaryMarker)
+        .named_slot("content", content)
+        .named_slot("fallback", fallback)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        widget,
+        widget::{
+            component::{containers::content_box::content_box, image_box::image_box},
+            unit::WidgetUnit,
+        },
+        LogKind, Logger,
+    };
+    use std::sync::{Arc, Mutex};
+
+    struct TestLogger(Arc<Mutex<Vec<String>>>);
+
+    impl Logger for TestLogger {
+        fn log(&mut self, kind: LogKind, message: &str) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("{:?}: {}", kind, message));
+        }
+    }
+
+    fn broken(_: WidgetContext) -> WidgetNode {
+        panic!("broken widget");
+    }
+
+    #[test]
+    fn test_error_boundary() {
+        let logs = Arc::new(Mutex::new(vec![]));
+        let mut application = Application::new();
+        application.set_logger(TestLogger(logs.clone()));
+        application.apply(widget! {
+            (#{"app"} content_box [
+                (#{"healthy"} image_box)
+                {error_boundary(
+                    widget! {
+                        (#{"wrapper"} content_box [
+                            (#{"broken"} broken)
+                        ])
+                    },
+                    widget! {(#{"fallback"} image_box)},
+                ).key("boundary")}
+            ])
+        });
+        assert!(application.process());
+        let keys = match application.rendered_tree() {
+            WidgetUnit::ContentBox(unit) => unit
+                .items
+                .iter()
+                .filter_map(|item| item.slot.as_data())
+                .map(|data| data.id().key().to_owned())
+                .collect::<Vec<_>>(),
+            _ => vec![],
+        };
+        assert_eq!(keys, vec!["healthy", "fallback"]);
+        assert!(application
+            .state_read(&"image_box:/app/boundary/fallback".parse().unwrap())
+            .is_some());
+        assert!(application
+            .state_read(&"content_box:/app/boundary/wrapper".parse().unwrap())
+            .is_none());
+        assert_eq!(
+            *logs.lock().unwrap(),
+            vec![
+                "Error: Error boundary `error_boundary:/app/boundary` caught panic: broken widget"
+            ]
+        );
+    }
+}
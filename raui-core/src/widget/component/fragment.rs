@@ -0,0 +1,125 @@
+//! Component that contributes no box of its own
+
+use crate::{
+    widget::{component::WidgetComponent, context::WidgetContext, node::WidgetNode},
+    PropsData,
+};
+use serde::{Deserialize, Serialize};
+
+/// Marks component made by [`fragment`], so it gets expanded in listed slots of its parent.
+#[derive(PropsData, Debug, Default, Copy, Clone, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub(crate) struct FragmentMarker;
+
+pub(crate) fn fragment_component(context: WidgetContext) -> WidgetNode {
+    WidgetNode::Tuple(context.listed_slots)
+}
+
+/// Makes component that renders given listed slots inline, as a [`WidgetNode::Tuple`].
+///
+/// When put in listed slots of another component, fragment gets replaced by its own listed slots
+/// before that component runs, so they get laid out by the parent as if they were its direct
+/// children. Fragment props and key are not passed to its children.
+///
+/// # Example
+///
+/// ```
+/// # use raui_core::prelude::*;
+/// widget! {
+///     (vertical_box [
+///         (image_box)
+///         {fragment(vec![widget! {(image_box)}, widget! {(image_box)}]).key("group")}
+///     ])
+/// };
+/// ```
+pub fn fragment<I, T>(listed_slots: I) -> WidgetComponent
+where
+    I: IntoIterator<Item = T>,
+    T: Into<WidgetNode>,
+{
+    WidgetComponent::new(fragment_component, "fragment")
+        .with_props(FragmentMarker)
+        .listed_slots(listed_slots.into_iter())
+}
+
+/// Replaces [`fragment`] components with their listed slots, recursively.
+pub(crate) fn expand_fragments(slots: Vec<WidgetNode>) -> Vec<WidgetNode> {
+    if !slots.iter().any(is_fragment) {
+        return slots;
+    }
+    let mut result = Vec::with_capacity(slots.len());
+    for slot in slots {
+        match slot {
+            WidgetNode::Component(component) if component.props.has::<FragmentMarker>() => {
+                result.extend(expand_fragments(component.listed_slots));
+            }
+            slot => result.push(slot),
+        }
+    }
+    result
+}
+
+fn is_fragment(node: &WidgetNode) -> bool {
+    match node {
+        WidgetNode::Component(component) => component.props.has::<FragmentMarker>(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        layout::{default_layout_engine::DefaultLayoutEngine, CoordsMapping},
+        widget,
+        widget::{
+            component::{containers::vertical_box::vertical_box, image_box::image_box},
+            utils::Rect,
+        },
+    };
+
+    #[test]
+    fn test_fragment() {
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"list"} vertical_box [
+                (#{"a"} image_box)
+                {fragment(vec![
+                    widget! {(#{"b"} image_box)},
+                    widget! {(#{"c"} image_box)},
+                ]).key("group")}
+            ])
+        });
+        application.process();
+        application
+            .layout(
+                &CoordsMapping::new(Rect::from((90.0, 90.0))),
+                &mut DefaultLayoutEngine,
+            )
+            .unwrap();
+        let mut items = application
+            .layout_data()
+            .items
+            .iter()
+            .filter(|(id, _)| id.type_name() == "image_box")
+            .map(|(id, item)| (id.path().to_owned(), item.ui_space.top))
+            .collect::<Vec<_>>();
+        items.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            items,
+            vec![
+                ("list/list/a".to_owned(), 0.0),
+                ("list/list/b".to_owned(), 30.0),
+                ("list/list/c".to_owned(), 60.0),
+            ]
+        );
+
+        let unmarked = WidgetComponent::new(fragment_component, "fragment")
+            .listed_slot(widget! {(#{"d"} image_box)});
+        let slots = expand_fragments(vec![unmarked.into()]);
+        assert_eq!(slots.len(), 1);
+        assert_eq!(slots[0].as_component().unwrap().type_name, "fragment");
+    }
+}
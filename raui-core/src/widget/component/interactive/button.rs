@@ -2,7 +2,9 @@ use crate::{
     messenger::MessageData,
     pre_hooks, unpack_named_slots, widget,
     widget::{
-        component::interactive::navigation::{use_nav_button, use_nav_item, NavSignal},
+        component::interactive::navigation::{
+            use_nav_button, use_nav_item, InteractionState, InteractiveDisabled, NavSignal,
+        },
         context::{WidgetContext, WidgetMountOrChangeContext},
         node::WidgetNode,
         unit::area::AreaBoxNode,
@@ -126,15 +128,29 @@ pub fn use_button(context: &mut WidgetContext) {
                 prev: Default::default(),
             },
         );
-        let _ = context.state.write_with(ButtonProps::default());
+        // carry over disabled flag written by `use_nav_disabled`, this write would override it.
+        let disabled = context
+            .props
+            .read_cloned_or_default::<InteractiveDisabled>();
+        let _ = context.state.update().write(
+            context
+                .state
+                .data()
+                .to_owned()
+                .with(ButtonProps::default())
+                .with(disabled),
+        );
     });
 
     context.life_cycle.change(|context| {
         let mut data = context.state.read_cloned_or_default::<ButtonProps>();
         let prev = data;
+        let mut interaction = context.state.read_cloned_or_default::<InteractionState>();
         let mut dirty = false;
+        let mut interaction_dirty = false;
         for msg in context.messenger.messages {
             if let Some(msg) = msg.as_any().downcast_ref() {
+                interaction_dirty = interaction.apply(msg) || interaction_dirty;
                 match msg {
                     NavSignal::Select(_) => {
                         data.selected = true;
@@ -176,7 +192,21 @@ pub fn use_button(context: &mut WidgetContext) {
                     prev,
                 },
             );
-            let _ = context.state.write_with(data);
+        }
+        // all are written at once, separate writes in the same frame would override each other.
+        if dirty || interaction_dirty {
+            let disabled = context
+                .props
+                .read_cloned_or_default::<InteractiveDisabled>();
+            let _ = context.state.update().write(
+                context
+                    .state
+                    .data()
+                    .to_owned()
+                    .with(data)
+                    .with(interaction)
+                    .with(disabled),
+            );
         }
     });
 }
@@ -185,6 +215,7 @@ pub fn use_button(context: &mut WidgetContext) {
 pub fn button(mut context: WidgetContext) -> WidgetNode {
     let WidgetContext {
         id,
+        props,
         state,
         named_slots,
         ..
@@ -192,7 +223,14 @@ pub fn button(mut context: WidgetContext) -> WidgetNode {
     unpack_named_slots!(named_slots => content);
 
     if let Some(p) = content.props_mut() {
+        let interaction = state.read_cloned_or_default::<InteractionState>();
+        let disabled = props.read_cloned::<InteractiveDisabled>().ok();
         p.write(state.read_cloned_or_default::<ButtonProps>());
+        p.write(interaction);
+        p.write(interaction.visual(disabled.map(|d| d.0).unwrap_or_default()));
+        if let Some(disabled) = disabled {
+            p.write(disabled);
+        }
     }
 
     widget! {{{
@@ -4,7 +4,9 @@ use crate::{
     widget::{
         component::interactive::{
             button::{use_button, ButtonProps},
-            navigation::{use_nav_item, use_nav_text_input, NavSignal, NavTextChange},
+            navigation::{
+                use_nav_item, use_nav_text_input, InteractiveDisabled, NavSignal, NavTextChange,
+            },
         },
         context::{WidgetContext, WidgetMountOrChangeContext},
         node::WidgetNode,
@@ -147,7 +149,14 @@ pub fn use_text_input(context: &mut WidgetContext) {
                 state: data.to_owned(),
             },
         );
-        let _ = context.state.write_with(data);
+        // carry over disabled flag written by `use_nav_disabled`, this write would override it.
+        let disabled = context
+            .props
+            .read_cloned_or_default::<InteractiveDisabled>();
+        let _ = context
+            .state
+            .update()
+            .write(context.state.data().to_owned().with(data).with(disabled));
     });
 
     context.life_cycle.change(|context| {
@@ -161,7 +170,8 @@ pub fn use_text_input(context: &mut WidgetContext) {
                         dirty = true;
                     }
                     NavSignal::TextChange(change) => {
-                        if data.focused {
+                        // placing cursor (e.g. by click) does not require focus.
+                        if data.focused || matches!(change, NavTextChange::MoveCursorTo(_)) {
                             match change {
                                 NavTextChange::InsertCharacter(c) => {
                                     if !c.is_control() {
@@ -191,6 +201,15 @@ pub fn use_text_input(context: &mut WidgetContext) {
                                     }
                                 }
                                 NavTextChange::MoveCursorStart => data.cursor_position = 0,
+                                NavTextChange::MoveCursorTo(index) => {
+                                    // index is of character, but cursor position is byte offset.
+                                    data.cursor_position = data
+                                        .text
+                                        .char_indices()
+                                        .nth(*index)
+                                        .map(|(offset, _)| offset)
+                                        .unwrap_or_else(|| data.text.len());
+                                }
                                 NavTextChange::MoveCursorEnd => {
                                     data.cursor_position = data.text.len();
                                 }
@@ -239,7 +258,13 @@ pub fn use_text_input(context: &mut WidgetContext) {
                     state: data.to_owned(),
                 },
             );
-            let _ = context.state.write_with(data);
+            let disabled = context
+                .props
+                .read_cloned_or_default::<InteractiveDisabled>();
+            let _ = context
+                .state
+                .update()
+                .write(context.state.data().to_owned().with(data).with(disabled));
         }
     });
 }
@@ -1,7 +1,8 @@
 use crate::{
+    interactive::default_interactions_engine::PointerButton,
     post_hooks, pre_hooks, unpack_named_slots, widget,
     widget::{
-        context::WidgetContext, node::WidgetNode, unit::area::AreaBoxNode, utils::Vec2,
+        context::WidgetContext, node::WidgetNode, unit::area::AreaBoxNode, utils::Vec2, WidgetId,
         WidgetIdOrRef,
     },
     MessageData, PropsData, Scalar,
@@ -33,7 +34,90 @@ pub struct NavJumpActive(#[serde(default)] pub NavJumpMode);
 #[prefab(crate::Prefab)]
 pub struct NavJumpLooped;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Disabled widgets are not hit-tested by pointers and are skipped by navigation, but they still
+/// occupy layout space. Set it to `false` rather than removing it to enable widget back.
+#[derive(PropsData, Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub struct InteractiveDisabled(#[serde(default)] pub bool);
+
+/// Interaction state of widget as seen by interactions engine, kept in widget state by
+/// `use_button` so components can style themselves with it. Widgets with their own hooks can
+/// track it with [`apply`][Self::apply], writing it to state together with their other state.
+#[derive(PropsData, Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub struct InteractionState {
+    #[serde(default)]
+    pub hovered: bool,
+    #[serde(default)]
+    pub pressed: bool,
+    #[serde(default)]
+    pub focused: bool,
+    /// Focus should be visibly indicated (like CSS `:focus-visible`), because widget got focused
+    /// with keyboard or gamepad rather than pointer.
+    #[serde(default)]
+    pub focus_visible: bool,
+}
+
+impl InteractionState {
+    /// Applies navigation signal to the state and returns true if anything has changed.
+    pub fn apply(&mut self, signal: &NavSignal) -> bool {
+        let prev = *self;
+        match signal {
+            NavSignal::PointerEnter => self.hovered = true,
+            NavSignal::PointerLeave => self.hovered = false,
+            NavSignal::Accept(v) => self.pressed = *v,
+            NavSignal::Select(_) => self.focused = true,
+            NavSignal::Unselect => {
+                self.focused = false;
+                self.focus_visible = false;
+            }
+            NavSignal::FocusVisible(v) => self.focus_visible = *v,
+            NavSignal::FocusTextInput(idref) => self.focused = idref.is_some(),
+            _ => {}
+        }
+        *self != prev
+    }
+
+    /// Single visual state, where disabled wins over pressed, pressed over hovered and hovered
+    /// over focused.
+    pub fn visual(&self, disabled: bool) -> InteractiveVisualState {
+        if disabled {
+            InteractiveVisualState::Disabled
+        } else if self.pressed {
+            InteractiveVisualState::Pressed
+        } else if self.hovered {
+            InteractiveVisualState::Hovered
+        } else if self.focused {
+            InteractiveVisualState::Focused
+        } else {
+            InteractiveVisualState::Normal
+        }
+    }
+}
+
+/// Visual state of interactive widget that themed components pick their look with, written by
+/// `button` into its content props (made with [`InteractionState::visual`]).
+#[derive(PropsData, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub enum InteractiveVisualState {
+    Normal,
+    Hovered,
+    Pressed,
+    Focused,
+    Disabled,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for InteractiveVisualState {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NavType {
     Container,
     Item,
@@ -42,9 +126,17 @@ pub enum NavType {
     TextInput,
     ScrollView,
     ScrollViewContent,
+    /// Receives [`NavSignal::Back`] of selected items within its subtree.
+    BackHandler,
+    /// While it is the most recently registered one, items outside of its subtree cannot be
+    /// selected and selection moves to its first item.
+    FocusTrap,
+    /// Stops [`NavEvent`]s of its descendants in given phase, after receiving them. Target that
+    /// stops bubble phase keeps its own events from bubbling.
+    EventStop(NavEventPhase),
 }
 
-#[derive(MessageData, Debug, Clone)]
+#[derive(MessageData, Debug, Clone, Serialize, Deserialize)]
 #[message_data(crate::messenger::MessageData)]
 pub enum NavSignal {
     None,
@@ -52,11 +144,24 @@ pub enum NavSignal {
     Unregister(NavType),
     Select(WidgetIdOrRef),
     Unselect,
+    /// Sent to selected items after selection changes, telling if focus should be visibly
+    /// indicated: true when selected with keyboard or gamepad, false when with pointer.
+    FocusVisible(bool),
     Lock,
     Unlock,
+    Disabled(bool),
+    PointerEnter,
+    PointerLeave,
+    /// Routes further pointer moves and release of given button to the sender.
+    CapturePointer(PointerButton),
+    ReleasePointer(PointerButton),
     Accept(bool),
     Context(bool),
     Cancel(bool),
+    /// Back/escape action. Routed to the nearest [`NavType::BackHandler`] containing selected item
+    /// (or the most recently registered one when nothing is selected). When there is no such
+    /// handler, [`NavBackUnhandled`] is reported by application signals instead.
+    Back,
     Up,
     Down,
     Left,
@@ -68,8 +173,38 @@ pub enum NavSignal {
     TextChange(NavTextChange),
     Axis(String, Scalar),
     Custom(WidgetIdOrRef, String),
+    Gesture(NavGesture),
 }
 
+/// Events are sent to all receivers at once, in the same frame as the target gets its signal,
+/// so widgets stop them up front by registering [`NavType::EventStop`] (see
+/// [`use_nav_event_stop`]) rather than while handling them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NavEventPhase {
+    /// Goes from the root down to the parent of the target. Stopping it in this phase does not
+    /// keep the signal from the target, only from descendants of the stopping widget and from
+    /// bubbling.
+    Capture,
+    /// Goes from the parent of the target up to the root.
+    Bubble,
+}
+
+/// Interaction of a descendant widget, propagated to its ancestors when the interactions engine
+/// has event propagation enabled.
+#[derive(MessageData, Debug, Clone)]
+#[message_data(crate::messenger::MessageData)]
+pub struct NavEvent {
+    pub target: WidgetId,
+    pub phase: NavEventPhase,
+    pub signal: NavSignal,
+}
+
+/// Signal reported by the application when [`NavSignal::Back`] was not handled by any widget, so
+/// the host can act on it (e.g. leave the current screen). Its id is the selected item, if any.
+#[derive(MessageData, Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[message_data(crate::messenger::MessageData)]
+pub struct NavBackUnhandled;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NavJumpMode {
     Direction,
@@ -125,7 +260,7 @@ impl Default for NavDirection {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum NavJump {
     First,
     Last,
@@ -139,6 +274,14 @@ pub enum NavJump {
     Scroll(NavScroll),
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NavGesture {
+    /// (source widget id, pointer location in ui space)
+    LongPress(WidgetId, Vec2),
+    /// (source widget id, swipe direction, pointer travel in ui space)
+    Swipe(WidgetId, NavDirection, Vec2),
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NavTextChange {
     InsertCharacter(char),
@@ -146,6 +289,13 @@ pub enum NavTextChange {
     MoveCursorRight,
     MoveCursorStart,
     MoveCursorEnd,
+    /// Places cursor before character (not byte) at given index (e.g. where text input got
+    /// clicked), applied also to text input that is not focused.
+    ///
+    /// Clicks give index of caret in text of the text box rendered by text input, so inputs that
+    /// render text other than their value (e.g. with cursor character inserted into it) get
+    /// indices shifted by that difference.
+    MoveCursorTo(usize),
     DeleteLeft,
     DeleteRight,
     NewLine,
@@ -416,7 +566,34 @@ pub fn use_nav_jump_step_pages_active(context: &mut WidgetContext) {
     context.props.write(NavJumpActive(NavJumpMode::StepPages));
 }
 
+/// Tells interactions engine when [`InteractiveDisabled`] prop of widget changes, keeping last
+/// sent value in widget state. Hooks that write whole widget state in the same frame have to
+/// carry current [`InteractiveDisabled`] prop over, otherwise it gets signaled again.
+pub fn use_nav_disabled(context: &mut WidgetContext) {
+    context.life_cycle.mount(|context| {
+        if let Ok(InteractiveDisabled(true)) = context.props.read() {
+            context.signals.write(NavSignal::Disabled(true));
+            let _ = context.state.write_with(InteractiveDisabled(true));
+        }
+    });
+
+    context.life_cycle.change(|context| {
+        let disabled = context
+            .props
+            .map_or_default::<InteractiveDisabled, _, _>(|p| p.0);
+        let prev = context
+            .state
+            .map_or_default::<InteractiveDisabled, _, _>(|p| p.0);
+        if disabled != prev {
+            context.signals.write(NavSignal::Disabled(disabled));
+            let _ = context.state.write_with(InteractiveDisabled(disabled));
+        }
+    });
+}
+
 pub fn use_nav_item(context: &mut WidgetContext) {
+    context.use_hook(use_nav_disabled);
+
     context.life_cycle.mount(|context| {
         if context.props.has::<NavItemActive>() {
             context.signals.write(NavSignal::Register(NavType::Item));
@@ -467,6 +644,56 @@ pub fn use_nav_text_input(context: &mut WidgetContext) {
     });
 }
 
+/// Makes widget receive [`NavSignal::Back`] of its selected descendants (or any, when nothing is
+/// selected and it is the most recently mounted back handler), so it can e.g. close a dialog.
+pub fn use_nav_back_handler(context: &mut WidgetContext) {
+    context.life_cycle.mount(|context| {
+        context
+            .signals
+            .write(NavSignal::Register(NavType::BackHandler));
+    });
+
+    context.life_cycle.unmount(|context| {
+        context
+            .signals
+            .write(NavSignal::Unregister(NavType::BackHandler));
+    });
+}
+
+/// Makes widget stop [`NavEvent`]s of its descendants in given phase, for as long as it is
+/// mounted.
+pub fn use_nav_event_stop(phase: NavEventPhase) -> impl FnMut(&mut WidgetContext) {
+    move |context| {
+        context.life_cycle.mount(move |context| {
+            context
+                .signals
+                .write(NavSignal::Register(NavType::EventStop(phase)));
+        });
+
+        context.life_cycle.unmount(move |context| {
+            context
+                .signals
+                .write(NavSignal::Unregister(NavType::EventStop(phase)));
+        });
+    }
+}
+
+/// Keeps selection within widget subtree for as long as it is mounted (e.g. modal dialogs), then
+/// restores selection that was there before.
+pub fn use_nav_focus_trap(context: &mut WidgetContext) {
+    context.life_cycle.mount(|context| {
+        context
+            .signals
+            .write(NavSignal::Register(NavType::FocusTrap));
+    });
+
+    context.life_cycle.unmount(|context| {
+        context
+            .signals
+            .write(NavSignal::Unregister(NavType::FocusTrap));
+    });
+}
+
 pub fn use_nav_scroll_view(context: &mut WidgetContext) {
     context.life_cycle.mount(|context| {
         context
@@ -7,7 +7,7 @@ use crate::{
         utils::Vec2,
         WidgetId, WidgetIdOrRef,
     },
-    MessageData, PropsData,
+    MessageData, PropsData, Scalar,
 };
 use serde::{Deserialize, Serialize};
 
@@ -15,14 +15,36 @@ fn is_zero(v: &Vec2) -> bool {
     v.x.abs() < 1.0e-6 && v.y.abs() < 1.0e-6
 }
 
+/// State of scroll view, kept in its `State` and sent with [`ScrollViewNotifyMessage`].
+///
+/// Content and viewport sizes are provided by interactions engine whenever layout changes them,
+/// so together with `offset` they reflect the latest layout.
 #[derive(PropsData, Debug, Default, Clone, Serialize, Deserialize)]
 #[props_data(crate::props::PropsData)]
 #[prefab(crate::Prefab)]
 pub struct ScrollViewState {
+    /// Scroll position as factor of scrollable distance (0 at start, 1 at end).
     #[serde(default)]
     pub value: Vec2,
+    /// Content to viewport size ratio (above 1 on axes content does not fit).
     #[serde(default)]
     pub size_factor: Vec2,
+    /// Size of scrolled content in local space units.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_zero")]
+    pub content_size: Vec2,
+    /// Size of area content is scrolled within, in local space units.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_zero")]
+    pub viewport_size: Vec2,
+    /// Scroll position in local space units, that is `value` of scrollable distance.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_zero")]
+    pub offset: Vec2,
+    /// Scroll value change per second, used while coasting with [`ScrollViewMomentum`].
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_zero")]
+    pub velocity: Vec2,
 }
 
 #[derive(PropsData, Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +68,39 @@ impl Default for ScrollViewRange {
     }
 }
 
+/// Enables inertial scrolling: after relative scroll input stops, scroll view keeps moving with
+/// the velocity of the last input, slowing down over time.
+#[derive(PropsData, Debug, Copy, Clone, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub struct ScrollViewMomentum {
+    /// Rate of exponential velocity decay per second.
+    #[serde(default = "ScrollViewMomentum::default_friction")]
+    pub friction: Scalar,
+    /// Speed below which scroll view stops coasting.
+    #[serde(default = "ScrollViewMomentum::default_threshold")]
+    pub threshold: Scalar,
+}
+
+impl ScrollViewMomentum {
+    fn default_friction() -> Scalar {
+        4.0
+    }
+
+    fn default_threshold() -> Scalar {
+        0.01
+    }
+}
+
+impl Default for ScrollViewMomentum {
+    fn default() -> Self {
+        Self {
+            friction: Self::default_friction(),
+            threshold: Self::default_threshold(),
+        }
+    }
+}
+
 #[derive(PropsData, Debug, Default, Clone, Serialize, Deserialize)]
 #[props_data(crate::props::PropsData)]
 #[prefab(crate::Prefab)]
@@ -55,6 +110,14 @@ pub struct ScrollViewNotifyProps(
     pub WidgetIdOrRef,
 );
 
+/// Sent by interactions engine to scroll view when its content or viewport size changes.
+#[derive(MessageData, Debug, Default, Copy, Clone, PartialEq)]
+#[message_data(crate::messenger::MessageData)]
+pub struct ScrollViewMetricsMessage {
+    pub content_size: Vec2,
+    pub viewport_size: Vec2,
+}
+
 #[derive(MessageData, Debug, Clone)]
 #[message_data(crate::messenger::MessageData)]
 pub struct ScrollViewNotifyMessage {
@@ -98,9 +161,30 @@ pub fn use_scroll_view(context: &mut WidgetContext) {
 
     context.life_cycle.change(|context| {
         let mut data = context.state.read_cloned_or_default::<ScrollViewState>();
-        let range = context.props.read::<ScrollViewRange>();
+        let range = context.props.read::<ScrollViewRange>().ok();
+        let momentum = context.props.read_cloned::<ScrollViewMomentum>().ok();
+        let delta_time = context.animator.delta_time();
         let mut dirty = false;
+        let mut input = false;
         for msg in context.messenger.messages {
+            if let Some(msg) = msg.as_any().downcast_ref::<ScrollViewMetricsMessage>() {
+                data.content_size = msg.content_size;
+                data.viewport_size = msg.viewport_size;
+                data.size_factor = Vec2 {
+                    x: if msg.viewport_size.x > 0.0 {
+                        msg.content_size.x / msg.viewport_size.x
+                    } else {
+                        0.0
+                    },
+                    y: if msg.viewport_size.y > 0.0 {
+                        msg.content_size.y / msg.viewport_size.y
+                    } else {
+                        0.0
+                    },
+                };
+                clamp(&mut data, range);
+                dirty = true;
+            }
             if let Some(NavSignal::Jump(NavJump::Scroll(NavScroll::Change(
                 value,
                 factor,
@@ -113,21 +197,41 @@ pub fn use_scroll_view(context: &mut WidgetContext) {
                 } else {
                     data.value = *value;
                 }
-                if factor.x <= 1.0 {
-                    data.value.x = 0.0;
-                }
-                if factor.y <= 1.0 {
-                    data.value.y = 0.0;
+                data.size_factor = *factor;
+                if momentum.is_some() && *relative && delta_time > 0.0 {
+                    data.velocity = Vec2 {
+                        x: value.x / delta_time,
+                        y: value.y / delta_time,
+                    };
+                } else {
+                    data.velocity = Vec2::default();
                 }
-                if let Ok(range) = &range {
-                    data.value.x = data.value.x.max(range.from.x).min(range.to.x);
-                    data.value.y = data.value.y.max(range.from.y).min(range.to.y);
+                clamp(&mut data, range);
+                dirty = true;
+                input = true;
+            }
+        }
+        if let Some(momentum) = momentum {
+            if !input && !is_zero(&data.velocity) && delta_time > 0.0 {
+                data.value.x += data.velocity.x * delta_time;
+                data.value.y += data.velocity.y * delta_time;
+                let decay = (-momentum.friction.max(0.0) * delta_time).exp();
+                data.velocity.x *= decay;
+                data.velocity.y *= decay;
+                let speed =
+                    (data.velocity.x * data.velocity.x + data.velocity.y * data.velocity.y).sqrt();
+                if speed < momentum.threshold {
+                    data.velocity = Vec2::default();
                 }
-                data.size_factor = *factor;
+                clamp(&mut data, range);
                 dirty = true;
             }
         }
         if dirty {
+            data.offset = Vec2 {
+                x: data.value.x * (data.content_size.x - data.viewport_size.x).max(0.0),
+                y: data.value.y * (data.content_size.y - data.viewport_size.y).max(0.0),
+            };
             notify(
                 &context,
                 ScrollViewNotifyMessage {
@@ -139,3 +243,161 @@ pub fn use_scroll_view(context: &mut WidgetContext) {
         }
     });
 }
+
+fn clamp(data: &mut ScrollViewState, range: Option<&ScrollViewRange>) {
+    if data.size_factor.x <= 1.0 {
+        data.value.x = 0.0;
+        data.velocity.x = 0.0;
+    }
+    if data.size_factor.y <= 1.0 {
+        data.value.y = 0.0;
+        data.velocity.y = 0.0;
+    }
+    if let Some(range) = range {
+        let x = data.value.x.max(range.from.x).min(range.to.x);
+        let y = data.value.y.max(range.from.y).min(range.to.y);
+        // coasting stops at range edges.
+        if x != data.value.x {
+            data.velocity.x = 0.0;
+        }
+        if y != data.value.y {
+            data.velocity.y = 0.0;
+        }
+        data.value = Vec2 { x, y };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        interactive::default_interactions_engine::{DefaultInteractionsEngine, Interaction},
+        layout::{default_layout_engine::DefaultLayoutEngine, CoordsMapping},
+        widget,
+        widget::{
+            component::containers::{
+                content_box::nav_content_box,
+                scroll_box::nav_scroll_box,
+                size_box::{size_box, SizeBoxProps},
+            },
+            node::WidgetNode,
+            unit::size::SizeBoxSizeValue,
+            utils::Rect,
+            WidgetRef,
+        },
+    };
+
+    #[pre_hooks(use_scroll_view)]
+    fn view(mut context: WidgetContext) -> WidgetNode {
+        widget! {()}
+    }
+
+    #[test]
+    fn test_scroll_view_momentum() {
+        let idref = WidgetRef::new();
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"view"} | {idref.clone()} view: {ScrollViewMomentum::default()})
+        });
+        application.animations_delta_time = 0.1;
+        application.process();
+        let id = idref.read().unwrap();
+        let read = |application: &Application| {
+            application
+                .state_read(&id)
+                .unwrap()
+                .read_cloned::<ScrollViewState>()
+                .unwrap()
+        };
+
+        application.send_message(
+            &id,
+            NavSignal::Jump(NavJump::Scroll(NavScroll::Change(
+                Vec2 { x: 0.0, y: 0.05 },
+                Vec2 { x: 1.0, y: 2.0 },
+                true,
+            ))),
+        );
+        application.process();
+        // state written while handling input is applied in the next process.
+        application.process();
+        let state = read(&application);
+        assert!((state.value.y - 0.05).abs() < 1.0e-6);
+        assert!((state.velocity.y - 0.5).abs() < 1.0e-6);
+
+        let mut prev = state.value.y;
+        let mut frames = 0;
+        while application.process() {
+            let state = read(&application);
+            assert!(state.value.y >= prev);
+            assert_eq!(state.value.x, 0.0);
+            prev = state.value.y;
+            frames += 1;
+            assert!(frames < 100);
+        }
+        let state = read(&application);
+        assert!(frames > 1);
+        assert!(state.value.y > 0.1);
+        assert!(state.value.y < 0.25);
+        assert_eq!(state.velocity, Vec2::default());
+    }
+
+    #[test]
+    fn test_scroll_view_metrics() {
+        let idref = WidgetRef::new();
+        let content_props = SizeBoxProps {
+            width: SizeBoxSizeValue::Exact(150.0),
+            height: SizeBoxSizeValue::Exact(400.0),
+            ..Default::default()
+        };
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"app"} nav_content_box [
+                (#{"view"} | {idref.clone()} nav_scroll_box {
+                    content = (#{"content"} size_box: {content_props})
+                })
+            ])
+        });
+        let mapping = CoordsMapping::new(Rect::from((100.0, 100.0)));
+        let mut interactions = DefaultInteractionsEngine::new();
+        let mut run = |application: &mut Application| {
+            application.forced_process();
+            application
+                .layout(&mapping, &mut DefaultLayoutEngine)
+                .unwrap();
+            interactions.interact(Interaction::None);
+            application.interact(&mut interactions).unwrap();
+        };
+        for _ in 0..3 {
+            run(&mut application);
+        }
+        let id = idref.read().unwrap();
+        let read = |application: &Application| {
+            application
+                .state_read(&id)
+                .unwrap()
+                .read_cloned::<ScrollViewState>()
+                .unwrap()
+        };
+        let state = read(&application);
+        assert_eq!(state.content_size, Vec2 { x: 150.0, y: 400.0 });
+        assert_eq!(state.viewport_size, Vec2 { x: 100.0, y: 100.0 });
+        assert_eq!(state.size_factor, Vec2 { x: 1.5, y: 4.0 });
+        assert_eq!(state.offset, Vec2::default());
+
+        application.send_message(
+            &id,
+            NavSignal::Jump(NavJump::Scroll(NavScroll::Change(
+                Vec2 { x: 1.0, y: 0.5 },
+                state.size_factor,
+                false,
+            ))),
+        );
+        for _ in 0..2 {
+            run(&mut application);
+        }
+        let state = read(&application);
+        assert_eq!(state.offset, Vec2 { x: 50.0, y: 150.0 });
+    }
+}
@@ -1,4 +1,6 @@
 pub mod containers;
+pub mod error_boundary;
+pub mod fragment;
 pub mod image_box;
 pub mod interactive;
 pub mod space_box;
@@ -16,7 +18,11 @@ use crate::{
     MessageData, PrefabValue, PropsData, Scalar,
 };
 use serde::{Deserialize, Serialize};
-use std::{any::TypeId, collections::HashMap, convert::TryFrom};
+use std::{
+    any::{Any, TypeId},
+    collections::BTreeMap,
+    convert::TryFrom,
+};
 
 fn is_false(v: &bool) -> bool {
     !*v
@@ -138,6 +144,98 @@ pub fn use_relative_layout_listener(context: &mut WidgetContext) {
     });
 }
 
+/// Marks widget to be skipped by application processing, along with everything it renders.
+///
+/// Deferred widget component is not invoked and renders nothing, but it stays mounted: its state
+/// and states of widgets it rendered before are kept until it is processed again. Containers can
+/// put it on hidden slots, so these do not run their life cycle while hidden. Messages sent to
+/// deferred widgets are kept and delivered once they get processed again.
+#[derive(PropsData, Debug, Default, Copy, Clone, Serialize, Deserialize)]
+#[props_data(crate::props::PropsData)]
+#[prefab(crate::Prefab)]
+pub struct DeferredSlot;
+
+/// Sent to widget every time its interval elapses.
+#[derive(MessageData, Debug, Copy, Clone, PartialEq)]
+#[message_data(crate::messenger::MessageData)]
+pub struct IntervalMessage;
+
+/// Sends [`IntervalMessage`] to widget every `duration` seconds of accumulated animations delta
+/// time. Time left over from one tick carries to the next one (so ticks do not drift) and when
+/// application processing was throttled, all ticks that elapsed meanwhile are delivered at once.
+///
+/// # Example
+///
+/// ```
+/// # use raui_core::prelude::*;
+/// fn caret(mut context: WidgetContext) -> WidgetNode {
+///     context.use_hook(use_interval(0.5));
+///     context.life_cycle.change(|context| {
+///         for msg in context.messenger.messages {
+///             if msg.as_any().downcast_ref::<IntervalMessage>().is_some() {
+///                 // blink caret.
+///             }
+///         }
+///     });
+///     widget! { () }
+/// }
+/// ```
+pub fn use_interval(duration: Scalar) -> impl FnMut(&mut WidgetContext) {
+    move |context| {
+        context.life_cycle.interval(duration);
+    }
+}
+
+/// Widget state holding last value of props watched by [`use_prop_change`].
+#[derive(Debug, Clone)]
+pub struct PropChangeWatch<T>(pub T);
+
+impl<T> PropsData for PropChangeWatch<T>
+where
+    T: 'static + PropsData + Clone,
+{
+    fn clone_props(&self) -> Box<dyn PropsData> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Watches `T` props of the widget and calls `f` with old and new value whenever they differ from
+/// the ones seen on previous render. Last seen value is stored in widget state, so first render
+/// only remembers it without calling `f`.
+///
+/// # Example
+///
+/// ```
+/// # use raui_core::prelude::*;
+/// fn counter(mut context: WidgetContext) -> WidgetNode {
+///     use_prop_change::<usize, _>(&mut context, |old, new| {
+///         println!("counter changed from {} to {}", old, new);
+///     });
+///     widget! { () }
+/// }
+/// ```
+pub fn use_prop_change<T, F>(context: &mut WidgetContext, f: F)
+where
+    T: 'static + PropsData + Clone + PartialEq,
+    F: FnOnce(&T, &T),
+{
+    let value = match context.props.read::<T>() {
+        Ok(value) => value,
+        Err(_) => return,
+    };
+    if let Ok(PropChangeWatch(last)) = context.state.read::<PropChangeWatch<T>>() {
+        if last == value {
+            return;
+        }
+        f(last, value);
+    }
+    let _ = context.state.write_with(PropChangeWatch(value.clone()));
+}
+
 #[derive(PropsData, Debug, Copy, Clone, Serialize, Deserialize)]
 #[props_data(crate::props::PropsData)]
 #[prefab(crate::Prefab)]
@@ -164,7 +262,8 @@ pub struct WidgetComponent {
     pub props: Props,
     pub shared_props: Option<Props>,
     pub listed_slots: Vec<WidgetNode>,
-    pub named_slots: HashMap<String, WidgetNode>,
+    /// Sorted by name, so iterating (and serializing) named slots is deterministic.
+    pub named_slots: BTreeMap<String, WidgetNode>,
 }
 
 impl WidgetComponent {
@@ -177,10 +276,34 @@ impl WidgetComponent {
             props: Props::default(),
             shared_props: None,
             listed_slots: Vec::new(),
-            named_slots: HashMap::new(),
+            named_slots: BTreeMap::new(),
         }
     }
 
+    /// Finishes building component into [`WidgetNode`].
+    ///
+    /// Component methods chain, so together they build the same [`WidgetNode`] the
+    /// [`widget`][crate::widget] macro makes for equivalent input: [`with_props`][Self::with_props]
+    /// and [`with_shared_props`][Self::with_shared_props] add props, while slots methods skip
+    /// empty nodes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use raui_core::prelude::*;
+    /// let node = WidgetComponent::new(content_box, "content_box")
+    ///     .key("list")
+    ///     .with_props(ContentBoxProps::default())
+    ///     .listed_slots((0..3).map(|i| WidgetComponent::new(image_box, "image_box").key(i)))
+    ///     .named_slot("unused", widget! {()})
+    ///     .build();
+    /// assert_eq!(node.as_component().unwrap().listed_slots.len(), 3);
+    /// assert!(node.as_component().unwrap().named_slots.is_empty());
+    /// ```
+    pub fn build(self) -> WidgetNode {
+        WidgetNode::Component(self)
+    }
+
     pub fn key<T>(mut self, v: T) -> Self
     where
         T: ToString,
@@ -232,39 +355,52 @@ impl WidgetComponent {
         self
     }
 
+    /// Appends listed slot, unless node is empty.
     pub fn listed_slot<T>(mut self, v: T) -> Self
     where
         T: Into<WidgetNode>,
     {
-        self.listed_slots.push(v.into());
+        let v = v.into();
+        if v.is_some() {
+            self.listed_slots.push(v);
+        }
         self
     }
 
+    /// Appends listed slots, skipping empty nodes.
     pub fn listed_slots<I, T>(mut self, v: I) -> Self
     where
         I: Iterator<Item = T>,
         T: Into<WidgetNode>,
     {
-        self.listed_slots.extend(v.map(|v| v.into()));
+        self.listed_slots
+            .extend(v.map(|v| v.into()).filter(|v| v.is_some()));
         self
     }
 
+    /// Sets named slot, unless node is empty.
     pub fn named_slot<T>(mut self, k: &str, v: T) -> Self
     where
         T: Into<WidgetNode>,
     {
-        self.named_slots.insert(k.to_owned(), v.into());
+        let v = v.into();
+        if v.is_some() {
+            self.named_slots.insert(k.to_owned(), v);
+        }
         self
     }
 
+    /// Sets named slots, skipping empty nodes.
     pub fn named_slots<I, K, T>(mut self, v: I) -> Self
     where
         I: Iterator<Item = (K, T)>,
         K: ToString,
         T: Into<WidgetNode>,
     {
-        self.named_slots
-            .extend(v.map(|(k, v)| (k.to_string(), v.into())));
+        self.named_slots.extend(
+            v.map(|(k, v)| (k.to_string(), v.into()))
+                .filter(|(_, v)| v.is_some()),
+        );
         self
     }
 
@@ -336,6 +472,6 @@ pub(crate) struct WidgetComponentPrefab {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub listed_slots: Vec<WidgetNodePrefab>,
     #[serde(default)]
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
-    pub named_slots: HashMap<String, WidgetNodePrefab>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub named_slots: BTreeMap<String, WidgetNodePrefab>,
 }
@@ -5,12 +5,13 @@ use crate::{
         context::WidgetContext,
         node::WidgetNode,
         unit::text::{
-            TextBoxDirection, TextBoxFont, TextBoxHorizontalAlign, TextBoxNode, TextBoxSizeValue,
-            TextBoxVerticalAlign,
+            TextBoxAutoSize, TextBoxDirection, TextBoxFont, TextBoxFontSizeMode,
+            TextBoxHorizontalAlign, TextBoxLineHeight, TextBoxNode, TextBoxOverflow,
+            TextBoxSizeValue, TextBoxVerticalAlign, TextBoxWrap,
         },
         utils::{Color, Transform},
     },
-    PropsData,
+    PropsData, Scalar,
 };
 use serde::{Deserialize, Serialize};
 
@@ -33,6 +34,18 @@ pub struct TextBoxProps {
     #[serde(default)]
     pub font: TextBoxFont,
     #[serde(default)]
+    pub font_size_mode: TextBoxFontSizeMode,
+    #[serde(default)]
+    pub line_height: TextBoxLineHeight,
+    #[serde(default)]
+    pub letter_spacing: Scalar,
+    #[serde(default)]
+    pub wrap: TextBoxWrap,
+    #[serde(default)]
+    pub auto_size: Option<TextBoxAutoSize>,
+    #[serde(default)]
+    pub overflow: TextBoxOverflow,
+    #[serde(default)]
     pub color: Color,
     #[serde(default)]
     pub transform: Transform,
@@ -54,6 +67,12 @@ pub fn text_box(context: WidgetContext) -> WidgetNode {
         vertical_align,
         direction,
         font,
+        font_size_mode,
+        line_height,
+        letter_spacing,
+        wrap,
+        auto_size,
+        overflow,
         mut color,
         transform,
     } = props.read_cloned_or_default();
@@ -72,6 +91,12 @@ pub fn text_box(context: WidgetContext) -> WidgetNode {
             vertical_align,
             direction,
             font,
+            font_size_mode,
+            line_height,
+            letter_spacing,
+            wrap,
+            auto_size,
+            overflow,
             color,
             transform,
         }
@@ -6,8 +6,9 @@ use crate::{
     signals::SignalSender,
     state::State,
     widget::{node::WidgetNode, WidgetId, WidgetLifeCycle, WidgetRef},
+    Scalar,
 };
-use std::collections::HashMap;
+use std::{collections::BTreeMap, hash::Hash};
 
 pub struct WidgetContext<'a, 'b> {
     pub id: &'a WidgetId,
@@ -18,13 +19,18 @@ pub struct WidgetContext<'a, 'b> {
     pub state: State<'a>,
     pub animator: &'a AnimatorStates,
     pub life_cycle: &'a mut WidgetLifeCycle,
-    pub named_slots: HashMap<String, WidgetNode>,
+    pub named_slots: BTreeMap<String, WidgetNode>,
     pub listed_slots: Vec<WidgetNode>,
+    /// Application time accumulated from scaled animations delta time, same for all widgets
+    /// processed within single frame.
+    pub time: f64,
+    /// Scaled animations delta time of current frame.
+    pub delta_time: Scalar,
     pub process_context: &'a mut ProcessContext<'b>,
 }
 
 impl<'a, 'b> WidgetContext<'a, 'b> {
-    pub fn take_named_slots(&mut self) -> HashMap<String, WidgetNode> {
+    pub fn take_named_slots(&mut self) -> BTreeMap<String, WidgetNode> {
         std::mem::take(&mut self.named_slots)
     }
 
@@ -43,6 +49,27 @@ impl<'a, 'b> WidgetContext<'a, 'b> {
         (f)(self);
         self
     }
+
+    /// Shorthand for [`WidgetLifeCycle::effect`].
+    pub fn use_effect<D, F>(&mut self, deps: D, f: F) -> &mut Self
+    where
+        D: Hash,
+        F: 'static + FnOnce(WidgetMountOrChangeContext) + Send + Sync,
+    {
+        self.life_cycle.effect(deps, f);
+        self
+    }
+
+    /// Shorthand for [`WidgetLifeCycle::effect_with_cleanup`].
+    pub fn use_effect_with_cleanup<D, F, C>(&mut self, deps: D, f: F) -> &mut Self
+    where
+        D: Hash,
+        F: 'static + FnOnce(WidgetMountOrChangeContext) -> C + Send + Sync,
+        C: 'static + FnOnce(WidgetUnmountContext) + Send + Sync,
+    {
+        self.life_cycle.effect_with_cleanup(deps, f);
+        self
+    }
 }
 
 impl<'a, 'b> std::fmt::Debug for WidgetContext<'a, 'b> {
@@ -13,7 +13,7 @@ use crate::{
         context::{WidgetContext, WidgetMountOrChangeContext, WidgetUnmountContext},
         node::WidgetNode,
     },
-    Prefab, PropsData,
+    Prefab, PropsData, Scalar,
 };
 use serde::{Deserialize, Serialize};
 use std::{
@@ -34,11 +34,14 @@ impl From<WidgetId> for WidgetIdDef {
     }
 }
 
+/// Identifier of widget made of its type name and path of keys.
+///
+/// Id text is shared between clones, so cloning it is cheap.
 #[derive(PropsData, Default, Hash, Eq, PartialEq, Clone, Serialize, Deserialize)]
 #[serde(try_from = "WidgetIdDef")]
 #[serde(into = "WidgetIdDef")]
 pub struct WidgetId {
-    id: String,
+    id: Arc<str>,
     type_name_len: u8,
     key_len: u8,
     depth: usize,
@@ -70,7 +73,7 @@ impl WidgetId {
             id.push_str(part);
         }
         Self {
-            id,
+            id: id.into(),
             type_name_len,
             key_len,
             depth,
@@ -89,12 +92,12 @@ impl WidgetId {
 
     #[inline]
     pub fn type_name(&self) -> &str {
-        &self.id.as_str()[0..self.type_name_len as usize]
+        &self.id[0..self.type_name_len as usize]
     }
 
     #[inline]
     pub fn path(&self) -> &str {
-        &self.id.as_str()[(self.type_name_len as usize + 2)..]
+        &self.id[(self.type_name_len as usize + 2)..]
     }
 
     #[inline]
@@ -107,6 +110,32 @@ impl WidgetId {
         self.id[(self.type_name_len as usize + 2)..].split('/')
     }
 
+    /// Id of the widget this one is nested in, or [`None`] for root widget
+    ///
+    /// Ids do not keep types of parents, so parent id has empty type name and should be compared
+    /// with other ids by [`path`][Self::path].
+    pub fn parent(&self) -> Option<Self> {
+        if self.depth < 2 {
+            return None;
+        }
+        let path = self
+            .parts()
+            .take(self.depth - 1)
+            .map(|part| part.to_owned())
+            .collect::<Vec<_>>();
+        Some(Self::new("", &path))
+    }
+
+    /// Returns `true` if `other` widget is nested (at any depth) in this one, compared by paths
+    pub fn is_ancestor_of(&self, other: &Self) -> bool {
+        if self.depth == 0 || other.depth <= self.depth {
+            return false;
+        }
+        let path = self.path();
+        let other = other.path();
+        other.starts_with(path) && other[path.len()..].starts_with('/')
+    }
+
     pub fn hashed_value(&self) -> u64 {
         let mut hasher = DefaultHasher::new();
         self.hash(&mut hasher);
@@ -172,6 +201,10 @@ impl TryFrom<WidgetIdDef> for WidgetId {
     type Error = String;
 
     fn try_from(id: WidgetIdDef) -> Result<Self, Self::Error> {
+        // default id serializes to empty string.
+        if id.0.is_empty() {
+            return Ok(Self::default());
+        }
         match Self::from_str(&id.0) {
             Ok(id) => Ok(id),
             Err(_) => Err(format!("Could not parse id: `{}`", id.0)),
@@ -191,6 +224,25 @@ impl From<WidgetRef> for WidgetRefDef {
     }
 }
 
+/// Shared handle that gets resolved to id of the widget it was passed to.
+///
+/// Create it in a parent, pass it to a child with `| {idref}` in [`widget!`] macro and read it
+/// later to get id of that child, for example to send it messages. It gets written every time the
+/// child gets processed, so it reads `None` until the first process of the child. Clones share the
+/// same id and it can be read from any thread - processing writes it behind a lock, so reads never
+/// see partially written id, but they may see id from either before or after concurrent process.
+///
+/// # Example
+///
+/// ```
+/// # use raui_core::prelude::*;
+/// let idref = WidgetRef::new();
+/// let mut application = Application::new();
+/// application.apply(widget! { (#{"image"} | {idref.clone()} image_box) });
+/// assert!(idref.read().is_none());
+/// application.process();
+/// assert_eq!(idref.read().unwrap().key(), "image");
+/// ```
 #[derive(PropsData, Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(from = "WidgetRefDef")]
 #[serde(into = "WidgetRefDef")]
@@ -201,6 +253,10 @@ impl WidgetRef {
         Default::default()
     }
 
+    pub fn is_resolved(&self) -> bool {
+        self.0.read().map(|data| data.is_some()).unwrap_or_default()
+    }
+
     pub(crate) fn write(&mut self, id: WidgetId) {
         if let Ok(mut data) = self.0.write() {
             *data = Some(id);
@@ -280,11 +336,21 @@ impl From<WidgetRef> for WidgetIdOrRef {
 
 pub type FnWidget = fn(WidgetContext) -> WidgetNode;
 
+/// Cleanup of effect registered with [`WidgetLifeCycle::effect_with_cleanup`].
+pub type WidgetEffectCleanup = Box<dyn FnOnce(WidgetUnmountContext) + Send + Sync>;
+
+pub(crate) type WidgetEffect =
+    Box<dyn FnOnce(WidgetMountOrChangeContext) -> Option<WidgetEffectCleanup> + Send + Sync>;
+
 #[derive(Default)]
 pub struct WidgetLifeCycle {
     mount: Vec<Box<dyn FnMut(WidgetMountOrChangeContext) + Send + Sync>>,
     change: Vec<Box<dyn FnMut(WidgetMountOrChangeContext) + Send + Sync>>,
     unmount: Vec<Box<dyn FnMut(WidgetUnmountContext) + Send + Sync>>,
+    /// (dependencies hash, effect)
+    effects: Vec<(u64, WidgetEffect)>,
+    exit: Option<Scalar>,
+    interval: Option<Scalar>,
 }
 
 impl WidgetLifeCycle {
@@ -302,6 +368,9 @@ impl WidgetLifeCycle {
         self.change.push(Box::new(f));
     }
 
+    /// Registers closure called when widget gets removed from the tree
+    ///
+    /// Widgets removed in the same process get unmounted nested ones first, then by their paths.
     pub fn unmount<F>(&mut self, f: F)
     where
         F: 'static + FnMut(WidgetUnmountContext) + Send + Sync,
@@ -309,6 +378,72 @@ impl WidgetLifeCycle {
         self.unmount.push(Box::new(f));
     }
 
+    /// Keeps widget alive for given duration after it gets removed from its content, flex or grid
+    /// box parent, so it can animate out before it gets unmounted. Widget has to be keyed to be
+    /// tracked. While exiting, widget animator has `"exit"` animation with `"exit"` value that can
+    /// be used to read exit progress.
+    pub fn exit(&mut self, duration: Scalar) {
+        self.exit = Some(duration.max(0.0));
+    }
+
+    pub fn exit_duration(&self) -> Option<Scalar> {
+        self.exit
+    }
+
+    /// Makes widget receive [`IntervalMessage`][component::IntervalMessage] every time given
+    /// amount of animations delta time passes, for as long as widget keeps calling it.
+    pub fn interval(&mut self, duration: Scalar) {
+        self.interval = Some(duration);
+    }
+
+    pub fn interval_duration(&self) -> Option<Scalar> {
+        self.interval
+    }
+
+    /// Runs `f` after widget mounts and after every later render in which `deps` hash differs from
+    /// the one of previous run. Effects are told apart by the order they get registered in, so
+    /// widget should register the same effects on every render.
+    pub fn effect<D, F>(&mut self, deps: D, f: F)
+    where
+        D: Hash,
+        F: 'static + FnOnce(WidgetMountOrChangeContext) + Send + Sync,
+    {
+        self.effects.push((
+            Self::effect_deps_hash(&deps),
+            Box::new(move |context| {
+                f(context);
+                None
+            }),
+        ));
+    }
+
+    /// Same as [`effect`][Self::effect], but cleanup returned by `f` runs before the next run of
+    /// the effect, or when widget unmounts.
+    pub fn effect_with_cleanup<D, F, C>(&mut self, deps: D, f: F)
+    where
+        D: Hash,
+        F: 'static + FnOnce(WidgetMountOrChangeContext) -> C + Send + Sync,
+        C: 'static + FnOnce(WidgetUnmountContext) + Send + Sync,
+    {
+        self.effects.push((
+            Self::effect_deps_hash(&deps),
+            Box::new(move |context| Some(Box::new(f(context)) as WidgetEffectCleanup)),
+        ));
+    }
+
+    fn effect_deps_hash<D>(deps: &D) -> u64
+    where
+        D: Hash,
+    {
+        let mut hasher = DefaultHasher::new();
+        deps.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    pub(crate) fn take_effects(&mut self) -> Vec<(u64, WidgetEffect)> {
+        std::mem::take(&mut self.effects)
+    }
+
     #[allow(clippy::type_complexity)]
     pub fn unwrap(
         self,
@@ -321,81 +456,176 @@ impl WidgetLifeCycle {
             mount,
             change,
             unmount,
+            ..
         } = self;
         (mount, change, unmount)
     }
 }
 
 pub fn setup(app: &mut Application) {
-    app.register_props::<()>("()");
-    app.register_props::<i8>("i8");
-    app.register_props::<i16>("i16");
-    app.register_props::<i32>("i32");
-    app.register_props::<i64>("i64");
-    app.register_props::<i128>("i128");
-    app.register_props::<u8>("u8");
-    app.register_props::<u16>("u16");
-    app.register_props::<u32>("u32");
-    app.register_props::<u64>("u64");
-    app.register_props::<u128>("u128");
-    app.register_props::<f32>("f32");
-    app.register_props::<f64>("f64");
-    app.register_props::<bool>("bool");
-    app.register_props::<String>("String");
-    app.register_props::<component::containers::anchor_box::AnchorProps>("AnchorProps");
-    app.register_props::<component::containers::anchor_box::PivotBoxProps>("PivotBoxProps");
-    app.register_props::<component::containers::content_box::ContentBoxProps>("ContentBoxProps");
-    app.register_props::<component::containers::flex_box::FlexBoxProps>("FlexBoxProps");
-    app.register_props::<component::containers::grid_box::GridBoxProps>("GridBoxProps");
-    app.register_props::<component::containers::horizontal_box::HorizontalBoxProps>(
+    app.register_props_with_default::<()>("()");
+    app.register_props_with_default::<i8>("i8");
+    app.register_props_with_default::<i16>("i16");
+    app.register_props_with_default::<i32>("i32");
+    app.register_props_with_default::<i64>("i64");
+    app.register_props_with_default::<i128>("i128");
+    app.register_props_with_default::<u8>("u8");
+    app.register_props_with_default::<u16>("u16");
+    app.register_props_with_default::<u32>("u32");
+    app.register_props_with_default::<u64>("u64");
+    app.register_props_with_default::<u128>("u128");
+    app.register_props_with_default::<f32>("f32");
+    app.register_props_with_default::<f64>("f64");
+    app.register_props_with_default::<bool>("bool");
+    app.register_props_with_default::<String>("String");
+    app.register_props_with_default::<crate::accessibility::A11yProps>("A11yProps");
+    app.register_props_with_default::<crate::animator::AnimationPrefab>("AnimationPrefab");
+    app.register_props_with_default::<component::containers::anchor_box::AnchorProps>(
+        "AnchorProps",
+    );
+    app.register_props_with_default::<component::containers::anchor_box::PivotBoxProps>(
+        "PivotBoxProps",
+    );
+    app.register_props_with_default::<component::containers::content_box::ContentBoxProps>(
+        "ContentBoxProps",
+    );
+    app.register_props_with_default::<component::containers::flex_box::FlexBoxProps>(
+        "FlexBoxProps",
+    );
+    app.register_props_with_default::<component::containers::grid_box::GridBoxProps>(
+        "GridBoxProps",
+    );
+    app.register_props_with_default::<component::containers::horizontal_box::HorizontalBoxProps>(
         "HorizontalBoxProps",
     );
-    app.register_props::<component::containers::hidden_box::HiddenBoxProps>("HiddenBoxProps");
-    app.register_props::<component::containers::scroll_box::ScrollBoxOwner>("ScrollBoxOwner");
-    app.register_props::<component::containers::scroll_box::SideScrollbarsProps>(
+    app.register_props_with_default::<component::containers::hidden_box::HiddenBoxProps>(
+        "HiddenBoxProps",
+    );
+    app.register_props_with_default::<component::containers::scroll_box::ScrollBoxOwner>(
+        "ScrollBoxOwner",
+    );
+    app.register_props_with_default::<component::containers::scroll_box::SideScrollbarsProps>(
         "SideScrollbarsProps",
     );
-    app.register_props::<component::containers::scroll_box::SideScrollbarsState>(
+    app.register_props_with_default::<component::containers::scroll_box::SideScrollbarsState>(
         "SideScrollbarsState",
     );
-    app.register_props::<component::containers::portal_box::PortalsContainer>("PortalsContainer");
-    app.register_props::<component::containers::size_box::SizeBoxProps>("SizeBoxProps");
-    app.register_props::<component::containers::switch_box::SwitchBoxProps>("SwitchBoxProps");
-    app.register_props::<component::containers::tabs_box::TabsBoxProps>("TabsBoxProps");
-    app.register_props::<component::containers::tabs_box::TabPlateProps>("TabPlateProps");
-    app.register_props::<component::containers::tooltip_box::TooltipState>("TooltipState");
-    app.register_props::<component::containers::variant_box::VariantBoxProps>("VariantBoxProps");
-    app.register_props::<component::containers::vertical_box::VerticalBoxProps>("VerticalBoxProps");
-    app.register_props::<component::containers::wrap_box::WrapBoxProps>("WrapBoxProps");
-    app.register_props::<component::image_box::ImageBoxProps>("ImageBoxProps");
-    app.register_props::<component::interactive::button::ButtonProps>("ButtonProps");
-    app.register_props::<component::interactive::button::ButtonNotifyProps>("ButtonNotifyProps");
-    app.register_props::<component::interactive::input_field::TextInputMode>("TextInputMode");
-    app.register_props::<component::interactive::input_field::TextInputProps>("TextInputProps");
-    app.register_props::<component::interactive::input_field::TextInputNotifyProps>(
+    app.register_props_with_default::<component::containers::scroll_box::ScrollbarProps>(
+        "ScrollbarProps",
+    );
+    app.register_props_with_default::<component::containers::scroll_box::ScrollbarState>(
+        "ScrollbarState",
+    );
+    app.register_props_with_default::<component::containers::modal_box::ModalBoxProps>(
+        "ModalBoxProps",
+    );
+    app.register_props_with_default::<component::containers::modal_box::ModalBoxState>(
+        "ModalBoxState",
+    );
+    app.register_props_with_default::<component::containers::navigator_box::NavigatorBoxProps>(
+        "NavigatorBoxProps",
+    );
+    app.register_props_with_default::<component::containers::navigator_box::NavigatorBoxState>(
+        "NavigatorBoxState",
+    );
+    app.register_props_with_default::<component::containers::navigator_box::NavigatorPageProps>(
+        "NavigatorPageProps",
+    );
+    app.register_props_with_default::<component::containers::portal_box::PortalsContainer>(
+        "PortalsContainer",
+    );
+    app.register_props_with_default::<component::containers::portal_box::PortalLayer>(
+        "PortalLayer",
+    );
+    app.register_props_with_default::<component::containers::size_box::SizeBoxProps>(
+        "SizeBoxProps",
+    );
+    app.register_props_with_default::<component::containers::suspense_box::SuspenseBoxProps>(
+        "SuspenseBoxProps",
+    );
+    app.register_props_with_default::<component::containers::switch_box::SwitchBoxProps>(
+        "SwitchBoxProps",
+    );
+    app.register_props_with_default::<component::containers::tabs_box::TabsBoxProps>(
+        "TabsBoxProps",
+    );
+    app.register_props_with_default::<component::containers::tabs_box::TabPlateProps>(
+        "TabPlateProps",
+    );
+    app.register_props_with_default::<component::containers::tooltip_box::TooltipState>(
+        "TooltipState",
+    );
+    app.register_props_with_default::<component::containers::variant_box::VariantBoxProps>(
+        "VariantBoxProps",
+    );
+    app.register_props_with_default::<component::containers::vertical_box::VerticalBoxProps>(
+        "VerticalBoxProps",
+    );
+    app.register_props_with_default::<component::containers::wrap_box::WrapBoxProps>(
+        "WrapBoxProps",
+    );
+    app.register_props_with_default::<component::image_box::ImageBoxProps>("ImageBoxProps");
+    app.register_props_with_default::<component::interactive::button::ButtonProps>("ButtonProps");
+    app.register_props_with_default::<component::interactive::button::ButtonNotifyProps>(
+        "ButtonNotifyProps",
+    );
+    app.register_props_with_default::<component::interactive::input_field::TextInputMode>(
+        "TextInputMode",
+    );
+    app.register_props_with_default::<component::interactive::input_field::TextInputProps>(
+        "TextInputProps",
+    );
+    app.register_props_with_default::<component::interactive::input_field::TextInputNotifyProps>(
         "TextInputNotifyProps",
     );
-    app.register_props::<component::interactive::navigation::NavItemActive>("NavItemActive");
-    app.register_props::<component::interactive::navigation::NavButtonTrackingActive>(
+    app.register_props_with_default::<component::interactive::navigation::NavItemActive>(
+        "NavItemActive",
+    );
+    app.register_props_with_default::<component::interactive::navigation::NavButtonTrackingActive>(
         "NavButtonTrackingActive",
     );
-    app.register_props::<component::interactive::navigation::NavContainerActive>(
+    app.register_props_with_default::<component::interactive::navigation::NavContainerActive>(
         "NavContainerActive",
     );
-    app.register_props::<component::interactive::navigation::NavJumpLooped>("NavJumpLooped");
-    app.register_props::<component::interactive::navigation::NavJumpMapProps>("NavJumpMapProps");
-    app.register_props::<component::interactive::scroll_view::ScrollViewState>("ScrollViewState");
-    app.register_props::<component::interactive::scroll_view::ScrollViewRange>("ScrollViewRange");
-    app.register_props::<component::interactive::scroll_view::ScrollViewNotifyProps>(
+    app.register_props_with_default::<component::interactive::navigation::NavJumpLooped>(
+        "NavJumpLooped",
+    );
+    app.register_props_with_default::<component::interactive::navigation::InteractiveDisabled>(
+        "InteractiveDisabled",
+    );
+    app.register_props_with_default::<component::interactive::navigation::InteractionState>(
+        "InteractionState",
+    );
+    app.register_props_with_default::<component::interactive::navigation::InteractiveVisualState>(
+        "InteractiveVisualState",
+    );
+    app.register_props_with_default::<component::interactive::navigation::NavJumpMapProps>(
+        "NavJumpMapProps",
+    );
+    app.register_props_with_default::<component::interactive::scroll_view::ScrollViewState>(
+        "ScrollViewState",
+    );
+    app.register_props_with_default::<component::interactive::scroll_view::ScrollViewRange>(
+        "ScrollViewRange",
+    );
+    app.register_props_with_default::<component::interactive::scroll_view::ScrollViewMomentum>(
+        "ScrollViewMomentum",
+    );
+    app.register_props_with_default::<component::interactive::scroll_view::ScrollViewNotifyProps>(
         "ScrollViewNotifyProps",
     );
-    app.register_props::<component::MessageForwardProps>("MessageForwardProps");
-    app.register_props::<component::WidgetAlpha>("WidgetAlpha");
-    app.register_props::<component::space_box::SpaceBoxProps>("SpaceBoxProps");
-    app.register_props::<component::text_box::TextBoxProps>("TextBoxProps");
-    app.register_props::<unit::content::ContentBoxItemLayout>("ContentBoxItemLayout");
-    app.register_props::<unit::flex::FlexBoxItemLayout>("FlexBoxItemLayout");
-    app.register_props::<unit::grid::GridBoxItemLayout>("GridBoxItemLayout");
+    app.register_props_with_default::<component::MessageForwardProps>("MessageForwardProps");
+    app.register_props_with_default::<component::WidgetAlpha>("WidgetAlpha");
+    app.register_props_with_default::<component::DeferredSlot>("DeferredSlot");
+    app.register_props_with_default::<component::fragment::FragmentMarker>("FragmentMarker");
+    app.register_props_with_default::<component::error_boundary::ErrorBoundaryMarker>(
+        "ErrorBoundaryMarker",
+    );
+    app.register_props_with_default::<component::space_box::SpaceBoxProps>("SpaceBoxProps");
+    app.register_props_with_default::<component::text_box::TextBoxProps>("TextBoxProps");
+    app.register_props_with_default::<unit::content::ContentBoxItemLayout>("ContentBoxItemLayout");
+    app.register_props_with_default::<unit::flex::FlexBoxItemLayout>("FlexBoxItemLayout");
+    app.register_props_with_default::<unit::grid::GridBoxItemLayout>("GridBoxItemLayout");
 
     app.register_component("anchor_box", component::containers::anchor_box::anchor_box);
     app.register_component("pivot_box", component::containers::anchor_box::pivot_box);
@@ -433,8 +663,22 @@ pub fn setup(app: &mut Application) {
         "nav_scroll_box_side_scrollbars",
         component::containers::scroll_box::nav_scroll_box_side_scrollbars,
     );
+    app.register_component("scrollbar", component::containers::scroll_box::scrollbar);
+    app.register_component("modal_box", component::containers::modal_box::modal_box);
+    app.register_component(
+        "navigator_box",
+        component::containers::navigator_box::navigator_box,
+    );
+    app.register_component(
+        "navigator_page",
+        component::containers::navigator_box::navigator_page,
+    );
     app.register_component("portal_box", component::containers::portal_box::portal_box);
     app.register_component("size_box", component::containers::size_box::size_box);
+    app.register_component(
+        "suspense_box",
+        component::containers::suspense_box::suspense_box,
+    );
     app.register_component(
         "nav_switch_box",
         component::containers::switch_box::nav_switch_box,
@@ -465,6 +709,11 @@ pub fn setup(app: &mut Application) {
         component::containers::vertical_box::vertical_box,
     );
     app.register_component("wrap_box", component::containers::wrap_box::wrap_box);
+    app.register_component("fragment", component::fragment::fragment_component);
+    app.register_component(
+        "error_boundary",
+        component::error_boundary::error_boundary_component,
+    );
     app.register_component("image_box", component::image_box::image_box);
     app.register_component("button", component::interactive::button::button);
     app.register_component(
@@ -549,6 +798,22 @@ macro_rules! make_widget {
 /// }
 /// # }
 /// ```
+///
+/// Both `widget! {()}` and `widget! {[]}` make absent node, that is skipped when rendering. For
+/// container that renders with no items use [`WidgetNode::empty_container`] or container component
+/// with no listed slots (for example `widget! {(content_box)}`).
+///
+/// Props can have more props merged into them (overriding props of the same type), optionally
+/// only when some condition holds:
+///
+/// ```rust
+/// # use raui_core::prelude::*;
+/// # fn test(disabled: bool) -> WidgetNode {
+/// widget! {
+///     (button: {NavItemActive} + if {disabled} {InteractiveDisabled(true)} + {WidgetAlpha(0.5)})
+/// }
+/// # }
+/// ```
 #[macro_export]
 macro_rules! widget {
     {()} => ($crate::widget::node::WidgetNode::None);
@@ -570,6 +835,9 @@ macro_rules! widget {
             $type_id:path
             $(
                 : {$props:expr}
+                $(
+                    + $( if {$merge_condition:expr} )? {$merge_props:expr}
+                )*
             )?
             $(
                 | {$shared_props:expr}
@@ -609,6 +877,17 @@ macro_rules! widget {
             let mut props = $crate::props::Props::default();
             $(
                 props = $crate::props::Props::from($props);
+                $(
+                    #[allow(unused_assignments)]
+                    #[allow(unused_mut)]
+                    let mut merge = true;
+                    $(
+                        merge = $merge_condition;
+                    )?
+                    if merge {
+                        props.merge_from($crate::props::Props::from($merge_props));
+                    }
+                )*
             )?
             #[allow(unused_assignments)]
             #[allow(unused_mut)]
@@ -617,7 +896,7 @@ macro_rules! widget {
                 shared_props = Some($crate::props::Props::from($shared_props));
             )?
             #[allow(unused_mut)]
-            let mut named_slots = std::collections::HashMap::new();
+            let mut named_slots = std::collections::BTreeMap::new();
             $(
                 $(
                     let widget = $crate::widget!{@wrap $named_slot_widget};
@@ -733,6 +1012,20 @@ macro_rules! unpack_named_slots {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        layout::CoordsMapping,
+        pre_hooks,
+        widget::{
+            component::{
+                containers::content_box::{content_box, ContentBoxProps},
+                image_box::image_box,
+                text_box::{text_box, TextBoxProps},
+                WidgetAlpha, WidgetComponent,
+            },
+            unit::WidgetUnit,
+            utils::Rect,
+        },
+    };
 
     #[test]
     fn test_widget_id() {
@@ -743,4 +1036,203 @@ mod tests {
         assert_eq!(id.key(), "me");
         assert_eq!(id.clone(), id);
     }
+
+    #[test]
+    fn test_widget_id_parent() {
+        let path = |parts: &[&str]| parts.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+        let root = WidgetId::new("root_box", &path(&["app"]));
+        let list = WidgetId::new("list_box", &path(&["app", "list"]));
+        let item = WidgetId::new("image_box", &path(&["app", "list", "item"]));
+        let sibling = WidgetId::new("image_box", &path(&["app", "list2", "item"]));
+
+        assert!(root.parent().is_none());
+        assert!(WidgetId::default().parent().is_none());
+        let parent = item.parent().unwrap();
+        assert_eq!(parent.path(), list.path());
+        assert_eq!(parent.type_name(), "");
+        assert_eq!(parent.key(), "list");
+        assert_eq!(parent.depth(), 2);
+        assert_eq!(parent.parent().unwrap().path(), "app");
+
+        assert!(root.is_ancestor_of(&list));
+        assert!(root.is_ancestor_of(&item));
+        assert!(list.is_ancestor_of(&item));
+        assert!(parent.is_ancestor_of(&item));
+        assert!(!list.is_ancestor_of(&sibling));
+        assert!(!item.is_ancestor_of(&list));
+        assert!(!item.is_ancestor_of(&item));
+        assert!(!WidgetId::default().is_ancestor_of(&item));
+        assert!(!item.is_ancestor_of(&WidgetId::default()));
+    }
+
+    #[test]
+    fn test_widget_id_clone() {
+        fn hash<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let path = (0..16).map(|i| format!("item{}", i)).collect::<Vec<_>>();
+        let id = WidgetId::new("type", &path);
+        let text = id.to_string();
+        // hash of previous `String` based layout.
+        let expected = hash(&(text.to_owned(), id.type_name_len, id.key_len, id.depth));
+        assert_eq!(id.hashed_value(), expected);
+        let clones = (0..10000).map(|_| id.clone()).collect::<Vec<_>>();
+        for clone in &clones {
+            assert!(Arc::ptr_eq(&clone.id, &id.id));
+            assert_eq!(clone, &id);
+            assert_eq!(clone.hashed_value(), expected);
+            assert_eq!(clone.as_ref(), text);
+        }
+        let parsed = WidgetId::from_str(&text).unwrap();
+        assert_eq!(parsed, id);
+        assert_eq!(parsed.hashed_value(), expected);
+        assert_eq!(parsed.parts().count(), 16);
+        assert_eq!(parsed.key(), "item15");
+    }
+
+    #[derive(PropsData, Debug, Default, Clone, Serialize, Deserialize)]
+    #[props_data(crate::props::PropsData)]
+    #[prefab(crate::Prefab)]
+    struct ChildRef(WidgetRef);
+
+    fn use_parent(context: &mut WidgetContext) {
+        context.life_cycle.change(|context| {
+            let ChildRef(idref) = context.props.read_cloned_or_default();
+            for msg in context.messenger.messages {
+                if msg.as_any().downcast_ref::<()>().is_some() {
+                    assert!(context.messenger.write_ref(&idref, ()));
+                }
+            }
+        });
+    }
+
+    #[pre_hooks(use_parent)]
+    fn parent(mut context: WidgetContext) -> WidgetNode {
+        let ChildRef(idref) = context.props.read_cloned_or_default();
+        widget! { (#{"child"} | {idref} child) }
+    }
+
+    fn use_child(context: &mut WidgetContext) {
+        context.life_cycle.change(|context| {
+            for msg in context.messenger.messages {
+                if msg.as_any().downcast_ref::<()>().is_some() {
+                    context.signals.write(());
+                }
+            }
+        });
+    }
+
+    #[pre_hooks(use_child)]
+    fn child(mut context: WidgetContext) -> WidgetNode {
+        image_box(context)
+    }
+
+    #[test]
+    fn test_widget_ref() {
+        let parent_ref = WidgetRef::new();
+        let child_ref = WidgetRef::new();
+        let mut application = Application::new();
+        application.apply(widget! {
+            (#{"parent"} | {parent_ref.clone()} parent: {ChildRef(child_ref.clone())})
+        });
+        assert!(!child_ref.is_resolved());
+        assert!(child_ref.read().is_none());
+        application.process();
+        let child_id = child_ref.read().unwrap();
+        assert_eq!(child_id.type_name(), "child");
+        assert_eq!(child_id.path(), "parent/child");
+        application.send_message(&parent_ref.read().unwrap(), ());
+        application.process();
+        application.process();
+        assert!(application
+            .signals()
+            .iter()
+            .any(|(id, msg)| id == &child_id && msg.as_any().downcast_ref::<()>().is_some()));
+    }
+
+    #[test]
+    fn test_widget_builder() {
+        let props = ContentBoxProps {
+            clipping: true,
+            ..Default::default()
+        };
+        let title_props = TextBoxProps {
+            text: "title".to_owned(),
+            ..Default::default()
+        };
+        let from_macro = widget! {
+            (#{"list"} content_box: {props.clone()} | {WidgetAlpha(0.5)} {
+                header = (#{"title"} text_box: {title_props.clone()})
+                footer = ()
+            } [
+                (#{"a"} image_box)
+                ()
+                (#{"b"} image_box)
+            ])
+        };
+        let from_builder = WidgetComponent::new(content_box, "content_box")
+            .key("list")
+            .with_props(props)
+            .with_shared_props(WidgetAlpha(0.5))
+            .named_slot(
+                "header",
+                WidgetComponent::new(text_box, "text_box")
+                    .key("title")
+                    .with_props(title_props),
+            )
+            .named_slot("footer", WidgetNode::None)
+            .listed_slot(WidgetComponent::new(image_box, "image_box").key("a"))
+            .listed_slot(WidgetNode::None)
+            .listed_slot(WidgetComponent::new(image_box, "image_box").key("b"))
+            .build();
+
+        let mut application = Application::new();
+        application.setup(setup);
+        assert_eq!(
+            application.serialize_node(&from_builder).unwrap(),
+            application.serialize_node(&from_macro).unwrap()
+        );
+        let snapshot = |node| {
+            let mut application = Application::new();
+            application.apply(node);
+            application.process();
+            application.render_snapshot(&CoordsMapping::new(Rect::from((100.0, 100.0))))
+        };
+        assert_eq!(snapshot(from_builder), snapshot(from_macro));
+    }
+
+    #[test]
+    fn test_widget_props_merge() {
+        let props = |node: &WidgetNode| node.as_component().unwrap().props.clone();
+        let text = |value: &str| TextBoxProps {
+            text: value.to_owned(),
+            ..Default::default()
+        };
+        let tree = |condition: bool| {
+            widget! {
+                (#{"text"} text_box: {text("base")}
+                    + if {condition} {text("merged")}
+                    + {WidgetAlpha(0.5)}
+                )
+            }
+        };
+
+        let node = tree(false);
+        assert_eq!(props(&node).read::<TextBoxProps>().unwrap().text, "base");
+        assert_eq!(props(&node).read::<WidgetAlpha>().unwrap().0, 0.5);
+        let node = tree(true);
+        assert_eq!(props(&node).read::<TextBoxProps>().unwrap().text, "merged");
+        assert_eq!(props(&node).read::<WidgetAlpha>().unwrap().0, 0.5);
+
+        let mut application = Application::new();
+        application.apply(node);
+        application.process();
+        match application.rendered_tree() {
+            WidgetUnit::TextBox(unit) => assert_eq!(unit.text, "merged"),
+            _ => panic!("expected text box"),
+        }
+    }
 }
@@ -2,13 +2,23 @@ use crate::{
     props::Props,
     widget::{
         component::{WidgetComponent, WidgetComponentPrefab},
-        unit::{WidgetUnitNode, WidgetUnitNodePrefab},
+        unit::{
+            content::ContentBoxNode, portal::PortalBoxSlotNode, WidgetUnitNode,
+            WidgetUnitNodePrefab,
+        },
+        WidgetId,
     },
     Prefab,
 };
 use serde::{Deserialize, Serialize};
 use std::mem::MaybeUninit;
 
+/// Node of widget tree.
+///
+/// [`WidgetNode::None`] (what `widget! {()}` and `widget! {[]}` make) means absence of node: it
+/// is skipped when rendering and gets no layout. Container unit with no items (see
+/// [`WidgetNode::empty_container`]) is not absent: it renders and gets laid out as any other box,
+/// it just has nothing inside, so [`WidgetNode::is_none`] reports it as some.
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum WidgetNode {
@@ -37,6 +47,30 @@ impl WidgetNode {
         }
     }
 
+    /// Makes content box unit of `id` with no items.
+    pub fn empty_container(id: WidgetId) -> Self {
+        Self::Unit(WidgetUnitNode::ContentBox(ContentBoxNode {
+            id,
+            ..Default::default()
+        }))
+    }
+
+    /// Tells if node is container unit (content, flex or grid box).
+    pub fn is_container(&self) -> bool {
+        match self {
+            Self::Unit(unit) => unit.is_container(),
+            _ => false,
+        }
+    }
+
+    /// Tells if node is container unit with no items.
+    pub fn is_empty_container(&self) -> bool {
+        match self {
+            Self::Unit(unit) => unit.is_empty_container(),
+            _ => false,
+        }
+    }
+
     pub fn as_component(&self) -> Option<&WidgetComponent> {
         match self {
             Self::Component(c) => Some(c),
@@ -113,10 +147,129 @@ impl WidgetNode {
         }
     }
 
+    /// Visits this node and then all its descendants in pre-order: members of tuples, listed and
+    /// named slots of components (in that order) and slots of units.
+    pub fn walk<F>(&self, visitor: &mut F)
+    where
+        F: FnMut(&WidgetNode),
+    {
+        visitor(self);
+        match self {
+            Self::None => {}
+            Self::Component(c) => {
+                for node in c.listed_slots.iter().chain(c.named_slots.values()) {
+                    node.walk(visitor);
+                }
+            }
+            Self::Unit(u) => match u {
+                WidgetUnitNode::None | WidgetUnitNode::ImageBox(_) | WidgetUnitNode::TextBox(_) => {
+                }
+                WidgetUnitNode::AreaBox(u) => u.slot.walk(visitor),
+                WidgetUnitNode::PortalBox(u) => match &*u.slot {
+                    PortalBoxSlotNode::Slot(node) => node.walk(visitor),
+                    PortalBoxSlotNode::ContentItem(item) => item.slot.walk(visitor),
+                    PortalBoxSlotNode::FlexItem(item) => item.slot.walk(visitor),
+                    PortalBoxSlotNode::GridItem(item) => item.slot.walk(visitor),
+                },
+                WidgetUnitNode::ContentBox(u) => {
+                    for item in &u.items {
+                        item.slot.walk(visitor);
+                    }
+                }
+                WidgetUnitNode::FlexBox(u) => {
+                    for item in &u.items {
+                        item.slot.walk(visitor);
+                    }
+                }
+                WidgetUnitNode::GridBox(u) => {
+                    for item in &u.items {
+                        item.slot.walk(visitor);
+                    }
+                }
+                WidgetUnitNode::SizeBox(u) => u.slot.walk(visitor),
+            },
+            Self::Tuple(v) => {
+                for node in v {
+                    node.walk(visitor);
+                }
+            }
+        }
+    }
+
+    /// Same as [`walk`][Self::walk], but allows visitor to modify nodes. Descendants are visited
+    /// after their parent got modified.
+    pub fn walk_mut<F>(&mut self, visitor: &mut F)
+    where
+        F: FnMut(&mut WidgetNode),
+    {
+        visitor(self);
+        match self {
+            Self::None => {}
+            Self::Component(c) => {
+                for node in c.listed_slots.iter_mut().chain(c.named_slots.values_mut()) {
+                    node.walk_mut(visitor);
+                }
+            }
+            Self::Unit(u) => match u {
+                WidgetUnitNode::None | WidgetUnitNode::ImageBox(_) | WidgetUnitNode::TextBox(_) => {
+                }
+                WidgetUnitNode::AreaBox(u) => u.slot.walk_mut(visitor),
+                WidgetUnitNode::PortalBox(u) => match &mut *u.slot {
+                    PortalBoxSlotNode::Slot(node) => node.walk_mut(visitor),
+                    PortalBoxSlotNode::ContentItem(item) => item.slot.walk_mut(visitor),
+                    PortalBoxSlotNode::FlexItem(item) => item.slot.walk_mut(visitor),
+                    PortalBoxSlotNode::GridItem(item) => item.slot.walk_mut(visitor),
+                },
+                WidgetUnitNode::ContentBox(u) => {
+                    for item in &mut u.items {
+                        item.slot.walk_mut(visitor);
+                    }
+                }
+                WidgetUnitNode::FlexBox(u) => {
+                    for item in &mut u.items {
+                        item.slot.walk_mut(visitor);
+                    }
+                }
+                WidgetUnitNode::GridBox(u) => {
+                    for item in &mut u.items {
+                        item.slot.walk_mut(visitor);
+                    }
+                }
+                WidgetUnitNode::SizeBox(u) => u.slot.walk_mut(visitor),
+            },
+            Self::Tuple(v) => {
+                for node in v {
+                    node.walk_mut(visitor);
+                }
+            }
+        }
+    }
+
     pub fn pack_tuple<const N: usize>(data: [WidgetNode; N]) -> Self {
         Self::Tuple(data.into())
     }
 
+    /// Same as [`pack_tuple`][Self::pack_tuple], but component members without their own key
+    /// get one made of tuple `key` and their index (`"{key}-{index}"`), so they keep their ids
+    /// (and with them their state) when the tuple changes its position among others.
+    pub fn pack_tuple_keyed<const N: usize>(key: impl ToString, data: [WidgetNode; N]) -> Self {
+        let key = key.to_string();
+        Self::Tuple(
+            Vec::from(data)
+                .into_iter()
+                .enumerate()
+                .map(|(index, mut node)| {
+                    if let Self::Component(component) = &mut node {
+                        if component.key.is_none() {
+                            component.key = Some(format!("{}-{}", key, index));
+                        }
+                    }
+                    node
+                })
+                .collect(),
+        )
+    }
+
     pub fn unpack_tuple<const N: usize>(self) -> [WidgetNode; N] {
         let mut data: [MaybeUninit<WidgetNode>; N] = unsafe { MaybeUninit::uninit().assume_init() };
         for item in data.iter_mut().take(N) {
@@ -192,3 +345,140 @@ impl Default for WidgetNodePrefab {
 }
 
 impl Prefab for WidgetNodePrefab {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        layout::{default_layout_engine::DefaultLayoutEngine, CoordsMapping},
+        widget,
+        widget::{
+            component::{
+                containers::{content_box::content_box, size_box::size_box},
+                image_box::image_box,
+                text_box::text_box,
+            },
+            unit::{
+                content::{ContentBoxItemNode, ContentBoxNode},
+                image::ImageBoxNode,
+            },
+            utils::Rect,
+        },
+        PropsData,
+    };
+
+    #[derive(PropsData, Debug, Default, Copy, Clone, Serialize, Deserialize)]
+    #[props_data(crate::props::PropsData)]
+    #[prefab(crate::Prefab)]
+    struct Debugged;
+
+    fn tree() -> WidgetNode {
+        let unit = WidgetNode::Unit(WidgetUnitNode::ContentBox(ContentBoxNode {
+            items: vec![
+                ContentBoxItemNode {
+                    slot: widget! { (image_box) },
+                    ..Default::default()
+                },
+                ContentBoxItemNode {
+                    slot: WidgetNode::Unit(WidgetUnitNode::ImageBox(ImageBoxNode::default())),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }));
+        let tuple = WidgetNode::pack_tuple([widget! { (image_box) }, WidgetNode::None]);
+        widget! {
+            (content_box [
+                (size_box {
+                    content = (text_box)
+                })
+                {unit}
+                {tuple}
+            ])
+        }
+    }
+
+    #[test]
+    fn test_walk() {
+        let mut components = vec![];
+        let mut count = 0;
+        tree().walk(&mut |node| {
+            count += 1;
+            if let Some(component) = node.as_component() {
+                components.push(component.type_name.to_owned());
+            }
+        });
+        // root, size box, text box, unit, its two items, tuple and its two members.
+        assert_eq!(count, 9);
+        assert_eq!(
+            components,
+            vec![
+                "content_box",
+                "size_box",
+                "text_box",
+                "image_box",
+                "image_box"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk_mut() {
+        let mut tree = tree();
+        tree.walk_mut(&mut |node| {
+            if let WidgetNode::Component(component) = node {
+                component.props.write(Debugged);
+            }
+        });
+        let mut debugged = 0;
+        let mut components = 0;
+        tree.walk(&mut |node| {
+            if let Some(component) = node.as_component() {
+                components += 1;
+                if component.props.has::<Debugged>() {
+                    debugged += 1;
+                }
+            }
+        });
+        assert_eq!(components, 5);
+        assert_eq!(debugged, components);
+    }
+
+    #[test]
+    fn test_empty_container() {
+        let id = WidgetId::new("content_box", &["app".to_owned()]);
+        let node = WidgetNode::empty_container(id.to_owned());
+        assert!(node.is_some());
+        assert!(!node.is_none());
+        assert!(node.is_container());
+        assert!(node.is_empty_container());
+        assert!(widget! {()}.is_none());
+        assert!(widget! {[]}.is_none());
+        assert!(!widget! {()}.is_empty_container());
+        assert!(!widget! {(image_box)}.is_container());
+
+        let mapping = CoordsMapping::new(Rect::from((100.0, 100.0)));
+        let mut application = Application::new();
+        application.apply(widget! { (#{"app"} content_box) });
+        application.process();
+        application
+            .layout(&mapping, &mut DefaultLayoutEngine)
+            .unwrap();
+        assert!(application.rendered_tree().is_some());
+        assert!(application.rendered_tree().is_empty_container());
+        assert_eq!(
+            application.layout_data().items.get(&id).unwrap().ui_space,
+            Rect::from((100.0, 100.0))
+        );
+
+        application.apply(widget! {()});
+        application.process();
+        application
+            .layout(&mapping, &mut DefaultLayoutEngine)
+            .unwrap();
+        assert!(application.rendered_tree().is_none());
+        assert!(!application.rendered_tree().is_empty_container());
+        assert!(application.layout_data().items.is_empty());
+    }
+}
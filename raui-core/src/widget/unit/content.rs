@@ -9,7 +9,11 @@ use crate::{
     PrefabValue, PropsData, Scalar,
 };
 use serde::{Deserialize, Serialize};
-use std::convert::TryFrom;
+use std::{cmp::Ordering, convert::TryFrom};
+
+fn is_true(v: &bool) -> bool {
+    *v
+}
 
 /// Allows customizing how an item in a [`content_box`] is laid out
 ///
@@ -18,19 +22,48 @@ use std::convert::TryFrom;
 #[props_data(crate::props::PropsData)]
 #[prefab(crate::Prefab)]
 pub struct ContentBoxItemLayout {
+    /// Item edges as factors (0 to 1) of the container size, each axis independently: `left` and
+    /// `right` along X, `top` and `bottom` along Y
+    ///
+    /// Equal anchors on given axis pin item to that point (e.g. `left: 0.5, right: 0.5` centers
+    /// item horizontally), different ones stretch it between them.
     #[serde(default = "ContentBoxItemLayout::default_anchors")]
     pub anchors: Rect,
     /// The margins to put on each side of the item
     #[serde(default)]
     pub margin: Rect,
+    /// Per-axis alignment (0 to 1) of the item laid out size within the anchored area, used
+    /// when item ends up bigger or smaller than that area (e.g. item with exact size pinned to
+    /// a point)
     #[serde(default)]
     pub align: Vec2,
     /// The amount to offset the item from where it would otherwise be laid out
     #[serde(default)]
     pub offset: Vec2,
-    /// The "Z" depth of the item
+    /// The "Z" depth of the item, items with higher depth are drawn on top of the others (see
+    /// [`ContentBox::items_in_draw_order`]) without changing their order in the list (and so
+    /// their ids)
     #[serde(default)]
     pub depth: Scalar,
+    /// Transform of the item around its own laid out rectangle, renderers apply it on top of the
+    /// box transform, so items of the same box can be transformed independently
+    #[serde(default)]
+    pub transform: Transform,
+    /// Relations between item edges and edges of its container or siblings, solved by
+    /// constraint based layout engines (ignored by [`DefaultLayoutEngine`])
+    ///
+    /// [`DefaultLayoutEngine`]: crate::layout::default_layout_engine::DefaultLayoutEngine
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub constraints: Vec<LayoutConstraint>,
+    /// Whether item gets drawn (see [`ContentBox::items_in_draw_order`]) and can be hit by
+    /// pointer. Invisible item stays mounted and laid out, so it keeps its state, unlike item
+    /// removed from the list or hidden with [`hidden_box`]
+    ///
+    /// [`hidden_box`]: crate::widget::component::containers::hidden_box::hidden_box
+    #[serde(default = "ContentBoxItemLayout::default_visible")]
+    #[serde(skip_serializing_if = "is_true")]
+    pub visible: bool,
 }
 
 impl ContentBoxItemLayout {
@@ -42,6 +75,10 @@ impl ContentBoxItemLayout {
             bottom: 1.0,
         }
     }
+
+    fn default_visible() -> bool {
+        true
+    }
 }
 
 impl Default for ContentBoxItemLayout {
@@ -52,10 +89,165 @@ impl Default for ContentBoxItemLayout {
             align: Default::default(),
             offset: Default::default(),
             depth: 0.0,
+            transform: Default::default(),
+            constraints: Default::default(),
+            visible: Self::default_visible(),
         }
     }
 }
 
+/// Edge or dimension of content box item (or its container) used in [`LayoutConstraint`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayoutEdge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    Width,
+    Height,
+    CenterX,
+    CenterY,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayoutRelation {
+    Equal,
+    LessOrEqual,
+    GreaterOrEqual,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for LayoutRelation {
+    fn default() -> Self {
+        Self::Equal
+    }
+}
+
+/// What the constrained item edge is related to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayoutConstraintTarget {
+    /// Only constant value
+    None,
+    /// Container content area (without its padding)
+    Parent,
+    /// Other item of the same container, by its widget key
+    Sibling(String),
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for LayoutConstraintTarget {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+/// How important constraint is - solver breaks weaker constraints first when they conflict
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayoutConstraintStrength {
+    Required,
+    Strong,
+    Medium,
+    Weak,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for LayoutConstraintStrength {
+    fn default() -> Self {
+        Self::Required
+    }
+}
+
+/// Linear relation: `edge` `relation` `target_edge` of `target` * `multiplier` + `constant`
+///
+/// # Example
+/// ```
+/// # use raui_core::prelude::*;
+/// // item width equals width of "a" sibling.
+/// let same_width = LayoutConstraint::relate(
+///     LayoutEdge::Width,
+///     LayoutRelation::Equal,
+///     LayoutConstraintTarget::Sibling("a".to_owned()),
+///     LayoutEdge::Width,
+/// );
+/// // item left edge is at least 10 units from the parent left edge.
+/// let gap = LayoutConstraint::relate(
+///     LayoutEdge::Left,
+///     LayoutRelation::GreaterOrEqual,
+///     LayoutConstraintTarget::Parent,
+///     LayoutEdge::Left,
+/// )
+/// .constant(10.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutConstraint {
+    pub edge: LayoutEdge,
+    #[serde(default)]
+    pub relation: LayoutRelation,
+    #[serde(default)]
+    pub target: LayoutConstraintTarget,
+    #[serde(default = "LayoutConstraint::default_target_edge")]
+    pub target_edge: LayoutEdge,
+    #[serde(default = "LayoutConstraint::default_multiplier")]
+    pub multiplier: Scalar,
+    #[serde(default)]
+    pub constant: Scalar,
+    #[serde(default)]
+    pub strength: LayoutConstraintStrength,
+}
+
+impl LayoutConstraint {
+    fn default_target_edge() -> LayoutEdge {
+        LayoutEdge::Left
+    }
+
+    fn default_multiplier() -> Scalar {
+        1.0
+    }
+
+    pub fn relate(
+        edge: LayoutEdge,
+        relation: LayoutRelation,
+        target: LayoutConstraintTarget,
+        target_edge: LayoutEdge,
+    ) -> Self {
+        Self {
+            edge,
+            relation,
+            target,
+            target_edge,
+            multiplier: Self::default_multiplier(),
+            constant: 0.0,
+            strength: Default::default(),
+        }
+    }
+
+    /// Relation of item edge to constant value
+    pub fn value(edge: LayoutEdge, relation: LayoutRelation, value: Scalar) -> Self {
+        Self::relate(
+            edge,
+            relation,
+            LayoutConstraintTarget::None,
+            Self::default_target_edge(),
+        )
+        .constant(value)
+    }
+
+    pub fn multiplier(mut self, value: Scalar) -> Self {
+        self.multiplier = value;
+        self
+    }
+
+    pub fn constant(mut self, value: Scalar) -> Self {
+        self.constant = value;
+        self
+    }
+
+    pub fn strength(mut self, value: LayoutConstraintStrength) -> Self {
+        self.strength = value;
+        self
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ContentBoxItem {
     #[serde(default)]
@@ -91,10 +283,48 @@ pub struct ContentBox {
     pub items: Vec<ContentBoxItem>,
     #[serde(default)]
     pub clipping: bool,
+    /// Clips only horizontal overflow, implied by `clipping`
+    #[serde(default)]
+    pub clip_x: bool,
+    /// Clips only vertical overflow, implied by `clipping`
+    #[serde(default)]
+    pub clip_y: bool,
+    /// Inset of the box content area, applied to all items on top of their own margins
+    #[serde(default)]
+    pub padding: Rect,
     #[serde(default)]
     pub transform: Transform,
 }
 
+impl ContentBox {
+    /// Whether overflow past left and right edges gets clipped
+    pub fn clips_x(&self) -> bool {
+        self.clipping || self.clip_x
+    }
+
+    /// Whether overflow past top and bottom edges gets clipped
+    pub fn clips_y(&self) -> bool {
+        self.clipping || self.clip_y
+    }
+
+    /// Visible items sorted by their layout `depth` (lowest first), items with the same depth keep
+    /// their list order
+    pub fn items_in_draw_order(&self) -> Vec<&ContentBoxItem> {
+        let mut result = self
+            .items
+            .iter()
+            .filter(|item| item.layout.visible)
+            .collect::<Vec<_>>();
+        result.sort_by(|a, b| {
+            a.layout
+                .depth
+                .partial_cmp(&b.layout.depth)
+                .unwrap_or(Ordering::Equal)
+        });
+        result
+    }
+}
+
 impl WidgetUnitData for ContentBox {
     fn id(&self) -> &WidgetId {
         &self.id
@@ -113,6 +343,9 @@ impl TryFrom<ContentBoxNode> for ContentBox {
             id,
             items,
             clipping,
+            clip_x,
+            clip_y,
+            padding,
             transform,
             ..
         } = node;
@@ -124,6 +357,9 @@ impl TryFrom<ContentBoxNode> for ContentBox {
             id,
             items,
             clipping,
+            clip_x,
+            clip_y,
+            padding,
             transform,
         })
     }
@@ -135,6 +371,9 @@ pub struct ContentBoxNode {
     pub props: Props,
     pub items: Vec<ContentBoxItemNode>,
     pub clipping: bool,
+    pub clip_x: bool,
+    pub clip_y: bool,
+    pub padding: Rect,
     pub transform: Transform,
 }
 
@@ -166,6 +405,12 @@ pub(crate) struct ContentBoxNodePrefab {
     #[serde(default)]
     pub clipping: bool,
     #[serde(default)]
+    pub clip_x: bool,
+    #[serde(default)]
+    pub clip_y: bool,
+    #[serde(default)]
+    pub padding: Rect,
+    #[serde(default)]
     pub transform: Transform,
 }
 
@@ -176,3 +421,159 @@ pub(crate) struct ContentBoxItemNodePrefab {
     #[serde(default)]
     pub layout: ContentBoxItemLayout,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        application::Application,
+        layout::{default_layout_engine::DefaultLayoutEngine, CoordsMapping},
+        widget,
+        widget::{
+            component::{
+                containers::content_box::{content_box, ContentBoxProps},
+                image_box::image_box,
+            },
+            context::WidgetContext,
+            unit::image::ImageBox,
+        },
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn test_items_in_draw_order() {
+        let item = |key: &str, depth| ContentBoxItem {
+            slot: WidgetUnit::ImageBox(ImageBox {
+                id: WidgetId::from_str(&format!("image_box:/root/{}", key)).unwrap(),
+                ..Default::default()
+            }),
+            layout: ContentBoxItemLayout {
+                depth,
+                ..Default::default()
+            },
+        };
+        let unit = ContentBox {
+            items: vec![
+                item("tooltip", 1.0),
+                item("a", 0.0),
+                item("b", 0.0),
+                item("background", -1.0),
+                item("c", 0.0),
+            ],
+            ..Default::default()
+        };
+        let order = unit
+            .items_in_draw_order()
+            .into_iter()
+            .map(|item| item.slot.as_data().unwrap().id().key().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(order, vec!["background", "a", "b", "c", "tooltip"]);
+        assert_eq!(unit.items[0].slot.as_data().unwrap().id().key(), "tooltip");
+    }
+
+    #[test]
+    fn test_item_transform() {
+        let box_transform = Transform {
+            rotation: 0.5,
+            ..Default::default()
+        };
+        let item_props = |rotation, x| {
+            Props::new(ContentBoxItemLayout {
+                transform: Transform {
+                    pivot: Vec2 { x: 0.5, y: 0.5 },
+                    rotation,
+                    scale: Vec2 { x, y: 1.0 },
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+        };
+        let tree = widget! {
+            (#{"cards"} content_box: {ContentBoxProps {
+                transform: box_transform,
+                ..Default::default()
+            }} [
+                (#{"a"} image_box: {item_props(-0.25, 1.0)})
+                (#{"b"} image_box: {item_props(0.25, 2.0)})
+            ])
+        };
+        let mut application = Application::new();
+        application.setup(crate::widget::setup);
+        let prefab = application.serialize_node(&tree).unwrap();
+        application.apply(application.deserialize_node(prefab).unwrap());
+        application.process();
+        let unit = match application.rendered_tree() {
+            WidgetUnit::ContentBox(unit) => unit,
+            _ => panic!("Rendered tree is not content box"),
+        };
+        assert_eq!(unit.transform, box_transform);
+        let transforms = unit
+            .items
+            .iter()
+            .map(|item| {
+                (
+                    item.layout.transform.rotation,
+                    item.layout.transform.scale.x,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(transforms, vec![(-0.25, 1.0), (0.25, 2.0)]);
+    }
+
+    #[test]
+    fn test_item_visibility() {
+        fn stateful(context: WidgetContext) -> WidgetNode {
+            context.life_cycle.mount(|context| {
+                context.signals.write(());
+            });
+            context.life_cycle.change(|context| {
+                for msg in context.messenger.messages {
+                    if let Some(value) = msg.as_any().downcast_ref::<u32>() {
+                        let _ = context.state.write(*value);
+                    }
+                }
+            });
+            image_box(context)
+        }
+
+        let tree = |visible| {
+            let props = ContentBoxItemLayout {
+                visible,
+                ..Default::default()
+            };
+            widget! {
+                (#{"app"} content_box [
+                    (#{"item"} stateful: {props})
+                ])
+            }
+        };
+        let id = WidgetId::new("stateful", &["app".to_owned(), "item".to_owned()]);
+        let mapping = CoordsMapping::new(Rect::from((100.0, 100.0)));
+        let mut application = Application::new();
+        let run = |application: &mut Application, visible| {
+            application.apply(tree(visible));
+            application.process();
+            application.process();
+            application
+                .layout(&mapping, &mut DefaultLayoutEngine)
+                .unwrap();
+            let mounts = application.consume_signals().len();
+            let drawn = match application.rendered_tree() {
+                WidgetUnit::ContentBox(unit) => unit.items_in_draw_order().len(),
+                _ => 0,
+            };
+            let state = application
+                .state_read(&id)
+                .and_then(|state| state.read_cloned::<u32>().ok());
+            (mounts, drawn, state)
+        };
+
+        assert_eq!(run(&mut application, true), (1, 1, None));
+        application.send_message(&id, 42u32);
+        assert_eq!(run(&mut application, true), (0, 1, Some(42)));
+        assert_eq!(run(&mut application, false), (0, 0, Some(42)));
+        assert!(application.layout_data().items.contains_key(&id));
+        assert_eq!(run(&mut application, false), (0, 0, Some(42)));
+        assert_eq!(run(&mut application, true), (0, 1, Some(42)));
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     widget::{
         node::{WidgetNode, WidgetNodePrefab},
         unit::{WidgetUnit, WidgetUnitData},
-        utils::{Rect, Transform},
+        utils::{Rect, Spacing, Transform},
         WidgetId,
     },
     PrefabValue, PropsData, Scalar,
@@ -134,9 +134,12 @@ pub struct FlexBox {
     #[serde(default)]
     pub direction: FlexBoxDirection,
     #[serde(default)]
-    pub separation: Scalar,
+    pub separation: Spacing,
     #[serde(default)]
     pub wrap: bool,
+    /// Inset of the box content area, applied to all items on top of their own margins
+    #[serde(default)]
+    pub padding: Rect,
     #[serde(default)]
     pub transform: Transform,
 }
@@ -161,6 +164,7 @@ impl TryFrom<FlexBoxNode> for FlexBox {
             direction,
             separation,
             wrap,
+            padding,
             transform,
             ..
         } = node;
@@ -174,6 +178,7 @@ impl TryFrom<FlexBoxNode> for FlexBox {
             direction,
             separation,
             wrap,
+            padding,
             transform,
         })
     }
@@ -185,8 +190,9 @@ pub struct FlexBoxNode {
     pub props: Props,
     pub items: Vec<FlexBoxItemNode>,
     pub direction: FlexBoxDirection,
-    pub separation: Scalar,
+    pub separation: Spacing,
     pub wrap: bool,
+    pub padding: Rect,
     pub transform: Transform,
 }
 
@@ -218,10 +224,12 @@ pub(crate) struct FlexBoxNodePrefab {
     #[serde(default)]
     pub direction: FlexBoxDirection,
     #[serde(default)]
-    pub separation: Scalar,
+    pub separation: Spacing,
     #[serde(default)]
     pub wrap: bool,
     #[serde(default)]
+    pub padding: Rect,
+    #[serde(default)]
     pub transform: Transform,
 }
 
@@ -62,6 +62,15 @@ pub struct GridBox {
     pub cols: usize,
     #[serde(default)]
     pub rows: usize,
+    /// Gap between columns, not applied at the outer edges
+    #[serde(default)]
+    pub col_gap: Scalar,
+    /// Gap between rows, not applied at the outer edges
+    #[serde(default)]
+    pub row_gap: Scalar,
+    /// Inset of the box content area, applied to all items on top of their own margins
+    #[serde(default)]
+    pub padding: Rect,
     #[serde(default)]
     pub transform: Transform,
 }
@@ -85,6 +94,9 @@ impl TryFrom<GridBoxNode> for GridBox {
             items,
             cols,
             rows,
+            col_gap,
+            row_gap,
+            padding,
             transform,
             ..
         } = node;
@@ -97,6 +109,9 @@ impl TryFrom<GridBoxNode> for GridBox {
             items,
             cols,
             rows,
+            col_gap,
+            row_gap,
+            padding,
             transform,
         })
     }
@@ -109,6 +124,9 @@ pub struct GridBoxNode {
     pub items: Vec<GridBoxItemNode>,
     pub cols: usize,
     pub rows: usize,
+    pub col_gap: Scalar,
+    pub row_gap: Scalar,
+    pub padding: Rect,
     pub transform: Transform,
 }
 
@@ -142,6 +160,12 @@ pub(crate) struct GridBoxNodePrefab {
     #[serde(default)]
     pub rows: usize,
     #[serde(default)]
+    pub col_gap: Scalar,
+    #[serde(default)]
+    pub row_gap: Scalar,
+    #[serde(default)]
+    pub padding: Rect,
+    #[serde(default)]
     pub transform: Transform,
 }
 
@@ -3,7 +3,7 @@ use crate::{
     widget::{
         node::WidgetNode,
         unit::WidgetUnitData,
-        utils::{Color, Rect, Transform},
+        utils::{lerp, Color, Rect, Transform, Vec2},
         WidgetId,
     },
     PrefabValue, Scalar,
@@ -134,16 +134,65 @@ impl Default for ImageBoxSizeValue {
     }
 }
 
-#[derive(Debug, Default, Copy, Clone, Serialize, Deserialize)]
+/// Keeps image content aspect ratio within the image box area.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub struct ImageBoxAspectRatio {
-    #[serde(default)]
+    /// Horizontal position (0 to 1) of the content within space left over by the aspect ratio,
+    /// 0 puts it at the left edge, 1 at the right one.
+    #[serde(default = "ImageBoxAspectRatio::default_alignment")]
     pub horizontal_alignment: Scalar,
-    #[serde(default)]
+    /// Vertical position (0 to 1) of the content within space left over by the aspect ratio,
+    /// 0 puts it at the top edge, 1 at the bottom one.
+    #[serde(default = "ImageBoxAspectRatio::default_alignment")]
     pub vertical_alignment: Scalar,
+    /// Makes content cover the whole area (overflowing it) instead of fitting inside of it.
     #[serde(default)]
     pub outside: bool,
 }
 
+impl Default for ImageBoxAspectRatio {
+    fn default() -> Self {
+        Self {
+            horizontal_alignment: Self::default_alignment(),
+            vertical_alignment: Self::default_alignment(),
+            outside: false,
+        }
+    }
+}
+
+impl ImageBoxAspectRatio {
+    fn default_alignment() -> Scalar {
+        0.5
+    }
+
+    /// Rectangle of content with given size, scaled to keep its aspect ratio in the `area` and
+    /// aligned within it.
+    pub fn content_rect(&self, area: Rect, content_size: Vec2) -> Rect {
+        let width = area.width();
+        let height = area.height();
+        if content_size.x <= 0.0 || content_size.y <= 0.0 || width <= 0.0 || height <= 0.0 {
+            return area;
+        }
+        let content_aspect = content_size.x / content_size.y;
+        let area_aspect = width / height;
+        let scale = if (content_aspect >= area_aspect) != self.outside {
+            width / content_size.x
+        } else {
+            height / content_size.y
+        };
+        let w = content_size.x * scale;
+        let h = content_size.y * scale;
+        let left = area.left + lerp(0.0, width - w, self.horizontal_alignment);
+        let top = area.top + lerp(0.0, height - h, self.vertical_alignment);
+        Rect {
+            left,
+            right: left + w,
+            top,
+            bottom: top + h,
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ImageBox {
     #[serde(default)]
@@ -236,3 +285,75 @@ pub(crate) struct ImageBoxNodePrefab {
     #[serde(default)]
     pub transform: Transform,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aspect_ratio_alignment() {
+        let area = Rect {
+            left: 10.0,
+            right: 110.0,
+            top: 0.0,
+            bottom: 200.0,
+        };
+        let wide = Vec2 { x: 200.0, y: 100.0 };
+        let rect = |horizontal_alignment, vertical_alignment| {
+            ImageBoxAspectRatio {
+                horizontal_alignment,
+                vertical_alignment,
+                outside: false,
+            }
+            .content_rect(area, wide)
+        };
+
+        assert_eq!(
+            ImageBoxAspectRatio::default().content_rect(area, wide),
+            rect(0.5, 0.5)
+        );
+        assert_eq!(
+            rect(0.5, 0.0),
+            Rect {
+                left: 10.0,
+                right: 110.0,
+                top: 0.0,
+                bottom: 50.0,
+            }
+        );
+        assert_eq!(
+            rect(0.5, 0.5),
+            Rect {
+                left: 10.0,
+                right: 110.0,
+                top: 75.0,
+                bottom: 125.0,
+            }
+        );
+        assert_eq!(
+            rect(0.0, 1.0),
+            Rect {
+                left: 10.0,
+                right: 110.0,
+                top: 150.0,
+                bottom: 200.0,
+            }
+        );
+
+        let cover = ImageBoxAspectRatio {
+            horizontal_alignment: 0.0,
+            vertical_alignment: 0.0,
+            outside: true,
+        }
+        .content_rect(area, wide);
+        assert_eq!(
+            cover,
+            Rect {
+                left: 10.0,
+                right: 410.0,
+                top: 0.0,
+                bottom: 200.0,
+            }
+        );
+    }
+}
@@ -21,6 +21,7 @@ use crate::{
             size::{SizeBox, SizeBoxNode, SizeBoxNodePrefab},
             text::{TextBox, TextBoxNode, TextBoxNodePrefab},
         },
+        utils::Transform,
         WidgetId,
     },
 };
@@ -72,6 +73,25 @@ impl WidgetUnit {
         !matches!(self, Self::None)
     }
 
+    /// Tells if unit is content, flex or grid box, that is one holding list of items.
+    pub fn is_container(&self) -> bool {
+        matches!(
+            self,
+            Self::ContentBox(_) | Self::FlexBox(_) | Self::GridBox(_)
+        )
+    }
+
+    /// Tells if unit is container with no items. Such unit is still laid out and rendered (it is
+    /// not [`WidgetUnit::None`]), just with nothing inside.
+    pub fn is_empty_container(&self) -> bool {
+        match self {
+            Self::ContentBox(v) => v.items.is_empty(),
+            Self::FlexBox(v) => v.items.is_empty(),
+            Self::GridBox(v) => v.items.is_empty(),
+            _ => false,
+        }
+    }
+
     pub fn as_data(&self) -> Option<&dyn WidgetUnitData> {
         match self {
             Self::None => None,
@@ -86,6 +106,20 @@ impl WidgetUnit {
         }
     }
 
+    /// Transform applied to this unit (and its content) around its layout rectangle, if unit
+    /// supports one.
+    pub fn transform(&self) -> Option<&Transform> {
+        match self {
+            Self::ContentBox(v) => Some(&v.transform),
+            Self::FlexBox(v) => Some(&v.transform),
+            Self::GridBox(v) => Some(&v.transform),
+            Self::SizeBox(v) => Some(&v.transform),
+            Self::ImageBox(v) => Some(&v.transform),
+            Self::TextBox(v) => Some(&v.transform),
+            Self::None | Self::AreaBox(_) | Self::PortalBox(_) => None,
+        }
+    }
+
     pub fn inspect(&self) -> Option<WidgetUnitInspectionNode> {
         self.as_data().map(|data| WidgetUnitInspectionNode {
             id: data.id().to_owned(),
@@ -156,6 +190,24 @@ impl WidgetUnitNode {
         !matches!(self, Self::None)
     }
 
+    /// Tells if unit is content, flex or grid box, that is one holding list of items.
+    pub fn is_container(&self) -> bool {
+        matches!(
+            self,
+            Self::ContentBox(_) | Self::FlexBox(_) | Self::GridBox(_)
+        )
+    }
+
+    /// Tells if unit is container with no items. See [`WidgetUnit::is_empty_container`].
+    pub fn is_empty_container(&self) -> bool {
+        match self {
+            Self::ContentBox(v) => v.items.is_empty(),
+            Self::FlexBox(v) => v.items.is_empty(),
+            Self::GridBox(v) => v.items.is_empty(),
+            _ => false,
+        }
+    }
+
     pub fn props(&self) -> Option<&Props> {
         match self {
             Self::None | Self::AreaBox(_) | Self::PortalBox(_) => None,
@@ -6,7 +6,7 @@ use crate::widget::{
         grid::{GridBoxItem, GridBoxItemNode, GridBoxItemNodePrefab},
         WidgetUnit, WidgetUnitData,
     },
-    WidgetId,
+    WidgetId, WidgetRef,
 };
 use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
@@ -66,6 +66,9 @@ pub struct PortalBox {
     pub slot: Box<PortalBoxSlot>,
     #[serde(default)]
     pub owner: WidgetId,
+    /// Stacking layer among portals injected into the same owner (higher layers go on top).
+    #[serde(default)]
+    pub layer: i32,
 }
 
 impl WidgetUnitData for PortalBox {
@@ -87,11 +90,21 @@ impl TryFrom<PortalBoxNode> for PortalBox {
     type Error = ();
 
     fn try_from(node: PortalBoxNode) -> Result<Self, Self::Error> {
-        let PortalBoxNode { id, slot, owner } = node;
+        let PortalBoxNode {
+            id,
+            slot,
+            owner,
+            owner_ref,
+            layer,
+        } = node;
+        // owner reference gets resolved only after whole tree got processed, so owners processed
+        // after their portals are found too.
+        let owner = owner_ref.and_then(|owner| owner.read()).unwrap_or(owner);
         Ok(Self {
             id,
             slot: Box::new(PortalBoxSlot::try_from(*slot)?),
             owner,
+            layer,
         })
     }
 }
@@ -101,6 +114,10 @@ pub struct PortalBoxNode {
     pub id: WidgetId,
     pub slot: Box<PortalBoxSlotNode>,
     pub owner: WidgetId,
+    /// Reference to owner that takes precedence over `owner` once resolved, read when the node
+    /// becomes a unit - that is after all widgets were processed, regardless of their order.
+    pub owner_ref: Option<WidgetRef>,
+    pub layer: i32,
 }
 
 impl From<PortalBoxNode> for WidgetNode {
@@ -117,6 +134,8 @@ pub(crate) struct PortalBoxNodePrefab {
     pub slot: Box<PortalBoxSlotNodePrefab>,
     #[serde(default)]
     pub owner: WidgetId,
+    #[serde(default)]
+    pub layer: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
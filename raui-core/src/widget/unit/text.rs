@@ -1,4 +1,5 @@
 use crate::{
+    layout::CoordsMapping,
     props::Props,
     widget::{
         node::WidgetNode,
@@ -77,6 +78,101 @@ pub struct TextBoxFont {
     pub size: Scalar,
 }
 
+/// How text box font size maps to real (screen) units
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextBoxFontSizeMode {
+    /// Font size is given in real units, so text keeps its size when UI gets scaled.
+    Absolute,
+    /// Font size is given in virtual (layout) units and gets scaled together with the UI, by
+    /// [`CoordsMapping::font_scale`].
+    Scaled,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for TextBoxFontSizeMode {
+    fn default() -> Self {
+        Self::Absolute
+    }
+}
+
+impl TextBoxFontSizeMode {
+    /// Factor that turns font size given in this mode into real units.
+    pub fn scale(&self, mapping: &CoordsMapping) -> Scalar {
+        match self {
+            Self::Absolute => 1.0,
+            Self::Scaled => mapping.font_scale(),
+        }
+    }
+}
+
+/// Distance between baselines of text lines
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TextBoxLineHeight {
+    /// Multiplier of font size.
+    Relative(Scalar),
+    /// Distance in regular units.
+    Exact(Scalar),
+}
+
+impl Default for TextBoxLineHeight {
+    fn default() -> Self {
+        Self::Relative(1.0)
+    }
+}
+
+impl TextBoxLineHeight {
+    /// Line height in regular units for given font size.
+    pub fn resolve(&self, font_size: Scalar) -> Scalar {
+        match self {
+            Self::Relative(v) => font_size * v,
+            Self::Exact(v) => *v,
+        }
+    }
+}
+
+/// Where text gets broken into lines when it does not fit text box width
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextBoxWrap {
+    /// Breaks only at spaces, words longer than the box overflow it.
+    Word,
+    /// Breaks at spaces, and within words that do not fit the box on their own.
+    BreakWord,
+    /// Breaks at any character.
+    Anywhere,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for TextBoxWrap {
+    fn default() -> Self {
+        Self::Word
+    }
+}
+
+/// Shrinks font size of text that does not fit its box, down to `min_size`
+///
+/// Requires layout to measure text, see [`TextFitPass`][crate::layout::text_fit::TextFitPass].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextBoxAutoSize {
+    #[serde(default)]
+    pub min_size: Scalar,
+}
+
+/// What happens to text that does not fit its box (at minimal font size, when auto sized)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextBoxOverflow {
+    /// Text is kept whole and gets clipped by the box.
+    Clip,
+    /// Text is cut and ends with ellipsis so it fits the box.
+    Ellipsis,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for TextBoxOverflow {
+    fn default() -> Self {
+        Self::Clip
+    }
+}
+
 #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub enum TextBoxSizeValue {
     Fill,
@@ -108,11 +204,31 @@ pub struct TextBox {
     #[serde(default)]
     pub font: TextBoxFont,
     #[serde(default)]
+    pub font_size_mode: TextBoxFontSizeMode,
+    #[serde(default)]
+    pub line_height: TextBoxLineHeight,
+    /// Additional space between characters, in regular units.
+    #[serde(default)]
+    pub letter_spacing: Scalar,
+    #[serde(default)]
+    pub wrap: TextBoxWrap,
+    #[serde(default)]
+    pub auto_size: Option<TextBoxAutoSize>,
+    #[serde(default)]
+    pub overflow: TextBoxOverflow,
+    #[serde(default)]
     pub color: Color,
     #[serde(default)]
     pub transform: Transform,
 }
 
+impl TextBox {
+    /// Font size in real units that renderers should use for this text box.
+    pub fn real_font_size(&self, mapping: &CoordsMapping) -> Scalar {
+        self.font.size * self.font_size_mode.scale(mapping)
+    }
+}
+
 impl WidgetUnitData for TextBox {
     fn id(&self) -> &WidgetId {
         &self.id
@@ -132,6 +248,12 @@ impl TryFrom<TextBoxNode> for TextBox {
             vertical_align,
             direction,
             font,
+            font_size_mode,
+            line_height,
+            letter_spacing,
+            wrap,
+            auto_size,
+            overflow,
             color,
             transform,
             ..
@@ -145,6 +267,12 @@ impl TryFrom<TextBoxNode> for TextBox {
             vertical_align,
             direction,
             font,
+            font_size_mode,
+            line_height,
+            letter_spacing,
+            wrap,
+            auto_size,
+            overflow,
             color,
             transform,
         })
@@ -162,6 +290,12 @@ pub struct TextBoxNode {
     pub vertical_align: TextBoxVerticalAlign,
     pub direction: TextBoxDirection,
     pub font: TextBoxFont,
+    pub font_size_mode: TextBoxFontSizeMode,
+    pub line_height: TextBoxLineHeight,
+    pub letter_spacing: Scalar,
+    pub wrap: TextBoxWrap,
+    pub auto_size: Option<TextBoxAutoSize>,
+    pub overflow: TextBoxOverflow,
     pub color: Color,
     pub transform: Transform,
 }
@@ -203,6 +337,19 @@ pub(crate) struct TextBoxNodePrefab {
     #[serde(default)]
     pub font: TextBoxFont,
     #[serde(default)]
+    pub font_size_mode: TextBoxFontSizeMode,
+    #[serde(default)]
+    pub line_height: TextBoxLineHeight,
+    /// Additional space between characters, in regular units.
+    #[serde(default)]
+    pub letter_spacing: Scalar,
+    #[serde(default)]
+    pub wrap: TextBoxWrap,
+    #[serde(default)]
+    pub auto_size: Option<TextBoxAutoSize>,
+    #[serde(default)]
+    pub overflow: TextBoxOverflow,
+    #[serde(default)]
     pub color: Color,
     #[serde(default)]
     pub transform: Transform,
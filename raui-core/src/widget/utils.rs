@@ -210,6 +210,96 @@ impl From<(Integer, Integer)> for IntRect {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpacingUnit {
+    /// Absolute value in layout units.
+    Pixels,
+    /// Percentage (`0` to `100`) of container size along the axis it separates along: main axis
+    /// for items, cross axis for lines of wrapping flex boxes.
+    Percentage,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for SpacingUnit {
+    fn default() -> Self {
+        Self::Pixels
+    }
+}
+
+/// Space between items of a container, either absolute or relative to the container size.
+///
+/// Converts from plain [`Scalar`] as pixels, and deserializes from plain number too, so both
+/// `separation: 10.0` and `separation: Spacing::percentage(5.0)` work.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "SpacingDef")]
+pub struct Spacing {
+    #[serde(default)]
+    pub value: Scalar,
+    #[serde(default)]
+    pub unit: SpacingUnit,
+}
+
+impl Spacing {
+    pub fn pixels(value: Scalar) -> Self {
+        Self {
+            value,
+            unit: SpacingUnit::Pixels,
+        }
+    }
+
+    pub fn percentage(value: Scalar) -> Self {
+        Self {
+            value,
+            unit: SpacingUnit::Percentage,
+        }
+    }
+
+    /// Spacing in layout units within container of `size` along its main axis.
+    #[inline]
+    pub fn resolve(&self, size: Scalar) -> Scalar {
+        match self.unit {
+            SpacingUnit::Pixels => self.value,
+            SpacingUnit::Percentage => size * self.value / 100.0,
+        }
+    }
+}
+
+impl std::fmt::Display for Spacing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.unit {
+            SpacingUnit::Pixels => write!(f, "{}", self.value),
+            SpacingUnit::Percentage => write!(f, "{}%", self.value),
+        }
+    }
+}
+
+impl From<Scalar> for Spacing {
+    fn from(value: Scalar) -> Self {
+        Self::pixels(value)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SpacingDef {
+    Pixels(Scalar),
+    Spacing {
+        #[serde(default)]
+        value: Scalar,
+        #[serde(default)]
+        unit: SpacingUnit,
+    },
+}
+
+impl From<SpacingDef> for Spacing {
+    fn from(data: SpacingDef) -> Self {
+        match data {
+            SpacingDef::Pixels(value) => Self::pixels(value),
+            SpacingDef::Spacing { value, unit } => Self { value, unit },
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(PropsData, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 #[props_data(crate::props::PropsData)]
@@ -264,7 +354,7 @@ pub struct Transform {
     #[serde(default)]
     pub rotation: Scalar,
     /// Scale in regular units.
-    #[serde(default)]
+    #[serde(default = "Transform::default_scale")]
     pub scale: Vec2,
     /// Skewing in radian angle units.
     /// {angle X, angle Y}
@@ -289,6 +379,62 @@ impl Transform {
     fn default_scale() -> Vec2 {
         Vec2 { x: 1.0, y: 1.0 }
     }
+
+    /// Linear part of transform (rotation * scale * skew) as row-major 2x2 matrix.
+    fn matrix(&self) -> [Scalar; 4] {
+        let (sin, cos) = self.rotation.sin_cos();
+        let (skew_x, skew_y) = (self.skew.x.tan(), self.skew.y.tan());
+        let a = self.scale.x;
+        let b = self.scale.x * skew_y;
+        let c = self.scale.y * skew_x;
+        let d = self.scale.y;
+        [
+            cos * a - sin * c,
+            cos * b - sin * d,
+            sin * a + cos * c,
+            sin * b + cos * d,
+        ]
+    }
+
+    /// Maps `point` from untransformed space of `rect` into space where this transform was applied
+    /// around that rectangle (the same way renderers apply transforms).
+    pub fn transform_point(&self, rect: Rect, point: Vec2) -> Vec2 {
+        let size = rect.size();
+        let [m00, m01, m10, m11] = self.matrix();
+        let pivot = Vec2 {
+            x: size.x * self.pivot.x,
+            y: size.y * self.pivot.y,
+        };
+        let x = point.x - rect.left - pivot.x;
+        let y = point.y - rect.top - pivot.y;
+        Vec2 {
+            x: rect.left + size.x * self.align.x + pivot.x + self.translation.x + m00 * x + m01 * y,
+            y: rect.top + size.y * self.align.y + pivot.y + self.translation.y + m10 * x + m11 * y,
+        }
+    }
+
+    /// Maps `point` from space where this transform was applied around `rect` back into the
+    /// untransformed space of that rectangle (the inverse of how renderers apply transforms).
+    ///
+    /// Returns [`None`] if transform cannot be inverted (e.g. has zero scale).
+    pub fn inverse_transform_point(&self, rect: Rect, point: Vec2) -> Option<Vec2> {
+        let size = rect.size();
+        let [m00, m01, m10, m11] = self.matrix();
+        let det = m00 * m11 - m01 * m10;
+        if det.abs() <= Scalar::EPSILON {
+            return None;
+        }
+        let pivot = Vec2 {
+            x: size.x * self.pivot.x,
+            y: size.y * self.pivot.y,
+        };
+        let x = point.x - rect.left - size.x * self.align.x - pivot.x - self.translation.x;
+        let y = point.y - rect.top - size.y * self.align.y - pivot.y - self.translation.y;
+        Some(Vec2 {
+            x: rect.left + pivot.x + (m11 * x - m01 * y) / det,
+            y: rect.top + pivot.y + (m00 * y - m10 * x) / det,
+        })
+    }
 }
 
 #[derive(Debug, Default, Copy, Clone)]
@@ -329,3 +475,143 @@ pub fn lerp(from: Scalar, to: Scalar, factor: Scalar) -> Scalar {
 pub fn lerp_clamped(from: Scalar, to: Scalar, factor: Scalar) -> Scalar {
     lerp(from, to, factor.max(0.0).min(1.0))
 }
+
+/// Linear interpolation of values, used for animating structured props
+pub trait Lerp {
+    /// Value between `self` (at `factor` 0) and `to` (at `factor` 1).
+    fn lerp(&self, to: &Self, factor: Scalar) -> Self;
+}
+
+impl Lerp for Scalar {
+    fn lerp(&self, to: &Self, factor: Scalar) -> Self {
+        lerp(*self, *to, factor)
+    }
+}
+
+impl Lerp for Vec2 {
+    fn lerp(&self, to: &Self, factor: Scalar) -> Self {
+        Self {
+            x: lerp(self.x, to.x, factor),
+            y: lerp(self.y, to.y, factor),
+        }
+    }
+}
+
+impl Lerp for Rect {
+    fn lerp(&self, to: &Self, factor: Scalar) -> Self {
+        Self {
+            left: lerp(self.left, to.left, factor),
+            right: lerp(self.right, to.right, factor),
+            top: lerp(self.top, to.top, factor),
+            bottom: lerp(self.bottom, to.bottom, factor),
+        }
+    }
+}
+
+impl Lerp for Color {
+    fn lerp(&self, to: &Self, factor: Scalar) -> Self {
+        Self {
+            r: lerp(self.r, to.r, factor),
+            g: lerp(self.g, to.g, factor),
+            b: lerp(self.b, to.b, factor),
+            a: lerp(self.a, to.a, factor),
+        }
+    }
+}
+
+impl Lerp for Transform {
+    fn lerp(&self, to: &Self, factor: Scalar) -> Self {
+        Self {
+            pivot: self.pivot.lerp(&to.pivot, factor),
+            align: self.align.lerp(&to.align, factor),
+            translation: self.translation.lerp(&to.translation, factor),
+            rotation: lerp(self.rotation, to.rotation, factor),
+            scale: self.scale.lerp(&to.scale, factor),
+            skew: self.skew.lerp(&to.skew, factor),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lerp() {
+        assert_eq!(2.0.lerp(&4.0, 0.0), 2.0);
+        assert_eq!(2.0.lerp(&4.0, 0.5), 3.0);
+        assert_eq!(2.0.lerp(&4.0, 1.0), 4.0);
+
+        let from = Vec2 { x: 0.0, y: 10.0 };
+        let to = Vec2 { x: 10.0, y: 0.0 };
+        assert_eq!(from.lerp(&to, 0.0), from);
+        assert_eq!(from.lerp(&to, 0.5), Vec2 { x: 5.0, y: 5.0 });
+        assert_eq!(from.lerp(&to, 1.0), to);
+
+        let from = Rect::from((10.0, 20.0));
+        let to = Rect {
+            left: 10.0,
+            right: 30.0,
+            top: 20.0,
+            bottom: 40.0,
+        };
+        assert_eq!(from.lerp(&to, 0.0), from);
+        assert_eq!(
+            from.lerp(&to, 0.5),
+            Rect {
+                left: 5.0,
+                right: 20.0,
+                top: 10.0,
+                bottom: 30.0,
+            }
+        );
+        assert_eq!(from.lerp(&to, 1.0), to);
+
+        let from = Color {
+            r: 1.0,
+            g: 0.0,
+            b: 0.0,
+            a: 0.0,
+        };
+        let to = Color {
+            r: 0.0,
+            g: 1.0,
+            b: 0.5,
+            a: 1.0,
+        };
+        assert_eq!(from.lerp(&to, 0.0), from);
+        assert_eq!(
+            from.lerp(&to, 0.5),
+            Color {
+                r: 0.5,
+                g: 0.5,
+                b: 0.25,
+                a: 0.5,
+            }
+        );
+        assert_eq!(from.lerp(&to, 1.0), to);
+
+        let from = Transform::default();
+        let to = Transform {
+            pivot: 1.0.into(),
+            align: 1.0.into(),
+            translation: Vec2 { x: 10.0, y: -10.0 },
+            rotation: 2.0,
+            scale: 3.0.into(),
+            skew: 0.5.into(),
+        };
+        assert_eq!(from.lerp(&to, 0.0), from);
+        assert_eq!(
+            from.lerp(&to, 0.5),
+            Transform {
+                pivot: 0.5.into(),
+                align: 0.5.into(),
+                translation: Vec2 { x: 5.0, y: -5.0 },
+                rotation: 1.0,
+                scale: 2.0.into(),
+                skew: 0.25.into(),
+            }
+        );
+        assert_eq!(from.lerp(&to, 1.0), to);
+    }
+}
@@ -187,6 +187,7 @@ impl GgezInteractionsEngine {
                 KeyCode::Escape => {
                     self.engine
                         .interact(Interaction::Navigate(NavSignal::Cancel(true)));
+                    self.engine.interact(Interaction::Navigate(NavSignal::Back));
                 }
                 _ => {}
             }
@@ -51,13 +51,7 @@ impl<'a> GgezRenderer<'a> {
             WidgetUnit::None | WidgetUnit::PortalBox(_) => Ok(()),
             WidgetUnit::AreaBox(unit) => self.render_node(&unit.slot, mapping, layout),
             WidgetUnit::ContentBox(unit) => {
-                let mut items = unit
-                    .items
-                    .iter()
-                    .map(|item| (item.layout.depth, item))
-                    .collect::<Vec<_>>();
-                items.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
-                for (_, item) in items {
+                for item in unit.items_in_draw_order() {
                     self.render_node(&item.slot, mapping, layout)?;
                 }
                 Ok(())
@@ -249,29 +243,11 @@ impl<'a> GgezRenderer<'a> {
                             let sfy = source.top;
                             let sty = source.bottom;
                             let rect = if let Some(aspect) = unit.content_keep_aspect_ratio {
-                                let ox = item.ui_space.left;
-                                let oy = item.ui_space.top;
-                                let rw = resource.width() as Scalar;
-                                let rh = resource.height() as Scalar;
-                                let iw = item.ui_space.width();
-                                let ih = item.ui_space.height();
-                                let ra = rw / rh;
-                                let ia = iw / ih;
-                                let scale = if (ra >= ia) != aspect.outside {
-                                    iw / rw
-                                } else {
-                                    ih / rh
+                                let size = Vec2 {
+                                    x: resource.width() as Scalar,
+                                    y: resource.height() as Scalar,
                                 };
-                                let w = rw * scale;
-                                let h = rh * scale;
-                                let ow = lerp(0.0, iw - w, aspect.horizontal_alignment);
-                                let oh = lerp(0.0, ih - h, aspect.vertical_alignment);
-                                Rect {
-                                    left: ox + ow,
-                                    right: ox + ow + w,
-                                    top: oy + oh,
-                                    bottom: oy + oh + h,
-                                }
+                                aspect.content_rect(item.ui_space, size)
                             } else {
                                 item.ui_space
                             };
@@ -456,10 +432,7 @@ impl<'a> GgezRenderer<'a> {
                                 unit.color.a,
                             ),
                         ));
-                        text.set_font(
-                            *resource,
-                            Scale::uniform(unit.font.size * mapping.scale().x),
-                        );
+                        text.set_font(*resource, Scale::uniform(unit.real_font_size(mapping)));
                         text.set_bounds(
                             [rect.width(), rect.height()],
                             match unit.horizontal_align {
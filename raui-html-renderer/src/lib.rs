@@ -272,7 +272,7 @@ impl HtmlRenderer {
             }
             WidgetUnit::ContentBox(ContentBox { items, .. }) => {
                 node!(self: div [writer] level={level} {
-                    for item in items {
+                    for item in items.iter().filter(|item| item.layout.visible) {
                         self.write_node(writer, &item.slot, level)?;
                     }
                 } (writer, level));
@@ -102,6 +102,8 @@ fn text_field_paper_content(context: WidgetContext) -> WidgetNode {
     } else {
         text.trim().to_owned()
     };
+    // clicks place cursor by caret index in rendered text, so after the cursor character they are
+    // off by one.
     let text = if text.is_empty() {
         hint
     } else if focused {
@@ -93,6 +93,7 @@ pub fn text_paper(context: WidgetContext) -> WidgetNode {
         font,
         color,
         transform,
+        ..Default::default()
     };
 
     widget! {
@@ -6,31 +6,35 @@ pub mod theme;
 use raui_core::application::Application;
 
 pub fn setup(app: &mut Application) {
-    app.register_props::<component::containers::context_paper::ContextPaperProps>(
+    app.register_props_with_default::<component::containers::context_paper::ContextPaperProps>(
         "ContextPaperProps",
     );
-    app.register_props::<component::containers::modal_paper::ModalPaperProps>("ModalPaperProps");
-    app.register_props::<component::containers::paper::PaperProps>("PaperProps");
-    app.register_props::<component::containers::paper::PaperContentLayoutProps>(
+    app.register_props_with_default::<component::containers::modal_paper::ModalPaperProps>(
+        "ModalPaperProps",
+    );
+    app.register_props_with_default::<component::containers::paper::PaperProps>("PaperProps");
+    app.register_props_with_default::<component::containers::paper::PaperContentLayoutProps>(
         "PaperContentLayoutProps",
     );
-    app.register_props::<component::containers::tooltip_paper::TooltipPaperProps>(
+    app.register_props_with_default::<component::containers::tooltip_paper::TooltipPaperProps>(
         "TooltipPaperProps",
     );
-    app.register_props::<component::containers::scroll_paper::SideScrollbarsPaperProps>(
+    app.register_props_with_default::<component::containers::scroll_paper::SideScrollbarsPaperProps>(
         "SideScrollbarsPaperProps",
     );
-    app.register_props::<component::icon_paper::IconPaperProps>("IconPaperProps");
-    app.register_props::<component::interactive::button_paper::ButtonPaperOverrideStyle>(
+    app.register_props_with_default::<component::icon_paper::IconPaperProps>("IconPaperProps");
+    app.register_props_with_default::<component::interactive::button_paper::ButtonPaperOverrideStyle>(
         "ButtonPaperOverrideStyle",
     );
-    app.register_props::<component::interactive::text_field_paper::TextFieldPaperProps>(
+    app.register_props_with_default::<component::interactive::text_field_paper::TextFieldPaperProps>(
         "TextFieldPaperProps",
     );
-    app.register_props::<component::switch_paper::SwitchPaperProps>("SwitchPaperProps");
-    app.register_props::<component::text_paper::TextPaperProps>("TextPaperProps");
-    app.register_props::<theme::ThemedWidgetProps>("ThemedWidgetProps");
-    app.register_props::<theme::ThemeProps>("ThemeProps");
+    app.register_props_with_default::<component::switch_paper::SwitchPaperProps>(
+        "SwitchPaperProps",
+    );
+    app.register_props_with_default::<component::text_paper::TextPaperProps>("TextPaperProps");
+    app.register_props_with_default::<theme::ThemedWidgetProps>("ThemedWidgetProps");
+    app.register_props_with_default::<theme::ThemeProps>("ThemeProps");
 
     app.register_component(
         "context_paper",
@@ -114,8 +118,8 @@ pub mod prelude {
         component::{
             containers::{
                 flex_paper::*, grid_paper::*, horizontal_paper::*, modal_paper::*, paper::*,
-                scroll_paper::*, scroll_paper::*, text_tooltip_paper::*, tooltip_paper::*,
-                vertical_paper::*, wrap_paper::*,
+                scroll_paper::*, text_tooltip_paper::*, tooltip_paper::*, vertical_paper::*,
+                wrap_paper::*,
             },
             icon_paper::*,
             interactive::{
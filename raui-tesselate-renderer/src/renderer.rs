@@ -12,7 +12,7 @@ use raui_core::{
     widget::{
         unit::{
             image::{ImageBoxColor, ImageBoxImage, ImageBoxImageScaling, ImageBoxMaterial},
-            text::TextBox,
+            text::{TextBox, TextBoxFontSizeMode},
             WidgetUnit,
         },
         utils::{lerp, Rect, Transform, Vec2},
@@ -21,6 +21,9 @@ use raui_core::{
 };
 use std::collections::{HashMap, VecDeque};
 
+/// How far past the box clip rect reaches along axis that content box does not clip
+const UNCLIPPED_EXTENT: Scalar = 1.0e6;
+
 fn raui_to_vec2(v: Vec2) -> vek::Vec2<Scalar> {
     vek::Vec2::new(v.x, v.y)
 }
@@ -124,6 +127,16 @@ where
     }
 
     fn push_transform(&mut self, transform: &Transform, rect: Rect) {
+        self.push_matrix(Self::transform_matrix(transform, rect));
+    }
+
+    /// Applies transform around given rectangle without moving origin to its top-left corner.
+    fn push_transform_in_place(&mut self, transform: &Transform, rect: Rect) {
+        let origin = vek::Mat4::<Scalar>::translation_2d(vek::Vec2::new(-rect.left, -rect.top));
+        self.push_matrix(Self::transform_matrix(transform, rect) * origin);
+    }
+
+    fn transform_matrix(transform: &Transform, rect: Rect) -> vek::Mat4<Scalar> {
         let size = rect.size();
         let offset = vek::Vec2::new(rect.left, rect.top);
         let offset = vek::Mat4::<Scalar>::translation_2d(offset);
@@ -147,8 +160,7 @@ where
             transform.skew.x.tan(),
             1.0,
         ));
-        let matrix = offset * align * pivot * translate * rotate * scale * skew * inv_pivot;
-        self.push_matrix(matrix);
+        offset * align * pivot * translate * rotate * scale * skew * inv_pivot
     }
 
     fn push_transform_simple(&mut self, rect: Rect) {
@@ -782,7 +794,7 @@ where
                             let v = self.count(&v.slot, layout);
                             (a.0 + v.0, a.1 + v.1, a.2 + v.2)
                         });
-                    if unit.clipping {
+                    if unit.clips_x() || unit.clips_y() {
                         batches += 2;
                     }
                     (vertices, indices, batches)
@@ -879,24 +891,45 @@ where
             }
             WidgetUnit::ContentBox(unit) => {
                 if let Some(item) = layout.items.get(&unit.id) {
-                    let mut items = unit
-                        .items
-                        .iter()
-                        .map(|item| (item.layout.depth, item))
-                        .collect::<Vec<_>>();
-                    items.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+                    let items = unit.items_in_draw_order();
                     let local_space = mapping.virtual_to_real_rect(item.local_space, local);
                     self.push_transform(&unit.transform, local_space);
-                    if unit.clipping {
+                    let clipping = unit.clips_x() || unit.clips_y();
+                    if clipping {
+                        // axis that is not clipped gets its clip rect stretched far past the box.
+                        let mut box_size = local_space.size();
+                        let mut offset = vek::Vec2::<Scalar>::zero();
+                        if !unit.clips_x() {
+                            box_size.x += UNCLIPPED_EXTENT * 2.0;
+                            offset.x = -UNCLIPPED_EXTENT;
+                        }
+                        if !unit.clips_y() {
+                            box_size.y += UNCLIPPED_EXTENT * 2.0;
+                            offset.y = -UNCLIPPED_EXTENT;
+                        }
+                        let matrix =
+                            self.top_transform() * vek::Mat4::<Scalar>::translation_2d(offset);
                         result.batches.push(Batch::ClipPush(BatchClipRect {
-                            box_size: local_space.size(),
-                            matrix: self.top_transform().into_col_array(),
+                            box_size,
+                            matrix: matrix.into_col_array(),
                         }));
                     }
-                    for (_, item) in items {
+                    for item in items {
+                        let item_space = item
+                            .slot
+                            .as_data()
+                            .and_then(|data| layout.items.get(data.id()))
+                            .map(|child| mapping.virtual_to_real_rect(child.local_space, true))
+                            .filter(|_| item.layout.transform != Transform::default());
+                        if let Some(item_space) = item_space {
+                            self.push_transform_in_place(&item.layout.transform, item_space);
+                        }
                         self.render_node(&item.slot, mapping, layout, result, true)?;
+                        if item_space.is_some() {
+                            self.pop_transform();
+                        }
                     }
-                    if unit.clipping {
+                    if clipping {
                         result.batches.push(Batch::ClipPop);
                     }
                     self.pop_transform();
@@ -974,27 +1007,7 @@ where
                                 .get(&image.id)
                                 .cloned()
                                 .unwrap_or(Vec2 { x: 1.0, y: 1.0 });
-                            let ox = rect.left;
-                            let oy = rect.top;
-                            let iw = rect.width();
-                            let ih = rect.height();
-                            let ra = size.x / size.y;
-                            let ia = iw / ih;
-                            let scale = if (ra >= ia) != aspect.outside {
-                                iw / size.x
-                            } else {
-                                ih / size.y
-                            };
-                            let w = size.x * scale;
-                            let h = size.y * scale;
-                            let ow = lerp(0.0, iw - w, aspect.horizontal_alignment);
-                            let oh = lerp(0.0, ih - h, aspect.vertical_alignment);
-                            Rect {
-                                left: ox + ow,
-                                right: ox + ow + w,
-                                top: oy + oh,
-                                bottom: oy + oh + h,
-                            }
+                            aspect.content_rect(rect, size)
                         } else {
                             rect
                         };
@@ -1010,6 +1023,16 @@ where
             },
             WidgetUnit::TextBox(unit) => {
                 if let Some(item) = layout.items.get(&unit.id) {
+                    // text tesselation engines expect font size in real units.
+                    let scaled;
+                    let unit = if unit.font_size_mode == TextBoxFontSizeMode::Absolute {
+                        unit
+                    } else {
+                        let mut copy = unit.to_owned();
+                        copy.font.size = unit.real_font_size(mapping);
+                        scaled = copy;
+                        &scaled
+                    };
                     let local_space = mapping.virtual_to_real_rect(item.local_space, local);
                     self.push_transform(&unit.transform, local_space);
                     let matrix = self.top_transform().into_col_array();
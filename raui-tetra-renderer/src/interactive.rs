@@ -8,7 +8,7 @@ use raui_core::{
     },
     layout::CoordsMapping,
     widget::{
-        component::interactive::navigation::{NavJump, NavScroll, NavSignal, NavTextChange},
+        component::interactive::navigation::{NavSignal, NavTextChange},
         utils::Vec2,
     },
     Scalar,
@@ -133,9 +133,7 @@ impl TetraInteractionsEngine {
                     y: -self.single_scroll_units.y * amount.y as Scalar,
                 };
                 self.engine
-                    .interact(Interaction::Navigate(NavSignal::Jump(NavJump::Scroll(
-                        NavScroll::Units(value, true),
-                    ))));
+                    .interact(Interaction::PointerScroll(self.pointer_position, value));
             }
             Event::KeyPressed { key } => {
                 if self.engine.focused_text_input().is_some() {
@@ -220,6 +218,7 @@ impl TetraInteractionsEngine {
                         Key::Escape => {
                             self.engine
                                 .interact(Interaction::Navigate(NavSignal::Cancel(true)));
+                            self.engine.interact(Interaction::Navigate(NavSignal::Back));
                         }
                         _ => {}
                     }
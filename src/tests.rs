@@ -420,7 +420,7 @@ fn test_layout_no_wrap() {
         FlexBoxNode {
             id: WidgetId::from_str("type:/list").unwrap(),
             direction: FlexBoxDirection::VerticalTopToBottom,
-            separation: 10.0,
+            separation: 10.0.into(),
             items: vec![
                 FlexBoxItemNode {
                     slot: SizeBoxNode {
@@ -493,7 +493,7 @@ fn test_layout_wrapping() {
         FlexBoxNode {
             id: WidgetId::from_str("type:/list").unwrap(),
             direction: FlexBoxDirection::HorizontalLeftToRight,
-            separation: 10.0,
+            separation: 10.0.into(),
             wrap: true,
             items: vec![
                 FlexBoxItemNode {
@@ -568,7 +568,7 @@ fn test_components() {
     let tree = widget! {
         (#{"app"} vertical_box: {
             VerticalBoxProps {
-                separation: 10.0,
+                separation: 10.0.into(),
                 ..Default::default()
             }
         } [